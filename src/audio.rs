@@ -0,0 +1,101 @@
+use crate::{AudioPricing, Model, Pricing, PricingError};
+use std::time::Duration;
+
+const SECONDS_PER_MINUTE: f64 = 60.0;
+const CHARACTERS_PER_1K: f64 = 1_000.0;
+
+// ------------------
+// Audio costs
+// ------------------
+
+impl AudioPricing {
+    /// Provider cost of transcribing `duration` of audio, billed to the
+    /// fraction of a minute.
+    pub fn audio_cost(&self, duration: Duration) -> Result<f64, PricingError> {
+        let per_minute = self.transcription_per_minute.ok_or_else(|| {
+            PricingError::Validation("audio pricing has no transcription price".to_string())
+        })?;
+        Ok(duration.as_secs_f64() / SECONDS_PER_MINUTE * per_minute)
+    }
+
+    /// Provider cost of synthesizing speech from `chars` characters of text.
+    pub fn tts_cost(&self, chars: u64) -> Result<f64, PricingError> {
+        let per1_k = self.tts_per1_k_characters.ok_or_else(|| {
+            PricingError::Validation("audio pricing has no text-to-speech price".to_string())
+        })?;
+        Ok(chars as f64 / CHARACTERS_PER_1K * per1_k)
+    }
+}
+
+impl Model {
+    fn audio_pricing(&self) -> Result<&AudioPricing, PricingError> {
+        match &self.pricing {
+            Some(Pricing::AudioPricing(audio)) => Ok(audio),
+            _ => Err(PricingError::Validation(format!(
+                "model {} has no audio pricing",
+                self.key
+            ))),
+        }
+    }
+
+    /// Provider cost of transcribing `duration` of audio with this model.
+    pub fn audio_cost(&self, duration: Duration) -> Result<f64, PricingError> {
+        self.audio_pricing()?.audio_cost(duration)
+    }
+
+    /// Provider cost of synthesizing `chars` characters with this model.
+    pub fn tts_cost(&self, chars: u64) -> Result<f64, PricingError> {
+        self.audio_pricing()?.tts_cost(chars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::ModelType;
+    use serde_json::json;
+
+    fn whisper() -> Model {
+        serde_json::from_value(json!({
+            "added": "2023-03-01",
+            "created": "2022-09-21",
+            "key": "whisper-1",
+            "type": "audio",
+            "pricing": { "transcriptionPerMinute": 0.006 }
+        }))
+        .expect("Failed to parse")
+    }
+
+    #[test]
+    fn test_audio_pricing_deserializes() {
+        let model = whisper();
+        assert_eq!(model.model_type, ModelType::Audio);
+        let Some(Pricing::AudioPricing(audio)) = &model.pricing else {
+            panic!("whisper-1 should have audio pricing");
+        };
+        assert_eq!(audio.transcription_per_minute, Some(0.006));
+        assert_eq!(audio.tts_per1_k_characters, None);
+
+        let json = serde_json::to_value(&model).unwrap();
+        assert_eq!(json["pricing"], json!({ "transcriptionPerMinute": 0.006 }));
+    }
+
+    #[test]
+    fn test_audio_costs() {
+        let model = whisper();
+        let cost = model.audio_cost(Duration::from_secs(90)).unwrap();
+        assert!((cost - 0.009).abs() < 1e-12);
+        assert!(model.tts_cost(1_000).is_err());
+
+        let tts = AudioPricing {
+            tts_per1_k_characters: Some(0.015),
+            ..Default::default()
+        };
+        assert!((tts.tts_cost(2_000).unwrap() - 0.03).abs() < 1e-12);
+
+        let pricing = pricing();
+        let gpt = pricing.find_model("openai", "gpt-4o").unwrap();
+        assert!(gpt.audio_cost(Duration::from_secs(60)).is_err());
+    }
+}
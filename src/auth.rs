@@ -0,0 +1,113 @@
+use crate::{AuthScheme, ProviderAuth};
+
+// ------------------
+// Provider auth
+// ------------------
+
+impl ProviderAuth {
+    /// The first of [`env_vars`](Self::env_vars) that is set and non-empty,
+    /// with its value.
+    pub fn credential_from_env(&self) -> Option<(&str, String)> {
+        self.credential_from(|name| std::env::var(name).ok())
+    }
+
+    /// [`credential_from_env`](Self::credential_from_env), reading variables
+    /// with `lookup` instead of from the process environment.
+    pub fn credential_from(
+        &self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Option<(&str, String)> {
+        self.env_vars.iter().find_map(|name| {
+            let value = lookup(name).filter(|value| !value.is_empty())?;
+            Some((name.as_str(), value))
+        })
+    }
+
+    /// Every header a request needs, given the `credential`: the required
+    /// headers plus the one carrying the credential. AWS SigV4 signs requests
+    /// instead, so adds no credential header.
+    pub fn headers(&self, credential: &str) -> Vec<(String, String)> {
+        let mut headers: Vec<_> = self
+            .required_headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        match (&self.scheme, &self.header) {
+            (AuthScheme::Bearer, _) => headers.push((
+                "authorization".to_string(),
+                format!("Bearer {}", credential),
+            )),
+            (AuthScheme::Header, Some(header)) => {
+                headers.push((header.clone(), credential.to_string()))
+            }
+            _ => {}
+        }
+        headers
+    }
+}
+
+/// Whether `name` looks like an environment variable name rather than, say, a
+/// pasted key: ASCII letters, digits, and underscores, not starting with a
+/// digit.
+pub(crate) fn is_env_var_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn anthropic() -> ProviderAuth {
+        serde_json::from_value(json!({
+            "scheme": "header",
+            "envVars": ["ANTHROPIC_API_KEY", "CLAUDE_API_KEY"],
+            "header": "x-api-key",
+            "requiredHeaders": { "anthropic-version": "2023-06-01" }
+        }))
+        .expect("Failed to parse")
+    }
+
+    #[test]
+    fn test_credential_from() {
+        let auth = anthropic();
+        let env = |name: &str| match name {
+            "ANTHROPIC_API_KEY" => Some(String::new()),
+            "CLAUDE_API_KEY" => Some("key".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            auth.credential_from(env),
+            Some(("CLAUDE_API_KEY", "key".to_string()))
+        );
+        assert_eq!(auth.credential_from(|_| None), None);
+    }
+
+    #[test]
+    fn test_headers() {
+        let mut auth = anthropic();
+        assert_eq!(
+            auth.headers("key"),
+            [
+                ("anthropic-version".to_string(), "2023-06-01".to_string()),
+                ("x-api-key".to_string(), "key".to_string()),
+            ]
+        );
+
+        auth.scheme = AuthScheme::Bearer;
+        assert_eq!(auth.headers("key")[1].1, "Bearer key");
+        auth.scheme = AuthScheme::from("oauth".to_string());
+        assert_eq!(auth.headers("key").len(), 1);
+        assert_eq!(serde_json::to_value(&auth).unwrap()["scheme"], "oauth");
+    }
+
+    #[test]
+    fn test_is_env_var_name() {
+        assert!(is_env_var_name("OPENAI_API_KEY"));
+        assert!(!is_env_var_name("sk-proj-abc123"));
+        assert!(!is_env_var_name("1KEY"));
+        assert!(!is_env_var_name(""));
+    }
+}
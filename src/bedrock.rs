@@ -0,0 +1,246 @@
+use crate::{AiPricingJson, Model, Provider};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+// Cross-region inference profile IDs start with the geography they route
+// within, e.g. `us.anthropic.claude-3-5-sonnet-20241022-v2:0`.
+const GEOGRAPHIES: [&str; 4] = ["us-gov", "us", "eu", "apac"];
+
+// ------------------
+// Inference profile ARNs
+// ------------------
+
+/// A parsed Bedrock inference profile ARN, such as
+/// `arn:aws:bedrock:us-east-1:123456789012:inference-profile/us.anthropic.claude-3-5-sonnet-20241022-v2:0`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InferenceProfileArn {
+    pub partition: String,
+    pub region: String,
+    /// The owning account; `None` for AWS-managed resources, whose ARNs leave
+    /// it blank.
+    pub account: Option<String>,
+    /// `inference-profile` for system-defined profiles or
+    /// `application-inference-profile` for ones created in an account.
+    pub resource_type: String,
+    /// The profile's ID, e.g. `us.anthropic.claude-3-5-sonnet-20241022-v2:0`.
+    pub profile_id: String,
+}
+
+impl FromStr for InferenceProfileArn {
+    type Err = ParseArnError;
+
+    fn from_str(arn: &str) -> Result<Self, Self::Err> {
+        let error = || ParseArnError(arn.to_string());
+        // The profile ID itself may contain colons, as in `v2:0`.
+        let mut parts = arn.trim().splitn(6, ':');
+        let mut next = || parts.next().ok_or_else(error);
+        let (prefix, partition, service, region, account, resource) =
+            (next()?, next()?, next()?, next()?, next()?, next()?);
+        if prefix != "arn" || service != "bedrock" || region.is_empty() {
+            return Err(error());
+        }
+        let (resource_type, profile_id) = resource.split_once('/').ok_or_else(error)?;
+        if !resource_type.ends_with("inference-profile") || profile_id.is_empty() {
+            return Err(error());
+        }
+
+        Ok(InferenceProfileArn {
+            partition: partition.to_string(),
+            region: region.to_string(),
+            account: (!account.is_empty()).then(|| account.to_string()),
+            resource_type: resource_type.to_string(),
+            profile_id: profile_id.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for InferenceProfileArn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "arn:{}:bedrock:{}:{}:{}/{}",
+            self.partition,
+            self.region,
+            self.account.as_deref().unwrap_or_default(),
+            self.resource_type,
+            self.profile_id
+        )
+    }
+}
+
+/// Returned when parsing a string that isn't a Bedrock inference profile ARN.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid inference profile ARN {0:?}")]
+pub struct ParseArnError(pub String);
+
+/// The cross-region inference geography serving an AWS region, e.g. `eu` for
+/// `eu-west-1`. `None` for regions without cross-region profiles.
+pub fn inference_geography(region: &str) -> Option<&'static str> {
+    let prefix = region.split('-').next()?;
+    match prefix {
+        "us" if region.starts_with("us-gov-") => Some("us-gov"),
+        "us" => Some("us"),
+        "eu" => Some("eu"),
+        "ap" => Some("apac"),
+        _ => None,
+    }
+}
+
+/// `id` without a leading geography, e.g. `anthropic.claude-3-5-sonnet-20241022-v2:0`
+/// for `us.anthropic.claude-3-5-sonnet-20241022-v2:0`.
+fn strip_geography(id: &str) -> &str {
+    GEOGRAPHIES
+        .iter()
+        .find_map(|geography| id.strip_prefix(geography)?.strip_prefix('.'))
+        .unwrap_or(id)
+}
+
+// ------------------
+// Model helpers
+// ------------------
+
+impl Model {
+    /// The model's parsed `inference_profile_arn`, if it has a valid one.
+    pub fn inference_profile(&self) -> Option<InferenceProfileArn> {
+        self.inference_profile_arn.as_deref()?.parse().ok()
+    }
+
+    /// The cross-region inference profile ID to call the model with from
+    /// `region`, e.g. `eu.anthropic.claude-3-5-sonnet-20241022-v2:0` from
+    /// `eu-west-1`. `None` if the model isn't served through inference
+    /// profiles or the region has no cross-region geography.
+    pub fn inference_profile_for_region(&self, region: &str) -> Option<String> {
+        let profile_id = self.profile_id()?;
+        let geography = inference_geography(region)?;
+        Some(format!("{}.{}", geography, strip_geography(&profile_id)))
+    }
+
+    /// Whether the model is the one called with `id`: its `model_id`, its
+    /// inference profile ID or ARN, or its profile in another geography.
+    pub fn has_bedrock_id(&self, id: &str) -> bool {
+        let id = match id.parse::<InferenceProfileArn>() {
+            Ok(arn) => arn.profile_id,
+            Err(_) => id.to_string(),
+        };
+        let base = strip_geography(&id);
+        let matches = |known: &str| known == id || strip_geography(known) == base;
+
+        self.model_id.as_deref().is_some_and(matches)
+            || self
+                .profile_id()
+                .is_some_and(|profile_id| matches(&profile_id))
+    }
+
+    fn profile_id(&self) -> Option<String> {
+        match &self.inference_profile_id {
+            Some(id) => Some(id.clone()),
+            None => Some(self.inference_profile()?.profile_id),
+        }
+    }
+}
+
+impl AiPricingJson {
+    /// Find the first model called with `id`, by `model_id` or inference
+    /// profile. See [`Model::has_bedrock_id`].
+    pub fn find_model_by_bedrock_id(&self, id: &str) -> Option<(&Provider, &Model)> {
+        self.all_models()
+            .find(|(_, model)| model.has_bedrock_id(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    const ARN: &str = "arn:aws:bedrock:us-east-1:123456789012:inference-profile/us.anthropic.claude-3-5-sonnet-20241022-v2:0";
+
+    #[test]
+    fn test_parse_arn() {
+        let arn: InferenceProfileArn = ARN.parse().unwrap();
+        assert_eq!(arn.region, "us-east-1");
+        assert_eq!(arn.account.as_deref(), Some("123456789012"));
+        assert_eq!(arn.resource_type, "inference-profile");
+        assert_eq!(
+            arn.profile_id,
+            "us.anthropic.claude-3-5-sonnet-20241022-v2:0"
+        );
+        assert_eq!(arn.to_string(), ARN);
+
+        let managed: InferenceProfileArn =
+            "arn:aws:bedrock:eu-west-1::inference-profile/eu.meta.llama3-2-1b-instruct-v1:0"
+                .parse()
+                .unwrap();
+        assert_eq!(managed.account, None);
+
+        for bad in [
+            "us.anthropic.claude-3-5-sonnet-20241022-v2:0",
+            "arn:aws:s3:us-east-1:123456789012:inference-profile/x",
+            "arn:aws:bedrock:us-east-1::foundation-model/anthropic.claude-v2",
+            "arn:aws:bedrock:us-east-1:123456789012",
+        ] {
+            assert_eq!(
+                bad.parse::<InferenceProfileArn>(),
+                Err(ParseArnError(bad.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_inference_profile_for_region() {
+        let mut pricing = pricing();
+        let bedrock = &mut pricing.providers[1];
+        let sonnet = &bedrock.models[0];
+        assert_eq!(
+            sonnet
+                .inference_profile_for_region("eu-central-1")
+                .as_deref(),
+            Some("eu.anthropic.claude-3-5-sonnet-20241022-v2:0")
+        );
+        assert_eq!(
+            sonnet
+                .inference_profile_for_region("ap-northeast-1")
+                .as_deref(),
+            Some("apac.anthropic.claude-3-5-sonnet-20241022-v2:0")
+        );
+        assert_eq!(sonnet.inference_profile_for_region("sa-east-1"), None);
+        assert_eq!(
+            bedrock.models[1].inference_profile_for_region("us-east-1"),
+            None
+        );
+
+        bedrock.models[1].inference_profile_arn = Some(ARN.to_string());
+        assert_eq!(
+            bedrock.models[1]
+                .inference_profile_for_region("us-gov-west-1")
+                .as_deref(),
+            Some("us-gov.anthropic.claude-3-5-sonnet-20241022-v2:0")
+        );
+    }
+
+    #[test]
+    fn test_find_model_by_bedrock_id() {
+        let pricing = pricing();
+        let key = |id| {
+            pricing
+                .find_model_by_bedrock_id(id)
+                .map(|(_, m)| m.key.as_str())
+        };
+
+        assert_eq!(
+            key("anthropic.claude-3-sonnet-20240229-v1:0"),
+            Some("claude-3-sonnet")
+        );
+        assert_eq!(
+            key("us.anthropic.claude-3-5-sonnet-20241022-v2:0"),
+            Some("claude-3-5-sonnet")
+        );
+        assert_eq!(
+            key("eu.anthropic.claude-3-5-sonnet-20241022-v2:0"),
+            Some("claude-3-5-sonnet")
+        );
+        assert_eq!(key(ARN), Some("claude-3-5-sonnet"));
+        assert_eq!(key("anthropic.claude-v2"), None);
+    }
+}
@@ -0,0 +1,205 @@
+#![allow(deprecated)]
+
+use crate::{AiPricingJson, CostBreakdown, ImageDetail, Model, PricingError, Provider, TokenUsage};
+use std::time::SystemTime;
+
+// ------------------
+// Customer prices
+// ------------------
+
+// The `Provider` methods these wrap can't see the file's promotions or
+// rounding policy, so these are the ones to bill from.
+impl AiPricingJson {
+    /// Provider cost and customer price of a text request to a model, as for
+    /// [`Provider::text_cost`] less promotions running now, then
+    /// rounded with [`rounding`](AiPricingJson::rounding).
+    pub fn text_cost(
+        &self,
+        provider_key: &str,
+        model_key: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cached_input_tokens: u64,
+    ) -> Result<CostBreakdown, PricingError> {
+        let (provider, model) = self.provider_model(provider_key, model_key)?;
+        let breakdown =
+            provider.text_cost(model_key, input_tokens, output_tokens, cached_input_tokens)?;
+        Ok(self.billed(model, breakdown))
+    }
+
+    /// Provider cost and customer price of generating `n` images, as for
+    /// [`Provider::image_cost`] less promotions running now, then
+    /// rounded with [`rounding`](AiPricingJson::rounding).
+    pub fn image_cost(
+        &self,
+        provider_key: &str,
+        model_key: &str,
+        size: &str,
+        quality: Option<&str>,
+        n: u32,
+    ) -> Result<CostBreakdown, PricingError> {
+        let (provider, model) = self.provider_model(provider_key, model_key)?;
+        let breakdown = provider.image_cost(model_key, size, quality, n)?;
+        Ok(self.billed(model, breakdown))
+    }
+
+    /// Provider cost and customer price of a text request served from
+    /// `region`, as for [`Provider::text_cost_in_region`] less promotions
+    /// running now, then rounded with [`rounding`](AiPricingJson::rounding).
+    pub fn text_cost_in_region(
+        &self,
+        provider_key: &str,
+        model_key: &str,
+        region: &str,
+        usage: &TokenUsage,
+    ) -> Result<CostBreakdown, PricingError> {
+        let (provider, model) = self.provider_model(provider_key, model_key)?;
+        let breakdown = provider.text_cost_in_region(model_key, region, usage)?;
+        Ok(self.billed(model, breakdown))
+    }
+
+    /// Provider cost and customer price of a text request to the deployment
+    /// `name`, as for [`Provider::deployment_text_cost`] less promotions
+    /// running now, then rounded with [`rounding`](AiPricingJson::rounding).
+    pub fn deployment_text_cost(
+        &self,
+        provider_key: &str,
+        name: &str,
+        usage: &TokenUsage,
+    ) -> Result<CostBreakdown, PricingError> {
+        let model = self.resolve_deployment(provider_key, name)?;
+        let breakdown = self
+            .provider(provider_key)?
+            .deployment_text_cost(name, usage)?;
+        Ok(self.billed(model, breakdown))
+    }
+
+    /// Provider cost and customer price of sending one image to a vision
+    /// model, as for [`Provider::vision_input_cost`] less promotions running
+    /// now, then rounded with [`rounding`](AiPricingJson::rounding).
+    pub fn vision_input_cost(
+        &self,
+        provider_key: &str,
+        model_key: &str,
+        width: u32,
+        height: u32,
+        detail: ImageDetail,
+    ) -> Result<CostBreakdown, PricingError> {
+        let (provider, model) = self.provider_model(provider_key, model_key)?;
+        let breakdown = provider.vision_input_cost(model_key, width, height, detail)?;
+        Ok(self.billed(model, breakdown))
+    }
+
+    /// `breakdown`, a marked-up charge for `model`, as the customer is billed
+    /// for it now: less running promotions, then rounded with
+    /// [`rounding`](AiPricingJson::rounding). Only the customer price is
+    /// rounded; the provider cost stays exact.
+    pub(crate) fn billed(&self, model: &Model, breakdown: CostBreakdown) -> CostBreakdown {
+        let discounted = self.apply_promotions(model, breakdown, SystemTime::now());
+        CostBreakdown::new(
+            discounted.provider_cost,
+            self.rounding.round(discounted.customer_price),
+        )
+    }
+
+    fn provider(&self, provider_key: &str) -> Result<&Provider, PricingError> {
+        self.find_provider(provider_key)
+            .ok_or_else(|| PricingError::Validation(format!("no provider {}", provider_key)))
+    }
+
+    fn provider_model(
+        &self,
+        provider_key: &str,
+        model_key: &str,
+    ) -> Result<(&Provider, &Model), PricingError> {
+        let provider = self.provider(provider_key)?;
+        let model = provider.find_model(model_key).ok_or_else(|| {
+            PricingError::Validation(format!(
+                "provider {} has no model {}",
+                provider_key, model_key
+            ))
+        })?;
+        Ok((provider, model))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::RoundingPolicy;
+    use serde_json::json;
+
+    fn with_promotion() -> AiPricingJson {
+        let mut pricing = pricing();
+        pricing.promotions = serde_json::from_value(json!([{
+            "id": "half-off",
+            "discount": { "type": "percentage", "value": 50.0 },
+            "starts": "2000-01-01"
+        }]))
+        .expect("Failed to parse");
+        pricing
+    }
+
+    #[test]
+    fn test_customer_prices_apply_promotions() {
+        let pricing = with_promotion();
+
+        let text = pricing
+            .text_cost("openai", "gpt-4o", 1_000_000, 0, 0)
+            .unwrap();
+        assert_eq!(text.provider_cost, 2.5);
+        assert!((text.customer_price - 1.5).abs() < 1e-12);
+
+        let image = pricing
+            .image_cost("openai", "dall-e-3", "1024x1024", None, 1)
+            .unwrap();
+        assert!((image.customer_price - 0.03).abs() < 1e-12);
+
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+        let regional = pricing
+            .text_cost_in_region("bedrock", "claude-3-5-sonnet", "us-east-1", &usage)
+            .unwrap();
+        let listed = pricing.providers[1]
+            .text_cost("claude-3-5-sonnet", 1_000_000, 0, 0)
+            .unwrap();
+        assert!((regional.customer_price - listed.customer_price / 2.0).abs() < 1e-12);
+
+        let vision = pricing
+            .vision_input_cost("openai", "gpt-4o", 1024, 1024, ImageDetail::High)
+            .unwrap();
+        let listed = pricing.providers[0]
+            .vision_input_cost("gpt-4o", 1024, 1024, ImageDetail::High)
+            .unwrap();
+        assert!((vision.customer_price - listed.customer_price / 2.0).abs() < 1e-12);
+
+        assert!(pricing.text_cost("azure", "gpt-4o", 1, 1, 0).is_err());
+        assert!(pricing.text_cost("openai", "gpt-5", 1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_customer_prices_are_rounded() {
+        let mut rounded = with_promotion();
+        rounded.rounding = RoundingPolicy::CeilToCent;
+
+        // 1.5 per 1M tokens, halved to 0.75, is 0.00000075 for one token.
+        let text = rounded.text_cost("openai", "gpt-4o", 1, 0, 0).unwrap();
+        assert_eq!(text.customer_price, 0.01);
+        assert_eq!(text.provider_cost, 0.0000025);
+
+        let image = rounded
+            .image_cost("openai", "dall-e-3", "1024x1024", None, 1)
+            .unwrap();
+        assert_eq!(image.customer_price, 0.03);
+
+        let json = serde_json::to_value(&rounded).unwrap();
+        assert_eq!(json["rounding"], "ceilToCent");
+        assert!(serde_json::to_value(pricing())
+            .unwrap()
+            .get("rounding")
+            .is_none());
+    }
+}
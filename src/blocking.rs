@@ -0,0 +1,68 @@
+use crate::overrides::Overrides;
+use crate::parse::parse;
+use crate::{AiPricingJson, Env, PricingError, DEFAULT_BASE_URL};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use reqwest::blocking::Client;
+use std::sync::Arc;
+
+// ------------------
+// Blocking fetch
+// ------------------
+
+static BLOCKING_CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// Cached pricing data, keyed by environment.
+static BLOCKING_CACHE: Lazy<DashMap<Env, Arc<AiPricingJson>>> = Lazy::new(DashMap::new);
+
+/// Synchronous counterpart to [`get_ai_pricing`](crate::get_ai_pricing), for
+/// CLI tools and batch jobs that don't otherwise run an async runtime.
+///
+/// Each environment is fetched once per process and cached. Setting
+/// `AI_PRICING_URL` fetches that URL instead. This must not be called from
+/// within an async runtime; use the async API there instead.
+pub fn get_ai_pricing_blocking(env: Env) -> Result<Arc<AiPricingJson>, PricingError> {
+    if let Some(cached) = BLOCKING_CACHE.get(&env) {
+        return Ok(Arc::clone(&cached));
+    }
+
+    let url = match Overrides::from_process().url {
+        Some(url) => url,
+        None => env.pricing_url(DEFAULT_BASE_URL),
+    };
+    let data = Arc::new(fetch_blocking(&url)?);
+    BLOCKING_CACHE.insert(env, Arc::clone(&data));
+
+    Ok(data)
+}
+
+fn fetch_blocking(url: &str) -> Result<AiPricingJson, PricingError> {
+    let request_error = |source| PricingError::Request {
+        source,
+        url: url.to_string(),
+    };
+
+    let resp = BLOCKING_CLIENT.get(url).send().map_err(request_error)?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(PricingError::Http {
+            status,
+            url: url.to_string(),
+        });
+    }
+
+    let body = resp.bytes().map_err(request_error)?;
+    parse(&body, url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_blocking_reports_connection_errors() {
+        // Nothing listens on port 1, so the request fails to connect.
+        let result = fetch_blocking("http://127.0.0.1:1/ai-pricing.json");
+        assert!(matches!(result, Err(PricingError::Request { .. })));
+    }
+}
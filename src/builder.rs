@@ -0,0 +1,561 @@
+use crate::{
+    AiPricingJson, Deployment, Encoder, FineTunePricing, Markup, MarkupOverlay, MarkupOverride,
+    Model, ModelFeature, ModelStatus, ModelType, ModerationOverride, ModerationThreshold, Pricing,
+    PricingError, ProdPriceIds, Promotion, Provider, ProviderAuth, RateLimits, RoundingPolicy,
+    TextPricing, SCHEMA_VERSION,
+};
+use std::collections::BTreeMap;
+
+// ------------------
+// Builders
+// ------------------
+
+impl AiPricingJson {
+    /// Build pricing data in code, e.g. in a generation pipeline or a test.
+    /// [`build`](AiPricingJsonBuilder::build) fails if `metered_price_id` is
+    /// missing.
+    pub fn builder() -> AiPricingJsonBuilder {
+        AiPricingJsonBuilder::default()
+    }
+}
+
+impl Provider {
+    /// Build a provider in code. [`build`](ProviderBuilder::build) fails if
+    /// `key`, `label`, or `markup` is missing.
+    pub fn builder() -> ProviderBuilder {
+        ProviderBuilder::default()
+    }
+}
+
+impl Model {
+    /// Build a model in code. [`build`](ModelBuilder::build) fails if `key`,
+    /// `model_type`, or `added` is missing.
+    pub fn builder() -> ModelBuilder {
+        ModelBuilder::default()
+    }
+}
+
+impl TextPricing {
+    /// Text pricing with just input and output prices per 1M tokens. The
+    /// per-1K prices are derived, as when deserializing.
+    pub fn from_per1_m(input_per1_m: f64, output_per1_m: f64) -> Self {
+        TextPricing {
+            cached_input_per1_k: None,
+            cached_input_per1_m: None,
+            reasoning_output_per1_k: None,
+            reasoning_output_per1_m: None,
+            cache_write_per1_k: None,
+            cache_write_per1_m: None,
+            cache_write_per1_m_by_ttl: Default::default(),
+            batch_input_per1_k: None,
+            batch_input_per1_m: None,
+            batch_output_per1_k: None,
+            batch_output_per1_m: None,
+            image_input_per1_k: None,
+            image_input_per1_m: None,
+            tiers: vec![],
+            input_per1_k: input_per1_m / 1_000.0,
+            input_per1_m,
+            output_per1_k: output_per1_m / 1_000.0,
+            output_per1_m,
+            extra: Default::default(),
+        }
+    }
+}
+
+fn required<T>(value: Option<T>, what: &str, field: &str) -> Result<T, PricingError> {
+    value.ok_or_else(|| PricingError::Validation(format!("{} is missing {}", what, field)))
+}
+
+/// Builds an [`AiPricingJson`]. See [`AiPricingJson::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct AiPricingJsonBuilder {
+    metered_price_id: Option<String>,
+    providers: Vec<Provider>,
+    currency: Option<String>,
+    markup_overlay: MarkupOverlay,
+    promotions: Vec<Promotion>,
+    rounding: RoundingPolicy,
+}
+
+impl AiPricingJsonBuilder {
+    pub fn metered_price_id(mut self, metered_price_id: impl Into<String>) -> Self {
+        self.metered_price_id = Some(metered_price_id.into());
+        self
+    }
+
+    /// Add a provider, after any added before.
+    pub fn provider(mut self, provider: Provider) -> Self {
+        self.providers.push(provider);
+        self
+    }
+
+    /// ISO 4217 code the prices are in. Defaults to
+    /// [`DEFAULT_CURRENCY`](crate::DEFAULT_CURRENCY).
+    pub fn currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = Some(currency.into());
+        self
+    }
+
+    pub fn markup_overlay(mut self, markup_overlay: MarkupOverlay) -> Self {
+        self.markup_overlay = markup_overlay;
+        self
+    }
+
+    pub fn promotion(mut self, promotion: Promotion) -> Self {
+        self.promotions.push(promotion);
+        self
+    }
+
+    /// How the cost APIs round customer charges. Defaults to
+    /// [`RoundingPolicy::Exact`].
+    pub fn rounding(mut self, rounding: RoundingPolicy) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// The pricing data, at the current [`SCHEMA_VERSION`].
+    pub fn build(self) -> Result<AiPricingJson, PricingError> {
+        Ok(AiPricingJson {
+            schema_version: SCHEMA_VERSION,
+            metered_price_id: required(self.metered_price_id, "pricing", "meteredPriceId")?,
+            providers: self.providers,
+            currency: self.currency,
+            markup_overlay: self.markup_overlay,
+            promotions: self.promotions,
+            rounding: self.rounding,
+            extra: Default::default(),
+        })
+    }
+}
+
+/// Builds a [`Provider`]. See [`Provider::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct ProviderBuilder {
+    key: Option<String>,
+    label: Option<String>,
+    description: Option<String>,
+    markup: Option<Markup>,
+    models: Vec<Model>,
+    moderation_threshold: Option<ModerationThreshold>,
+    provider_host: String,
+    website: String,
+    rate_limits: Option<RateLimits>,
+    auth: Option<ProviderAuth>,
+    deployments: Vec<Deployment>,
+}
+
+impl ProviderBuilder {
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Defaults to the label.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Markup percentages on image and text costs, e.g. `20.0` for 20%.
+    pub fn markup(mut self, image_percentage: f64, text_percentage: f64) -> Self {
+        self.markup = Some(Markup {
+            image_percentage,
+            text_percentage,
+        });
+        self
+    }
+
+    /// Add a model, after any added before.
+    pub fn model(mut self, model: Model) -> Self {
+        self.models.push(model);
+        self
+    }
+
+    /// Defaults to [`ModerationThreshold::default`].
+    pub fn moderation_threshold(mut self, moderation_threshold: ModerationThreshold) -> Self {
+        self.moderation_threshold = Some(moderation_threshold);
+        self
+    }
+
+    pub fn provider_host(mut self, provider_host: impl Into<String>) -> Self {
+        self.provider_host = provider_host.into();
+        self
+    }
+
+    pub fn website(mut self, website: impl Into<String>) -> Self {
+        self.website = website.into();
+        self
+    }
+
+    pub fn rate_limits(mut self, rate_limits: RateLimits) -> Self {
+        self.rate_limits = Some(rate_limits);
+        self
+    }
+
+    pub fn auth(mut self, auth: ProviderAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    pub fn deployment(mut self, deployment: Deployment) -> Self {
+        self.deployments.push(deployment);
+        self
+    }
+
+    pub fn build(self) -> Result<Provider, PricingError> {
+        let key = required(self.key, "provider", "key")?;
+        let what = format!("provider {}", key);
+        let label = required(self.label, &what, "label")?;
+        Ok(Provider {
+            description: self.description.unwrap_or_else(|| label.clone()),
+            markup: required(self.markup, &what, "markup")?,
+            key,
+            label,
+            models: self.models,
+            moderation_threshold: self.moderation_threshold.unwrap_or_default(),
+            provider_host: self.provider_host,
+            website: self.website,
+            rate_limits: self.rate_limits,
+            auth: self.auth,
+            deployments: self.deployments,
+            extra: Default::default(),
+        })
+    }
+}
+
+/// Builds a [`Model`]. See [`Model::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct ModelBuilder {
+    key: Option<String>,
+    model_type: Option<ModelType>,
+    added: Option<String>,
+    created: Option<String>,
+    features: Vec<ModelFeature>,
+    aliases: Vec<String>,
+    model_id: Option<String>,
+    inference_profile_arn: Option<String>,
+    inference_profile_id: Option<String>,
+    pricing: Option<Pricing>,
+    regions: BTreeMap<String, TextPricing>,
+    streaming: Option<bool>,
+    system_disabled: Option<bool>,
+    deprecated: Option<bool>,
+    status: Option<ModelStatus>,
+    deprecation_date: Option<String>,
+    replacement_model_key: Option<String>,
+    encoder: Option<Encoder>,
+    context_window: Option<u64>,
+    max_output_tokens: Option<u64>,
+    prod_price_ids: Option<ProdPriceIds>,
+    moderation_threshold: Option<ModerationOverride>,
+    markup: Option<MarkupOverride>,
+    rate_limits: Option<RateLimits>,
+    fine_tuning: Option<FineTunePricing>,
+    per_request: Option<f64>,
+    tool_pricing: BTreeMap<String, f64>,
+}
+
+impl ModelBuilder {
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn model_type(mut self, model_type: ModelType) -> Self {
+        self.model_type = Some(model_type);
+        self
+    }
+
+    /// When the model was added to the pricing file, e.g. `2024-05-13`.
+    pub fn added(mut self, added: impl Into<String>) -> Self {
+        self.added = Some(added.into());
+        self
+    }
+
+    /// When the provider released the model. Defaults to `added`.
+    pub fn created(mut self, created: impl Into<String>) -> Self {
+        self.created = Some(created.into());
+        self
+    }
+
+    pub fn feature(mut self, feature: ModelFeature) -> Self {
+        self.features.push(feature);
+        self
+    }
+
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    pub fn model_id(mut self, model_id: impl Into<String>) -> Self {
+        self.model_id = Some(model_id.into());
+        self
+    }
+
+    pub fn inference_profile_arn(mut self, inference_profile_arn: impl Into<String>) -> Self {
+        self.inference_profile_arn = Some(inference_profile_arn.into());
+        self
+    }
+
+    pub fn inference_profile_id(mut self, inference_profile_id: impl Into<String>) -> Self {
+        self.inference_profile_id = Some(inference_profile_id.into());
+        self
+    }
+
+    pub fn pricing(mut self, pricing: Pricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    /// Text pricing with just input and output prices per 1M tokens. See
+    /// [`TextPricing::from_per1_m`].
+    pub fn text_pricing(self, input_per1_m: f64, output_per1_m: f64) -> Self {
+        self.pricing(Pricing::TextPricing(TextPricing::from_per1_m(
+            input_per1_m,
+            output_per1_m,
+        )))
+    }
+
+    /// Text pricing for `region`, in place of the model's own pricing there.
+    pub fn region(mut self, region: impl Into<String>, pricing: TextPricing) -> Self {
+        self.regions.insert(region.into(), pricing);
+        self
+    }
+
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = Some(streaming);
+        self
+    }
+
+    pub fn system_disabled(mut self, system_disabled: bool) -> Self {
+        self.system_disabled = Some(system_disabled);
+        self
+    }
+
+    pub fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = Some(deprecated);
+        self
+    }
+
+    pub fn status(mut self, status: ModelStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// When the model stops being served, e.g. `2025-06-30`.
+    pub fn deprecation_date(mut self, deprecation_date: impl Into<String>) -> Self {
+        self.deprecation_date = Some(deprecation_date.into());
+        self
+    }
+
+    pub fn replacement_model_key(mut self, replacement_model_key: impl Into<String>) -> Self {
+        self.replacement_model_key = Some(replacement_model_key.into());
+        self
+    }
+
+    pub fn encoder(mut self, encoder: Encoder) -> Self {
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn context_window(mut self, context_window: u64) -> Self {
+        self.context_window = Some(context_window);
+        self
+    }
+
+    pub fn max_output_tokens(mut self, max_output_tokens: u64) -> Self {
+        self.max_output_tokens = Some(max_output_tokens);
+        self
+    }
+
+    pub fn prod_price_ids(mut self, prod_price_ids: ProdPriceIds) -> Self {
+        self.prod_price_ids = Some(prod_price_ids);
+        self
+    }
+
+    pub fn moderation_threshold(mut self, moderation_threshold: ModerationOverride) -> Self {
+        self.moderation_threshold = Some(moderation_threshold);
+        self
+    }
+
+    pub fn markup(mut self, markup: MarkupOverride) -> Self {
+        self.markup = Some(markup);
+        self
+    }
+
+    pub fn rate_limits(mut self, rate_limits: RateLimits) -> Self {
+        self.rate_limits = Some(rate_limits);
+        self
+    }
+
+    pub fn fine_tuning(mut self, fine_tuning: FineTunePricing) -> Self {
+        self.fine_tuning = Some(fine_tuning);
+        self
+    }
+
+    /// Flat charge per request, on top of token costs.
+    pub fn per_request(mut self, per_request: f64) -> Self {
+        self.per_request = Some(per_request);
+        self
+    }
+
+    /// Charge per call of the provider-run tool `name`, e.g. `web_search`.
+    pub fn tool_price(mut self, name: impl Into<String>, price: f64) -> Self {
+        self.tool_pricing.insert(name.into(), price);
+        self
+    }
+
+    pub fn build(self) -> Result<Model, PricingError> {
+        let key = required(self.key, "model", "key")?;
+        let what = format!("model {}", key);
+        let added = required(self.added, &what, "added")?;
+        Ok(Model {
+            created: self.created.unwrap_or_else(|| added.clone()),
+            added,
+            features: self.features,
+            model_type: required(self.model_type, &what, "type")?,
+            key,
+            aliases: self.aliases,
+            model_id: self.model_id,
+            inference_profile_arn: self.inference_profile_arn,
+            inference_profile_id: self.inference_profile_id,
+            pricing: self.pricing,
+            regions: self.regions,
+            streaming: self.streaming,
+            system_disabled: self.system_disabled,
+            deprecated: self.deprecated,
+            status: self.status,
+            deprecation_date: self.deprecation_date,
+            replacement_model_key: self.replacement_model_key,
+            encoder: self.encoder,
+            context_window: self.context_window,
+            max_output_tokens: self.max_output_tokens,
+            prod_price_ids: self.prod_price_ids,
+            moderation_threshold: self.moderation_threshold,
+            markup: self.markup,
+            rate_limits: self.rate_limits,
+            fine_tuning: self.fine_tuning,
+            per_request: self.per_request,
+            tool_pricing: self.tool_pricing,
+            extra: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpt_4o() -> Model {
+        Model::builder()
+            .key("gpt-4o")
+            .model_type(ModelType::Text)
+            .added("2024-05-13")
+            .feature(ModelFeature::Vision)
+            .text_pricing(2.5, 10.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_builders() {
+        let pricing = AiPricingJson::builder()
+            .metered_price_id("price_metered")
+            .provider(
+                Provider::builder()
+                    .key("openai")
+                    .label("OpenAI")
+                    .markup(50.0, 20.0)
+                    .model(gpt_4o())
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let openai = &pricing.providers[0];
+        assert_eq!(openai.description, "OpenAI");
+        assert_eq!(pricing.schema_version, SCHEMA_VERSION);
+        let model = &openai.models[0];
+        assert_eq!(model.created, "2024-05-13");
+        let Some(Pricing::TextPricing(text)) = &model.pricing else {
+            panic!("expected text pricing");
+        };
+        assert_eq!(text.input_per1_k, 0.0025);
+
+        let cost = pricing
+            .text_cost("openai", "gpt-4o", 1_000_000, 0, 0)
+            .unwrap();
+        assert_eq!(cost.provider_cost, 2.5);
+        assert!(pricing.validate().errors().next().is_none());
+
+        let json = serde_json::to_value(&pricing).unwrap();
+        let round_trip: AiPricingJson = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.providers[0].models[0].key, "gpt-4o");
+    }
+
+    #[test]
+    fn test_model_builder_sets_every_field() {
+        let model = Model::builder()
+            .key("claude-3-5-sonnet")
+            .model_type(ModelType::Text)
+            .added("2024-10-22")
+            .text_pricing(3.0, 15.0)
+            .region("eu-central-1", TextPricing::from_per1_m(3.3, 16.5))
+            .inference_profile_arn("arn:aws:bedrock:us-east-1::inference-profile/sonnet")
+            .inference_profile_id("us.anthropic.claude-3-5-sonnet")
+            .system_disabled(true)
+            .deprecation_date("2025-10-22")
+            .moderation_threshold(ModerationOverride::default())
+            .fine_tuning(FineTunePricing {
+                training_per1_m: 25.0,
+                hosted_input_per1_m: 3.0,
+                hosted_output_per1_m: 15.0,
+                storage_per_hour: None,
+                extra: Default::default(),
+            })
+            .per_request(0.01)
+            .tool_price("web_search", 0.025)
+            .build()
+            .unwrap();
+
+        assert_eq!(model.regions["eu-central-1"].input_per1_m, 3.3);
+        assert!(model.inference_profile_arn.is_some());
+        assert!(model.inference_profile_id.is_some());
+        assert_eq!(model.system_disabled, Some(true));
+        assert_eq!(model.deprecation_date.as_deref(), Some("2025-10-22"));
+        assert!(model.moderation_threshold.is_some());
+        assert_eq!(model.fine_tuning.unwrap().training_per1_m, 25.0);
+        assert_eq!(model.per_request, Some(0.01));
+        assert_eq!(model.tool_pricing["web_search"], 0.025);
+    }
+
+    #[test]
+    fn test_required_fields() {
+        let err = AiPricingJson::builder().build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid pricing data: pricing is missing meteredPriceId"
+        );
+
+        let err = Provider::builder().key("openai").label("OpenAI").build();
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("provider openai is missing markup"));
+
+        let err = Model::builder().key("gpt-4o").added("2024-05-13").build();
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("model gpt-4o is missing type"));
+        assert!(Model::builder().build().is_err());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::AiPricingJson;
+
+/// Pricing JSON embedded at compile time from `data/ai-pricing.json`.
+///
+/// Refresh that file from the published prod pricing before cutting a release.
+pub const BUNDLED_PRICING_JSON: &[u8] = include_bytes!("../data/ai-pricing.json");
+
+impl AiPricingJson {
+    /// The pricing embedded in this build of the crate.
+    ///
+    /// Meant as a last resort for air-gapped tests and for when neither the
+    /// pricing host nor a persisted snapshot is available. It is only as
+    /// current as the crate release it shipped with.
+    pub fn bundled() -> AiPricingJson {
+        serde_json::from_slice(BUNDLED_PRICING_JSON).expect("bundled pricing JSON is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_pricing_is_valid() {
+        let report = AiPricingJson::bundled().validate();
+        assert!(report.is_ok(), "bundled pricing has issues:\n{}", report);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::AiPricingJson;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+// ------------------
+// Cache entry
+// ------------------
+
+/// A cached pricing snapshot along with the bookkeeping needed for expiry.
+#[derive(Clone)]
+struct CacheEntry {
+    data: Arc<AiPricingJson>,
+    fetched_at: Instant,
+    // Set while one caller re-fetches an expired entry, so concurrent callers
+    // keep getting the old value instead of piling onto the same refresh.
+    refreshing: Arc<AtomicBool>,
+}
+
+impl CacheEntry {
+    fn new(data: Arc<AiPricingJson>) -> Self {
+        CacheEntry {
+            data,
+            fetched_at: Instant::now(),
+            refreshing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn is_expired(&self, ttl: Option<Duration>) -> bool {
+        ttl.is_some_and(|ttl| self.fetched_at.elapsed() >= ttl)
+    }
+
+    /// Whether the entry is still young enough to be served while it is
+    /// revalidated in the background.
+    fn is_servable_stale(&self, ttl: Option<Duration>, max_stale: Option<Duration>) -> bool {
+        match (ttl, max_stale) {
+            (Some(ttl), Some(max_stale)) => self.fetched_at.elapsed() < ttl + max_stale,
+            _ => false,
+        }
+    }
+}
+
+/// Clears an entry's `refreshing` flag when dropped, so a failed or cancelled
+/// refresh doesn't leave the entry stuck serving stale data forever.
+pub(crate) struct RefreshGuard(Arc<AtomicBool>);
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+// ------------------
+// Pricing cache
+// ------------------
+
+/// What the caller should do after consulting the cache.
+pub(crate) enum Lookup {
+    /// Serve this value as-is.
+    Hit(Arc<AiPricingJson>),
+    /// Serve this stale value now, and refresh in the background while
+    /// holding the refresh claim.
+    Revalidate(Arc<AiPricingJson>, RefreshGuard),
+    /// Fetch fresh data and [`PricingCache::store`] it. Holds the refresh
+    /// claim on an expired entry, if there was one.
+    Fetch(Option<RefreshGuard>),
+}
+
+/// A single refreshable pricing snapshot.
+#[derive(Default)]
+pub(crate) struct PricingCache {
+    slot: RwLock<Option<CacheEntry>>,
+    // Bumped whenever fresh data lands, so a caller that waited on another
+    // caller's fetch can tell whether it produced anything.
+    generation: AtomicU64,
+}
+
+impl PricingCache {
+    /// Decide how to serve a request. Entries older than `ttl` are refreshed;
+    /// with `max_stale` set, an expired entry younger than `ttl + max_stale` is
+    /// still served while a background refresh runs.
+    pub(crate) fn lookup(
+        &self,
+        ttl: Option<Duration>,
+        max_stale: Option<Duration>,
+        bust_cache: bool,
+    ) -> Lookup {
+        let cached = self.slot.read().unwrap().clone();
+
+        match cached {
+            Some(entry) if !bust_cache => {
+                if !entry.is_expired(ttl) {
+                    return Lookup::Hit(entry.data);
+                }
+                // Expired: claim the refresh, or serve the old value if another
+                // caller already has.
+                if entry
+                    .refreshing
+                    .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                    .is_err()
+                {
+                    return Lookup::Hit(entry.data);
+                }
+                let guard = RefreshGuard(entry.refreshing.clone());
+                if entry.is_servable_stale(ttl, max_stale) {
+                    return Lookup::Revalidate(entry.data, guard);
+                }
+                Lookup::Fetch(Some(guard))
+            }
+            _ => Lookup::Fetch(None),
+        }
+    }
+
+    pub(crate) fn store(&self, data: Arc<AiPricingJson>) {
+        self.replace(CacheEntry::new(data));
+    }
+
+    /// Cache a snapshot that was fetched `age` ago, e.g. one read back from
+    /// disk, so it expires when it would have had it been fetched then.
+    pub(crate) fn store_aged(&self, data: Arc<AiPricingJson>, age: Duration) {
+        let mut entry = CacheEntry::new(data);
+        entry.fetched_at = Instant::now().checked_sub(age).unwrap_or(entry.fetched_at);
+        self.replace(entry);
+    }
+
+    fn replace(&self, entry: CacheEntry) {
+        *self.slot.write().unwrap() = Some(entry);
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Changes whenever the cached snapshot is stored or touched.
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// The cached snapshot regardless of age, if any.
+    pub(crate) fn current(&self) -> Option<Arc<AiPricingJson>> {
+        self.slot
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|entry| Arc::clone(&entry.data))
+    }
+
+    /// Mark the cached snapshot as freshly fetched without replacing it, e.g.
+    /// after a 304. Returns `None` if the cache is empty.
+    pub(crate) fn touch(&self) -> Option<Arc<AiPricingJson>> {
+        let mut slot = self.slot.write().unwrap();
+        let entry = slot.as_mut()?;
+        entry.fetched_at = Instant::now();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        Some(Arc::clone(&entry.data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_pricing() -> Arc<AiPricingJson> {
+        Arc::new(AiPricingJson {
+            schema_version: crate::SCHEMA_VERSION,
+            metered_price_id: "price_test".to_string(),
+            providers: vec![],
+            currency: None,
+            markup_overlay: Default::default(),
+            promotions: vec![],
+            rounding: Default::default(),
+            extra: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_cache_entry_expiry() {
+        let entry = CacheEntry::new(empty_pricing());
+        assert!(!entry.is_expired(None));
+        assert!(!entry.is_expired(Some(Duration::from_secs(60))));
+        assert!(entry.is_expired(Some(Duration::ZERO)));
+    }
+
+    #[test]
+    fn test_expired_entry_served_while_refreshing() {
+        let cache = PricingCache::default();
+        assert!(matches!(
+            cache.lookup(None, None, false),
+            Lookup::Fetch(None)
+        ));
+
+        cache.store(empty_pricing());
+        assert!(matches!(cache.lookup(None, None, false), Lookup::Hit(_)));
+
+        // The first caller to see the expired entry claims the refresh...
+        let guard = match cache.lookup(Some(Duration::ZERO), None, false) {
+            Lookup::Fetch(Some(guard)) => guard,
+            _ => panic!("expected to claim the refresh"),
+        };
+        // ...and everyone else keeps getting the old value meanwhile.
+        assert!(matches!(
+            cache.lookup(Some(Duration::ZERO), None, false),
+            Lookup::Hit(_)
+        ));
+
+        // Dropping the claim without storing lets the next caller retry.
+        drop(guard);
+        assert!(matches!(
+            cache.lookup(Some(Duration::ZERO), None, false),
+            Lookup::Fetch(Some(_))
+        ));
+    }
+
+    #[test]
+    fn test_stale_entry_revalidated_within_max_staleness() {
+        let cache = PricingCache::default();
+        cache.store(empty_pricing());
+
+        let ttl = Some(Duration::ZERO);
+        let guard = match cache.lookup(ttl, Some(Duration::from_secs(60)), false) {
+            Lookup::Revalidate(_, guard) => guard,
+            _ => panic!("expected a background revalidation"),
+        };
+        assert!(matches!(
+            cache.lookup(ttl, Some(Duration::from_secs(60)), false),
+            Lookup::Hit(_)
+        ));
+        drop(guard);
+
+        // Past the staleness limit the caller has to wait for fresh data.
+        assert!(matches!(
+            cache.lookup(ttl, Some(Duration::ZERO), false),
+            Lookup::Fetch(Some(_))
+        ));
+    }
+
+    #[test]
+    fn test_touch_restarts_ttl() {
+        let cache = PricingCache::default();
+        assert!(cache.touch().is_none());
+
+        cache.store(empty_pricing());
+        assert!(matches!(
+            cache.lookup(Some(Duration::ZERO), None, false),
+            Lookup::Fetch(Some(_))
+        ));
+        cache.touch().expect("cache should hold a value");
+        assert!(matches!(
+            cache.lookup(Some(Duration::from_secs(60)), None, false),
+            Lookup::Hit(_)
+        ));
+    }
+}
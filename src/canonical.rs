@@ -0,0 +1,81 @@
+use crate::AiPricingJson;
+use serde_json::{Map, Number, Value};
+
+// Integral floats up to 2^53 convert to integers exactly.
+const MAX_EXACT_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+// ------------------
+// Canonical JSON
+// ------------------
+
+impl AiPricingJson {
+    /// Serialize to pretty-printed JSON in a canonical form, so that diffs
+    /// between two published files show only semantic changes: object keys
+    /// are sorted, integral numbers lose their `.0` (`10.0` becomes `10`),
+    /// and the output ends with a newline.
+    pub fn to_canonical_json(&self) -> Result<String, serde_json::Error> {
+        let value = canonicalize(serde_json::to_value(self)?);
+        let mut json = serde_json::to_string_pretty(&value)?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        Value::Number(number) => Value::Number(canonical_number(number)),
+        value => value,
+    }
+}
+
+fn canonical_number(number: Number) -> Number {
+    match number.as_f64() {
+        Some(f) if number.is_f64() && f.fract() == 0.0 && f.abs() <= MAX_EXACT_INTEGER => {
+            Number::from(f as i64)
+        }
+        _ => number,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_canonical_json() {
+        let json = pricing().to_canonical_json().unwrap();
+        assert!(json.ends_with("}\n"));
+        assert!(json.contains(r#""outputPer1M": 10,"#));
+        assert!(json.contains(r#""inputPer1M": 2.5,"#));
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let keys: Vec<&String> = value["providers"][0].as_object().unwrap().keys().collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+
+        let round_trip: AiPricingJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip.to_canonical_json().unwrap(), json);
+    }
+
+    #[test]
+    fn test_canonical_numbers() {
+        let number = |json: &str| canonical_number(serde_json::from_str(json).unwrap());
+        assert_eq!(number("10.0").to_string(), "10");
+        assert_eq!(number("-0.0").to_string(), "0");
+        assert_eq!(number("0.00125").to_string(), "0.00125");
+        assert_eq!(number("1e300").to_string(), "1e300");
+    }
+}
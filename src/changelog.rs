@@ -0,0 +1,235 @@
+use crate::{AiPricingJson, ModelRef, ModelStatus};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+// ------------------
+// Changelog
+// ------------------
+
+/// One customer-visible change between two snapshots.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ChangelogItem {
+    ModelAdded {
+        model: ModelRef,
+    },
+    ModelRemoved {
+        model: ModelRef,
+    },
+    ModelDeprecated {
+        model: ModelRef,
+    },
+    StatusChanged {
+        model: ModelRef,
+        old: Option<ModelStatus>,
+        new: Option<ModelStatus>,
+    },
+    PriceIncreased {
+        model: ModelRef,
+        field: String,
+        old: f64,
+        new: f64,
+    },
+    PriceDecreased {
+        model: ModelRef,
+        field: String,
+        old: f64,
+        new: f64,
+    },
+}
+
+/// The changes that took effect with one snapshot.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogEntry {
+    /// The snapshot's label, e.g. its publish date.
+    pub label: String,
+    pub items: Vec<ChangelogItem>,
+}
+
+/// Customer-visible pricing changes across a series of snapshots, newest
+/// first.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Changelog {
+    pub entries: Vec<ChangelogEntry>,
+}
+
+impl Changelog {
+    /// Build a changelog from labelled snapshots, oldest first. Each snapshot
+    /// after the first gets an entry for what changed since the one before,
+    /// unless nothing customer-visible did.
+    ///
+    /// Only model additions, removals, deprecations, and price changes are
+    /// listed; markups, moderation, and price IDs are internal.
+    pub fn from_snapshots<'a, L: AsRef<str>>(
+        snapshots: impl IntoIterator<Item = (L, &'a AiPricingJson)>,
+    ) -> Self {
+        let mut entries = Vec::new();
+        let mut previous: Option<&AiPricingJson> = None;
+
+        for (label, snapshot) in snapshots {
+            if let Some(previous) = previous {
+                let items = items(previous, snapshot);
+                if !items.is_empty() {
+                    entries.push(ChangelogEntry {
+                        label: label.as_ref().to_string(),
+                        items,
+                    });
+                }
+            }
+            previous = Some(snapshot);
+        }
+
+        entries.reverse();
+        Changelog { entries }
+    }
+
+    /// Render as markdown, one `##` section per entry.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            let _ = writeln!(out, "## {}\n", entry.label);
+            for item in &entry.items {
+                let _ = writeln!(out, "- {}", describe(item));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn items(old: &AiPricingJson, new: &AiPricingJson) -> Vec<ChangelogItem> {
+    let diff = old.diff(new);
+    let mut items = Vec::new();
+
+    items.extend(
+        diff.added_models
+            .into_iter()
+            .map(|model| ChangelogItem::ModelAdded { model }),
+    );
+    items.extend(
+        diff.deprecated_models
+            .into_iter()
+            .map(|model| ChangelogItem::ModelDeprecated { model }),
+    );
+    items.extend(
+        diff.status_changes
+            .into_iter()
+            .map(|change| ChangelogItem::StatusChanged {
+                model: change.model,
+                old: change.old,
+                new: change.new,
+            }),
+    );
+    items.extend(
+        diff.removed_models
+            .into_iter()
+            .map(|model| ChangelogItem::ModelRemoved { model }),
+    );
+    for change in diff.price_changes {
+        let (Some(old), Some(new)) = (change.old, change.new) else {
+            continue;
+        };
+        let (model, field) = (change.model, change.field);
+        items.push(if new > old {
+            ChangelogItem::PriceIncreased {
+                model,
+                field,
+                old,
+                new,
+            }
+        } else {
+            ChangelogItem::PriceDecreased {
+                model,
+                field,
+                old,
+                new,
+            }
+        });
+    }
+
+    items
+}
+
+fn describe(item: &ChangelogItem) -> String {
+    let name = |model: &ModelRef| format!("`{}` ({})", model.model_key, model.provider_key);
+    match item {
+        ChangelogItem::ModelAdded { model } => format!("Added {}", name(model)),
+        ChangelogItem::ModelRemoved { model } => format!("Removed {}", name(model)),
+        ChangelogItem::ModelDeprecated { model } => format!("Deprecated {}", name(model)),
+        ChangelogItem::StatusChanged { model, old, new } => {
+            let status = |status: &Option<ModelStatus>| {
+                status
+                    .as_ref()
+                    .map_or("unset", ModelStatus::as_str)
+                    .to_string()
+            };
+            format!("{} status: {} → {}", name(model), status(old), status(new))
+        }
+        ChangelogItem::PriceIncreased {
+            model,
+            field,
+            old,
+            new,
+        } => format!("{} {}: ${} → ${} (increase)", name(model), field, old, new),
+        ChangelogItem::PriceDecreased {
+            model,
+            field,
+            old,
+            new,
+        } => format!("{} {}: ${} → ${} (decrease)", name(model), field, old, new),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::Pricing;
+
+    #[test]
+    fn test_changelog() {
+        let first = pricing();
+
+        let mut second = pricing();
+        if let Some(Pricing::TextPricing(text)) = &mut second.providers[0].models[0].pricing {
+            text.input_per1_m = 2.0;
+        }
+        second.providers[0].markup.text_percentage = 30.0;
+
+        let mut third = second.clone();
+        third.providers[1].models[0].deprecated = Some(true);
+        third.providers[0].models[0].status = Some(ModelStatus::Legacy);
+
+        let changelog = Changelog::from_snapshots([
+            ("2024-01-01", &first),
+            ("2024-02-01", &second),
+            ("2024-02-15", &second),
+            ("2024-03-01", &third),
+        ]);
+        let labels: Vec<&str> = changelog.entries.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(labels, ["2024-03-01", "2024-02-01"]);
+        assert!(matches!(
+            &changelog.entries[1].items[..],
+            [ChangelogItem::PriceDecreased { old, new, .. }] if *old == 2.5 && *new == 2.0
+        ));
+
+        let markdown = changelog.to_markdown();
+        assert!(markdown.starts_with(
+            "## 2024-03-01\n\n- Deprecated `gpt-4o` (openai)\n\
+             - Deprecated `claude-3-5-sonnet` (bedrock)\n\
+             - `gpt-4o` (openai) status: unset → legacy\n"
+        ));
+        assert!(markdown.contains("- `gpt-4o` (openai) inputPer1M: $2.5 → $2 (decrease)\n"));
+    }
+}
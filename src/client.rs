@@ -0,0 +1,757 @@
+use crate::cache::{Lookup, PricingCache, RefreshGuard};
+use crate::overrides::Overrides;
+use crate::{
+    persist, telemetry, AiPricingJson, Env, HttpSource, PricingError, PricingSource, RetryPolicy,
+    DEFAULT_BASE_URL,
+};
+use reqwest::Client;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+// ------------------
+// Client
+// ------------------
+
+/// Fetches and caches pricing from a [`PricingSource`], by default one
+/// environment's published file over HTTP.
+///
+/// Each client owns its own source and cache, so independently configured
+/// clients can coexist in one process. Cloning is cheap and clones share the
+/// same cache.
+pub struct PricingClient<S = HttpSource> {
+    inner: Arc<Inner<S>>,
+}
+
+impl<S> Clone for PricingClient<S> {
+    fn clone(&self) -> Self {
+        PricingClient {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+struct Inner<S> {
+    source: S,
+    ttl: Option<Duration>,
+    max_stale: Option<Duration>,
+    retry: RetryPolicy,
+    on_fetch_error: FetchErrorPolicy,
+    persist_path: Option<PathBuf>,
+    max_persisted_age: Option<Duration>,
+    #[cfg(feature = "bundled")]
+    bundled_fallback: bool,
+    cache: PricingCache,
+    // Held for the duration of a fetch, so concurrent callers share one.
+    fetch_lock: tokio::sync::Mutex<()>,
+}
+
+/// What to do when fetching pricing fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FetchErrorPolicy {
+    /// Return the error to the caller.
+    #[default]
+    Fail,
+    /// Log a warning and serve the cached snapshot, however old, if there is
+    /// one. Errors are still returned when the cache is empty.
+    ServeStale,
+}
+
+impl PricingClient {
+    pub fn builder() -> PricingClientBuilder {
+        PricingClientBuilder::default()
+    }
+
+    /// The URL this client fetches pricing from.
+    pub fn url(&self) -> &str {
+        self.inner.source.url()
+    }
+}
+
+impl<S: PricingSource> PricingClient<S> {
+    /// The source this client loads pricing from.
+    pub fn source(&self) -> &S {
+        &self.inner.source
+    }
+
+    /// Return the cached pricing, fetching it first if the cache is empty or
+    /// older than the configured TTL.
+    pub async fn get(&self) -> Result<Arc<AiPricingJson>, PricingError> {
+        self.load(self.inner.ttl, false).await
+    }
+
+    /// The configured TTL, if any.
+    pub(crate) fn ttl(&self) -> Option<Duration> {
+        self.inner.ttl
+    }
+
+    /// Fetch fresh pricing regardless of the cache, and cache the result.
+    pub async fn refresh(&self) -> Result<Arc<AiPricingJson>, PricingError> {
+        self.load(None, true).await
+    }
+
+    /// Cache `data` as if it had just been fetched, so it is served without
+    /// touching the source until the TTL expires, or for good without one.
+    /// Lets tests control exactly which prices are in effect.
+    pub fn set_pricing(&self, data: impl Into<Arc<AiPricingJson>>) {
+        self.inner.cache.store(data.into());
+    }
+
+    /// Re-fetch pricing every `interval` in a background task, publishing each
+    /// snapshot that differs from the previous one through the returned
+    /// receiver.
+    ///
+    /// The receiver starts out holding the current pricing, fetched first if
+    /// the cache is empty. Failed refreshes are skipped and retried on the next
+    /// tick. The task stops once every receiver has been dropped.
+    pub async fn spawn_refresher(
+        &self,
+        interval: Duration,
+    ) -> Result<watch::Receiver<Arc<AiPricingJson>>, PricingError> {
+        let initial = self.get().await?;
+        let (tx, rx) = watch::channel(initial);
+
+        let client = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately; we already have fresh data.
+            ticker.tick().await;
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = tx.closed() => break,
+                }
+
+                let Ok(fresh) = client.refresh().await else {
+                    continue;
+                };
+                tx.send_if_modified(|current| {
+                    if *current == fresh {
+                        return false;
+                    }
+                    *current = fresh;
+                    true
+                });
+            }
+        });
+
+        Ok(rx)
+    }
+
+    pub(crate) async fn load(
+        &self,
+        ttl: Option<Duration>,
+        bust_cache: bool,
+    ) -> Result<Arc<AiPricingJson>, PricingError> {
+        let _guard = match self
+            .inner
+            .cache
+            .lookup(ttl, self.inner.max_stale, bust_cache)
+        {
+            Lookup::Hit(data) => {
+                telemetry::cache_hit(false);
+                return Ok(data);
+            }
+            Lookup::Revalidate(data, guard) => {
+                telemetry::cache_hit(true);
+                self.spawn_revalidate(guard);
+                return Ok(data);
+            }
+            Lookup::Fetch(guard) => {
+                if !bust_cache {
+                    telemetry::cache_miss();
+                }
+                guard
+            }
+        };
+
+        match self.fetch_and_store().await {
+            Ok(data) => Ok(data),
+            Err(err) => self.fall_back(err).await,
+        }
+    }
+
+    /// Find something to serve after a failed fetch: the cached snapshot if
+    /// the policy allows it, or the persisted snapshot on a cold start.
+    async fn fall_back(&self, err: PricingError) -> Result<Arc<AiPricingJson>, PricingError> {
+        if let Some(stale) = self.inner.cache.current() {
+            if self.inner.on_fetch_error == FetchErrorPolicy::ServeStale {
+                log::warn!("serving cached pricing after fetch failed: {}", err);
+                return Ok(stale);
+            }
+            return Err(err);
+        }
+
+        if let Some(path) = &self.inner.persist_path {
+            match persist::read_snapshot(path, self.inner.max_persisted_age).await {
+                Ok(Some((data, age))) => {
+                    log::warn!(
+                        "serving pricing persisted at {} after fetch failed: {}",
+                        path.display(),
+                        err
+                    );
+                    let data = Arc::new(data);
+                    self.inner.cache.store_aged(Arc::clone(&data), age);
+                    return Ok(data);
+                }
+                Ok(None) => {}
+                Err(read_err) => log::warn!(
+                    "failed to read persisted pricing from {}: {}",
+                    path.display(),
+                    read_err
+                ),
+            }
+        }
+
+        #[cfg(feature = "bundled")]
+        if self.inner.bundled_fallback {
+            log::warn!("serving bundled pricing after fetch failed: {}", err);
+            // Not cached, so the next call tries the network again.
+            return Ok(Arc::new(AiPricingJson::bundled()));
+        }
+
+        Err(err)
+    }
+
+    /// Refresh the cache in a background task. A failed refresh leaves the
+    /// stale value in place, and the next request past the TTL tries again.
+    fn spawn_revalidate(&self, guard: RefreshGuard) {
+        let client = self.clone();
+        tokio::spawn(async move {
+            let _guard = guard;
+            let _ = client.fetch_and_store().await;
+        });
+    }
+
+    /// Load pricing from the source and update the cache.
+    ///
+    /// Only one load runs at a time. Callers that arrive while one is in
+    /// flight wait for it and share its result; if it fails, the next waiter
+    /// tries again.
+    ///
+    /// When the cache already holds a snapshot the load is conditional, and
+    /// an unchanged source keeps that snapshot, restarting its TTL.
+    async fn fetch_and_store(&self) -> Result<Arc<AiPricingJson>, PricingError> {
+        let generation = self.inner.cache.generation();
+        let _flight = self.inner.fetch_lock.lock().await;
+        if self.inner.cache.generation() != generation {
+            if let Some(data) = self.inner.cache.current() {
+                return Ok(data);
+            }
+        }
+
+        let started = Instant::now();
+        let data = match self.load_from_source().await {
+            Ok(Fresh::Loaded(data)) => {
+                telemetry::refreshed(true, started.elapsed());
+                data
+            }
+            Ok(Fresh::Unchanged(cached)) => {
+                telemetry::refreshed(false, started.elapsed());
+                return Ok(cached);
+            }
+            Err(err) => {
+                telemetry::refresh_failed(&err, started.elapsed());
+                return Err(err);
+            }
+        };
+
+        if let Some(path) = &self.inner.persist_path {
+            if let Err(err) = persist::write_snapshot(path, &data).await {
+                log::warn!("failed to persist pricing to {}: {}", path.display(), err);
+            }
+        }
+        let data = Arc::new(data);
+        self.inner.cache.store(Arc::clone(&data));
+        Ok(data)
+    }
+
+    /// Load from the source, conditionally if the cache holds a snapshot to
+    /// fall back on.
+    async fn load_from_source(&self) -> Result<Fresh, PricingError> {
+        let source = &self.inner.source;
+        let retry = &self.inner.retry;
+        if self.inner.cache.current().is_some() {
+            if let Some(data) = retry.run(|| source.load_if_modified()).await? {
+                return Ok(Fresh::Loaded(data));
+            }
+            if let Some(cached) = self.inner.cache.touch() {
+                return Ok(Fresh::Unchanged(cached));
+            }
+        }
+        retry.run(|| source.load()).await.map(Fresh::Loaded)
+    }
+}
+
+/// The outcome of a successful load from the source.
+enum Fresh {
+    Loaded(AiPricingJson),
+    /// The source reported no change; the cached snapshot, now touched.
+    Unchanged(Arc<AiPricingJson>),
+}
+
+// ------------------
+// Builder
+// ------------------
+
+#[derive(Debug, Default)]
+pub struct PricingClientBuilder {
+    http: Option<Client>,
+    url: Option<String>,
+    base_url: Option<String>,
+    env: Option<Env>,
+    default_env: Option<Env>,
+    timeout: Option<Duration>,
+    ttl: Option<Duration>,
+    max_stale: Option<Duration>,
+    retry: RetryPolicy,
+    on_fetch_error: FetchErrorPolicy,
+    persist_path: Option<PathBuf>,
+    max_persisted_age: Option<Duration>,
+    #[cfg(feature = "bundled")]
+    bundled_fallback: bool,
+}
+
+impl PricingClientBuilder {
+    /// HTTP client to fetch with, e.g. one configured with a corporate proxy,
+    /// connection pool limits, or custom TLS roots. Defaults to a client
+    /// shared by every `PricingClient` in the process.
+    pub fn http_client(mut self, http: Client) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    /// Fetch pricing from exactly this URL instead of resolving an
+    /// environment's file under the base URL. `file://` URLs are read from
+    /// local disk.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Base URL the environment's pricing file is resolved against.
+    /// Defaults to [`DEFAULT_BASE_URL`].
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Environment to fetch. Defaults to [`Env::Prod`].
+    pub fn env(mut self, env: Env) -> Self {
+        self.env = Some(env);
+        self
+    }
+
+    /// Environment to fetch when neither [`env`](Self::env) nor a URL was
+    /// chosen. Unlike `env`, `AI_PRICING_URL` still overrides it, so the
+    /// default clients behind [`get_ai_pricing`](crate::get_ai_pricing) can be
+    /// pointed at a mirror.
+    pub(crate) fn default_env(mut self, env: Env) -> Self {
+        self.default_env = Some(env);
+        self
+    }
+
+    /// Timeout applied to each pricing request. Defaults to none.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// How long a fetched snapshot is served before being re-fetched.
+    /// Defaults to caching for the client's lifetime.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Serve expired snapshots immediately and refresh them in the background,
+    /// so request latency never includes a pricing fetch. Once a snapshot is
+    /// more than `max_stale` past its TTL, callers wait for fresh data again.
+    /// Has no effect without a [`ttl`](Self::ttl).
+    pub fn stale_while_revalidate(mut self, max_stale: Duration) -> Self {
+        self.max_stale = Some(max_stale);
+        self
+    }
+
+    /// How to retry transient fetch failures. Defaults to
+    /// [`RetryPolicy::NONE`].
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// What to do when a fetch fails. Defaults to [`FetchErrorPolicy::Fail`].
+    pub fn on_fetch_error(mut self, policy: FetchErrorPolicy) -> Self {
+        self.on_fetch_error = policy;
+        self
+    }
+
+    /// Persist every successfully fetched snapshot to `path`, and fall back to
+    /// it when the first fetch fails, so a service can start while the pricing
+    /// host is unreachable.
+    pub fn persist_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.persist_path = Some(path.into());
+        self
+    }
+
+    /// Ignore a persisted snapshot older than `max_age`. Defaults to using it
+    /// regardless of age.
+    pub fn max_persisted_age(mut self, max_age: Duration) -> Self {
+        self.max_persisted_age = Some(max_age);
+        self
+    }
+
+    /// Serve [`AiPricingJson::bundled`] when a fetch fails and there is
+    /// nothing cached or persisted to fall back to.
+    #[cfg(feature = "bundled")]
+    pub fn bundled_fallback(mut self, enabled: bool) -> Self {
+        self.bundled_fallback = enabled;
+        self
+    }
+
+    /// Build the client. Settings left unset here are taken from the
+    /// [`URL_VAR`](crate::URL_VAR), [`ENV_VAR`](crate::ENV_VAR), and
+    /// [`TTL_SECS_VAR`](crate::TTL_SECS_VAR) environment variables
+    /// when present: `AI_PRICING_URL` applies unless [`url`](Self::url),
+    /// [`base_url`](Self::base_url), or [`env`](Self::env) was set, and
+    /// `AI_PRICING_ENV` and `AI_PRICING_TTL_SECS` unless [`env`](Self::env) or
+    /// [`ttl`](Self::ttl) was.
+    ///
+    /// Fails if a variable that applies can't be parsed. Variables that don't
+    /// apply are never parsed.
+    pub fn build(self) -> Result<PricingClient, PricingError> {
+        self.build_with_overrides(Overrides::from_process())
+    }
+
+    fn build_with_overrides(mut self, overrides: Overrides) -> Result<PricingClient, PricingError> {
+        // `AI_PRICING_URL` replaces the whole location, so it only applies
+        // when nothing about the location was chosen in code.
+        let url = match (self.url.take(), &self.base_url, &self.env, &overrides.url) {
+            (Some(url), _, _, _) => url,
+            (None, None, None, Some(url)) => url.clone(),
+            (None, base_url, _, _) => {
+                let base_url = base_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+                let env = match self.env.take().or(self.default_env.take()) {
+                    Some(env) => env,
+                    None => overrides.env()?.unwrap_or(Env::Prod),
+                };
+                env.pricing_url(base_url)
+            }
+        };
+        if self.ttl.is_none() {
+            self.ttl = overrides.ttl()?;
+        }
+
+        let mut source = match &self.http {
+            Some(http) => HttpSource::with_client(http.clone(), url),
+            None => HttpSource::new(url),
+        };
+        if let Some(timeout) = self.timeout {
+            source = source.with_timeout(timeout);
+        }
+
+        Ok(self.build_with_source(source))
+    }
+
+    /// Build a client around a custom source. The caching and fallback
+    /// settings apply; the URL, environment, and timeout settings are ignored,
+    /// and so are the environment variable overrides.
+    pub fn build_with_source<S: PricingSource>(self, source: S) -> PricingClient<S> {
+        PricingClient {
+            inner: Arc::new(Inner {
+                source,
+                ttl: self.ttl,
+                max_stale: self.max_stale,
+                retry: self.retry,
+                on_fetch_error: self.on_fetch_error,
+                persist_path: self.persist_path,
+                max_persisted_age: self.max_persisted_age,
+                #[cfg(feature = "bundled")]
+                bundled_fallback: self.bundled_fallback,
+                cache: PricingCache::default(),
+                fetch_lock: tokio::sync::Mutex::new(()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ENV_VAR, TTL_SECS_VAR};
+
+    #[test]
+    fn test_builder_resolves_url() {
+        let client = PricingClient::builder()
+            .base_url("http://localhost:8080/pricing/")
+            .env(Env::Dev)
+            .build()
+            .expect("Failed to build client");
+        assert_eq!(
+            client.url(),
+            "http://localhost:8080/pricing/ai-pricing-dev.json"
+        );
+
+        let client = PricingClient::builder()
+            .build()
+            .expect("Failed to build client");
+        assert_eq!(
+            client.url(),
+            "https://images.bookcicle.com/ai/ai-pricing.json"
+        );
+    }
+
+    #[test]
+    fn test_builder_applies_env_var_overrides() {
+        let overrides = Overrides {
+            url: None,
+            env: Some("staging".to_string()),
+            ttl: Some("60".to_string()),
+        };
+        let client = PricingClient::builder()
+            .build_with_overrides(overrides.clone())
+            .unwrap();
+        assert_eq!(
+            client.url(),
+            "https://images.bookcicle.com/ai/ai-pricing-staging.json"
+        );
+        assert_eq!(client.ttl(), Some(Duration::from_secs(60)));
+
+        // Settings made in code win over the environment.
+        let client = PricingClient::builder()
+            .env(Env::Dev)
+            .ttl(Duration::from_secs(5))
+            .build_with_overrides(overrides)
+            .unwrap();
+        assert!(client.url().ends_with("/ai-pricing-dev.json"));
+        assert_eq!(client.ttl(), Some(Duration::from_secs(5)));
+
+        let overrides = Overrides {
+            url: Some("https://mirror.example.com/pricing.json".to_string()),
+            ..Overrides::default()
+        };
+        let client = PricingClient::builder()
+            .build_with_overrides(overrides.clone())
+            .unwrap();
+        assert_eq!(client.url(), "https://mirror.example.com/pricing.json");
+
+        let client = PricingClient::builder()
+            .env(Env::Dev)
+            .build_with_overrides(overrides.clone())
+            .unwrap();
+        assert_eq!(
+            client.url(),
+            "https://images.bookcicle.com/ai/ai-pricing-dev.json"
+        );
+
+        let client = PricingClient::builder()
+            .base_url("http://localhost:8080")
+            .build_with_overrides(overrides)
+            .unwrap();
+        assert_eq!(client.url(), "http://localhost:8080/ai-pricing.json");
+    }
+
+    #[test]
+    fn test_default_clients_honor_the_url_override() {
+        let overrides = Overrides {
+            url: Some("https://mirror.example.com/pricing.json".to_string()),
+            ..Overrides::default()
+        };
+        let client = PricingClient::builder()
+            .default_env(Env::Dev)
+            .build_with_overrides(overrides)
+            .unwrap();
+        assert_eq!(client.url(), "https://mirror.example.com/pricing.json");
+
+        // The environment asked for in code wins over `AI_PRICING_ENV`.
+        let overrides = Overrides {
+            env: Some("staging".to_string()),
+            ..Overrides::default()
+        };
+        let client = PricingClient::builder()
+            .default_env(Env::Dev)
+            .build_with_overrides(overrides)
+            .unwrap();
+        assert!(client.url().ends_with("/ai-pricing-dev.json"));
+    }
+
+    #[test]
+    fn test_unused_malformed_overrides_are_ignored() {
+        let overrides = Overrides {
+            url: None,
+            env: Some("pord".to_string()),
+            ttl: Some("5m".to_string()),
+        };
+        let client = PricingClient::builder()
+            .default_env(Env::Prod)
+            .ttl(Duration::from_secs(5))
+            .build_with_overrides(overrides.clone())
+            .unwrap();
+        assert_eq!(client.ttl(), Some(Duration::from_secs(5)));
+
+        let built = PricingClient::builder()
+            .env(Env::Prod)
+            .build_with_overrides(overrides.clone());
+        assert!(matches!(
+            built,
+            Err(PricingError::Config {
+                var: TTL_SECS_VAR,
+                ..
+            })
+        ));
+        let built = PricingClient::builder()
+            .ttl(Duration::from_secs(5))
+            .build_with_overrides(overrides);
+        assert!(matches!(
+            built,
+            Err(PricingError::Config { var: ENV_VAR, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_pricing_skips_the_source() {
+        // Nothing listens on port 1, so any fetch would fail.
+        let client = PricingClient::builder()
+            .base_url("http://127.0.0.1:1")
+            .build()
+            .expect("Failed to build client");
+        assert!(client.get().await.is_err());
+
+        let mut pricing = crate::test_fixtures::pricing();
+        pricing.metered_price_id = "price_injected".to_string();
+        client.set_pricing(pricing);
+        assert_eq!(
+            client.get().await.unwrap().metered_price_id,
+            "price_injected"
+        );
+        assert!(client.refresh().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_serve_stale_on_fetch_error() {
+        // Nothing listens on port 1, so every fetch fails to connect.
+        let build = |policy| {
+            PricingClient::builder()
+                .base_url("http://127.0.0.1:1")
+                .ttl(Duration::ZERO)
+                .on_fetch_error(policy)
+                .build()
+                .expect("Failed to build client")
+        };
+
+        let client = build(FetchErrorPolicy::ServeStale);
+        assert!(matches!(
+            client.get().await,
+            Err(PricingError::Request { .. })
+        ));
+
+        let stale = Arc::new(crate::test_fixtures::pricing());
+        client.inner.cache.store(Arc::clone(&stale));
+        let served = client.get().await.expect("should serve the stale snapshot");
+        assert!(Arc::ptr_eq(&served, &stale));
+
+        let client = build(FetchErrorPolicy::Fail);
+        client.inner.cache.store(stale);
+        assert!(client.get().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cold_start_from_persisted_snapshot() {
+        let dir = std::env::temp_dir().join(format!("ai-pricing-client-{}", std::process::id()));
+        let path = dir.join("pricing.json");
+        persist::write_snapshot(&path, &crate::test_fixtures::pricing())
+            .await
+            .unwrap();
+
+        let client = PricingClient::builder()
+            .base_url("http://127.0.0.1:1")
+            .persist_to(&path)
+            .build()
+            .expect("Failed to build client");
+        let data = client
+            .get()
+            .await
+            .expect("should load the persisted snapshot");
+        assert_eq!(data.metered_price_id, "price_metered");
+
+        let client = PricingClient::builder()
+            .base_url("http://127.0.0.1:1")
+            .persist_to(&path)
+            .max_persisted_age(Duration::ZERO)
+            .build()
+            .expect("Failed to build client");
+        assert!(client.get().await.is_err());
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_url_source() {
+        let path =
+            std::env::temp_dir().join(format!("ai-pricing-file-url-{}.json", std::process::id()));
+        std::fs::write(&path, crate::test_fixtures::PRICING_JSON).unwrap();
+
+        let client = PricingClient::builder()
+            .url(format!("file://{}", path.display()))
+            .build()
+            .expect("Failed to build client");
+        let data = client.get().await.expect("Failed to read file source");
+        assert_eq!(data.providers.len(), 2);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_custom_source() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct CountingSource(AtomicUsize);
+
+        impl PricingSource for CountingSource {
+            async fn load(&self) -> Result<AiPricingJson, PricingError> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(crate::test_fixtures::pricing())
+            }
+        }
+
+        let client =
+            PricingClient::builder().build_with_source(CountingSource(AtomicUsize::new(0)));
+        client.get().await.unwrap();
+        client.get().await.unwrap();
+        assert_eq!(client.source().0.load(Ordering::SeqCst), 1);
+
+        client.refresh().await.unwrap();
+        assert_eq!(client.source().0.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_cold_fetches_are_deduplicated() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct SlowSource(AtomicUsize);
+
+        impl PricingSource for SlowSource {
+            async fn load(&self) -> Result<AiPricingJson, PricingError> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(crate::test_fixtures::pricing())
+            }
+        }
+
+        let client = PricingClient::builder().build_with_source(SlowSource(AtomicUsize::new(0)));
+        let tasks: Vec<_> = (0..50)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.get().await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().expect("Failed to load pricing");
+        }
+
+        assert_eq!(client.source().0.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,687 @@
+#[cfg(feature = "decimal")]
+use crate::decimal::to_decimal;
+use crate::{
+    Markup, MarkupOverride, Model, PriceTier, Pricing, PricingError, Provider, TextPricing,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(not(feature = "decimal"))]
+const TOKENS_PER_1M: f64 = 1_000_000.0;
+
+// ------------------
+// Text cost
+// ------------------
+
+/// The provider cost of a text request, broken down by token kind.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Cost {
+    pub input_cost: f64,
+    pub cached_input_cost: f64,
+    pub output_cost: f64,
+    #[serde(default)]
+    pub reasoning_output_cost: f64,
+    #[serde(default)]
+    pub cache_write_cost: f64,
+    /// Flat per-request charges.
+    #[serde(default)]
+    pub request_cost: f64,
+    /// Charges for tool calls.
+    #[serde(default)]
+    pub tool_cost: f64,
+    pub total: f64,
+}
+
+/// How a text request is billed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub enum CostMode {
+    /// A regular, synchronous request.
+    #[default]
+    Standard,
+    /// A request submitted through the provider's asynchronous batch API.
+    Batch,
+}
+
+/// Token counts for a text request, by how each is billed. Each count is
+/// billed at its own rate, so no token should be counted twice.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", default)]
+pub struct TokenUsage {
+    /// Uncached prompt tokens.
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Prompt tokens read from the cache.
+    pub cached_input_tokens: u64,
+    pub reasoning_tokens: u64,
+    /// Prompt tokens written to the cache.
+    pub cache_write_tokens: u64,
+    /// TTL of the cache writes, e.g. `1h`, selecting a rate from
+    /// [`TextPricing::cache_write_per1_m_by_ttl`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_write_ttl: Option<String>,
+    /// Batch requests pay the batch input and output prices where the model
+    /// has them. Other token kinds are billed at their usual rates.
+    pub mode: CostMode,
+    /// Requests made, for models with a [`per_request`](Model::per_request)
+    /// charge.
+    pub requests: u64,
+    /// Tool calls made, by tool name, priced by
+    /// [`tool_pricing`](Model::tool_pricing).
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_calls: BTreeMap<String, u64>,
+}
+
+impl TokenUsage {
+    /// Every prompt token, cached or not, as used to pick a
+    /// [`PriceTier`]. Saturates rather than overflowing.
+    pub fn prompt_tokens(&self) -> u64 {
+        self.input_tokens
+            .saturating_add(self.cached_input_tokens)
+            .saturating_add(self.cache_write_tokens)
+    }
+}
+
+impl TextPricing {
+    /// Price per 1M input tokens in `mode`, falling back to the standard price
+    /// for models without a batch price.
+    pub fn input_rate_per1_m(&self, mode: CostMode) -> f64 {
+        match mode {
+            CostMode::Standard => self.input_per1_m,
+            CostMode::Batch => self
+                .batch_input_per1_m
+                .or(self.batch_input_per1_k.map(|per1_k| per1_k * 1_000.0))
+                .unwrap_or(self.input_per1_m),
+        }
+    }
+
+    /// Price per 1M output tokens in `mode`, falling back to the standard
+    /// price for models without a batch price.
+    pub fn output_rate_per1_m(&self, mode: CostMode) -> f64 {
+        match mode {
+            CostMode::Standard => self.output_per1_m,
+            CostMode::Batch => self
+                .batch_output_per1_m
+                .or(self.batch_output_per1_k.map(|per1_k| per1_k * 1_000.0))
+                .unwrap_or(self.output_per1_m),
+        }
+    }
+
+    /// Price per 1M cached input tokens, falling back to the per-1K price and
+    /// then to the regular input price for models without a cached rate.
+    pub fn cached_input_rate_per1_m(&self) -> f64 {
+        self.cached_input_per1_m
+            .or(self.cached_input_per1_k.map(|per1_k| per1_k * 1_000.0))
+            .unwrap_or(self.input_per1_m)
+    }
+
+    /// Price per 1M reasoning tokens, falling back to the per-1K price and
+    /// then to the regular output price for models without a reasoning rate.
+    pub fn reasoning_output_rate_per1_m(&self) -> f64 {
+        self.reasoning_output_per1_m
+            .or(self.reasoning_output_per1_k.map(|per1_k| per1_k * 1_000.0))
+            .unwrap_or(self.output_per1_m)
+    }
+
+    /// Price per 1M tokens written to the cache with `ttl`. Falls back to the
+    /// untiered cache-write price, then to the regular input price, when
+    /// there's no price for `ttl`.
+    pub fn cache_write_rate_per1_m(&self, ttl: Option<&str>) -> f64 {
+        ttl.and_then(|ttl| self.cache_write_per1_m_by_ttl.get(ttl).copied())
+            .or(self.cache_write_per1_m)
+            .or(self.cache_write_per1_k.map(|per1_k| per1_k * 1_000.0))
+            .unwrap_or(self.input_per1_m)
+    }
+
+    /// The tier for a request with `prompt_tokens`: the first whose
+    /// `up_to_tokens` covers it. `None` for untiered pricing, or when the
+    /// request is larger than every tier.
+    pub fn tier(&self, prompt_tokens: u64) -> Option<&PriceTier> {
+        self.tiers.iter().find(|tier| {
+            tier.up_to_tokens
+                .is_none_or(|up_to_tokens| prompt_tokens <= up_to_tokens)
+        })
+    }
+
+    /// The tier that sets `usage`'s input and output prices. Batch requests
+    /// pay the batch prices instead, where the model has them.
+    pub(crate) fn standard_tier(&self, usage: &TokenUsage) -> Option<&PriceTier> {
+        let batch_priced = self.batch_input_per1_m.is_some() || self.batch_input_per1_k.is_some();
+        match usage.mode {
+            CostMode::Batch if batch_priced => None,
+            _ => self.tier(usage.prompt_tokens()),
+        }
+    }
+
+    /// Compute the provider cost of a request.
+    ///
+    /// `input_tokens` are the uncached prompt tokens; `cached_input_tokens` are
+    /// billed separately at the cached rate, so they should not also be counted
+    /// in `input_tokens`.
+    ///
+    /// With the `decimal` feature this is computed in decimal arithmetic, as
+    /// [`cost_decimal`](Self::cost_decimal), and only converted to `f64` at
+    /// the end.
+    pub fn cost(&self, input_tokens: u64, output_tokens: u64, cached_input_tokens: u64) -> Cost {
+        self.cost_with_reasoning(input_tokens, output_tokens, cached_input_tokens, 0)
+    }
+
+    /// [`cost`](Self::cost) for a request that also produced
+    /// `reasoning_tokens`, billed at the reasoning rate and reported as
+    /// `reasoning_output_cost`. They should not also be counted in
+    /// `output_tokens`.
+    pub fn cost_with_reasoning(
+        &self,
+        input_tokens: u64,
+        output_tokens: u64,
+        cached_input_tokens: u64,
+        reasoning_tokens: u64,
+    ) -> Cost {
+        self.cost_for_usage(&TokenUsage {
+            input_tokens,
+            output_tokens,
+            cached_input_tokens,
+            reasoning_tokens,
+            ..TokenUsage::default()
+        })
+    }
+
+    /// [`cost`](Self::cost) for every kind of token in `usage`, including
+    /// cache writes, which are reported as `cache_write_cost`. Request and
+    /// tool-call counts are priced by the model; see
+    /// [`Model::cost_for_usage`].
+    pub fn cost_for_usage(&self, usage: &TokenUsage) -> Cost {
+        #[cfg(feature = "decimal")]
+        {
+            self.cost_for_usage_decimal(usage).into()
+        }
+        #[cfg(not(feature = "decimal"))]
+        {
+            let per_token = |tokens: u64, per1_m: f64| tokens as f64 * per1_m / TOKENS_PER_1M;
+            let tier = self.standard_tier(usage);
+            let input_rate =
+                tier.map_or(self.input_rate_per1_m(usage.mode), |tier| tier.input_per1_m);
+            let output_rate = tier.map_or(self.output_rate_per1_m(usage.mode), |tier| {
+                tier.output_per1_m
+            });
+            let input_cost = per_token(usage.input_tokens, input_rate);
+            let cached_input_cost =
+                per_token(usage.cached_input_tokens, self.cached_input_rate_per1_m());
+            let output_cost = per_token(usage.output_tokens, output_rate);
+            let reasoning_output_cost =
+                per_token(usage.reasoning_tokens, self.reasoning_output_rate_per1_m());
+            let cache_write_cost = per_token(
+                usage.cache_write_tokens,
+                self.cache_write_rate_per1_m(usage.cache_write_ttl.as_deref()),
+            );
+
+            Cost {
+                input_cost,
+                cached_input_cost,
+                output_cost,
+                reasoning_output_cost,
+                cache_write_cost,
+                total: input_cost
+                    + cached_input_cost
+                    + output_cost
+                    + reasoning_output_cost
+                    + cache_write_cost,
+                ..Cost::default()
+            }
+        }
+    }
+}
+
+impl Model {
+    /// Provider cost of a text request to this model: its tokens, as for
+    /// [`TextPricing::cost_for_usage`], plus its per-request and tool-call
+    /// charges.
+    pub fn cost_for_usage(&self, usage: &TokenUsage) -> Result<Cost, PricingError> {
+        let Some(Pricing::TextPricing(pricing)) = &self.pricing else {
+            return Err(PricingError::Validation(format!(
+                "model {} has no text pricing",
+                self.key
+            )));
+        };
+        self.cost_at(pricing, usage)
+    }
+
+    /// [`cost_for_usage`](Self::cost_for_usage) with `pricing` in place of the
+    /// model's own token prices. With the `decimal` feature this is computed
+    /// in decimal arithmetic, surcharges included.
+    pub(crate) fn cost_at(
+        &self,
+        pricing: &TextPricing,
+        usage: &TokenUsage,
+    ) -> Result<Cost, PricingError> {
+        #[cfg(feature = "decimal")]
+        {
+            self.cost_at_decimal(pricing, usage).map(Cost::from)
+        }
+        #[cfg(not(feature = "decimal"))]
+        {
+            let mut cost = pricing.cost_for_usage(usage);
+            cost.request_cost = usage.requests as f64 * self.per_request.unwrap_or_default();
+            for (tool, calls) in &usage.tool_calls {
+                let per_call = self.tool_pricing.get(tool).ok_or_else(|| {
+                    PricingError::Validation(format!(
+                        "model {} has no price for tool {}",
+                        self.key, tool
+                    ))
+                })?;
+                cost.tool_cost += *calls as f64 * per_call;
+            }
+            cost.total += cost.request_cost + cost.tool_cost;
+            Ok(cost)
+        }
+    }
+}
+
+// ------------------
+// Markup
+// ------------------
+
+/// Which of a provider's markup percentages applies to a charge.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub enum Modality {
+    Text,
+    Image,
+}
+
+/// A provider cost, the markup on it, and the resulting customer price and
+/// margin, as produced by the cost APIs.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CostBreakdown {
+    pub provider_cost: f64,
+    pub markup_amount: f64,
+    pub customer_price: f64,
+    /// Gross margin as a percentage of the customer price, e.g. `20.0` for a
+    /// 25% markup. Zero when the customer price is zero.
+    pub margin_pct: f64,
+}
+
+impl CostBreakdown {
+    /// Break down a charge given its provider cost and customer price.
+    pub fn new(provider_cost: f64, customer_price: f64) -> Self {
+        #[cfg(feature = "decimal")]
+        {
+            crate::decimal::breakdown(to_decimal(provider_cost), to_decimal(customer_price))
+        }
+        #[cfg(not(feature = "decimal"))]
+        {
+            let markup_amount = customer_price - provider_cost;
+            let margin_pct = if customer_price == 0.0 {
+                0.0
+            } else {
+                markup_amount / customer_price * 100.0
+            };
+
+            CostBreakdown {
+                provider_cost,
+                markup_amount,
+                customer_price,
+                margin_pct,
+            }
+        }
+    }
+}
+
+#[deprecated(note = "renamed to `CostBreakdown`")]
+pub type MarkedUpPrice = CostBreakdown;
+
+impl Markup {
+    /// The markup percentage for `modality`, e.g. `20.0` for 20%.
+    pub fn percentage(&self, modality: Modality) -> f64 {
+        match modality {
+            Modality::Text => self.text_percentage,
+            Modality::Image => self.image_percentage,
+        }
+    }
+
+    /// Apply the markup for `modality` to a provider cost.
+    pub fn apply(&self, provider_cost: f64, modality: Modality) -> CostBreakdown {
+        #[cfg(feature = "decimal")]
+        {
+            self.apply_decimal(to_decimal(provider_cost), modality)
+        }
+        #[cfg(not(feature = "decimal"))]
+        {
+            let customer_price = provider_cost * (1.0 + self.percentage(modality) / 100.0);
+            CostBreakdown::new(provider_cost, customer_price)
+        }
+    }
+}
+
+impl Markup {
+    /// This markup with `overrides` applied on top.
+    pub fn merged(&self, overrides: &MarkupOverride) -> Markup {
+        Markup {
+            image_percentage: overrides.image_percentage.unwrap_or(self.image_percentage),
+            text_percentage: overrides.text_percentage.unwrap_or(self.text_percentage),
+        }
+    }
+}
+
+/// The markup that applies to `model`: `provider`'s, with the model's own
+/// override merged on top. The cost APIs price with this.
+pub fn effective_markup(provider: &Provider, model: &Model) -> Markup {
+    match &model.markup {
+        Some(overrides) => provider.markup.merged(overrides),
+        None => provider.markup.clone(),
+    }
+}
+
+impl Provider {
+    /// Apply this provider's markup to a provider cost.
+    pub fn apply_markup(&self, provider_cost: f64, modality: Modality) -> CostBreakdown {
+        self.markup.apply(provider_cost, modality)
+    }
+
+    /// Apply the markup for `model` to a provider cost. See
+    /// [`effective_markup`].
+    pub fn apply_model_markup(
+        &self,
+        model: &Model,
+        provider_cost: f64,
+        modality: Modality,
+    ) -> CostBreakdown {
+        effective_markup(self, model).apply(provider_cost, modality)
+    }
+
+    /// Provider cost and marked-up customer price of a text request to one of
+    /// this provider's models. Tokens are counted as for [`TextPricing::cost`].
+    #[deprecated(note = "ignores promotions; use `AiPricingJson::text_cost`")]
+    pub fn text_cost(
+        &self,
+        model_key: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cached_input_tokens: u64,
+    ) -> Result<CostBreakdown, PricingError> {
+        let model = self.find_model(model_key).ok_or_else(|| {
+            PricingError::Validation(format!("provider {} has no model {}", self.key, model_key))
+        })?;
+        let Some(Pricing::TextPricing(pricing)) = &model.pricing else {
+            return Err(PricingError::Validation(format!(
+                "model {} has no text pricing",
+                model.key
+            )));
+        };
+
+        let cost = pricing.cost(input_tokens, output_tokens, cached_input_tokens);
+        Ok(self.apply_model_markup(model, cost.total, Modality::Text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pricing() -> TextPricing {
+        TextPricing {
+            cached_input_per1_k: Some(0.00125),
+            cached_input_per1_m: Some(1.25),
+            reasoning_output_per1_k: None,
+            reasoning_output_per1_m: None,
+            cache_write_per1_k: None,
+            cache_write_per1_m: None,
+            cache_write_per1_m_by_ttl: Default::default(),
+            batch_input_per1_k: None,
+            batch_input_per1_m: None,
+            batch_output_per1_k: None,
+            batch_output_per1_m: None,
+            image_input_per1_k: None,
+            image_input_per1_m: None,
+            tiers: Vec::new(),
+            input_per1_k: 0.0025,
+            input_per1_m: 2.5,
+            output_per1_k: 0.01,
+            output_per1_m: 10.0,
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_text_cost() {
+        let cost = pricing().cost(1_000_000, 500_000, 200_000);
+        assert_eq!(cost.input_cost, 2.5);
+        assert_eq!(cost.output_cost, 5.0);
+        assert_eq!(cost.cached_input_cost, 0.25);
+        assert_eq!(cost.total, 7.75);
+    }
+
+    #[test]
+    fn test_cached_rate_falls_back_to_input() {
+        let pricing = TextPricing {
+            cached_input_per1_k: None,
+            cached_input_per1_m: None,
+            ..pricing()
+        };
+        assert_eq!(pricing.cost(0, 0, 1_000_000).cached_input_cost, 2.5);
+    }
+
+    #[test]
+    fn test_reasoning_cost() {
+        let cost = pricing().cost_with_reasoning(0, 100_000, 0, 400_000);
+        assert_eq!(cost.output_cost, 1.0);
+        assert_eq!(cost.reasoning_output_cost, 4.0);
+        assert_eq!(cost.total, 5.0);
+
+        let pricing: TextPricing = serde_json::from_value(serde_json::json!({
+            "inputPer1M": 15.0,
+            "outputPer1M": 60.0,
+            "reasoningOutputPer1K": 0.05
+        }))
+        .unwrap();
+        assert_eq!(pricing.reasoning_output_per1_m, Some(50.0));
+        let cost = pricing.cost_with_reasoning(0, 0, 0, 1_000_000);
+        assert_eq!(cost.reasoning_output_cost, 50.0);
+        assert_eq!(pricing.cost(0, 1_000_000, 0).reasoning_output_cost, 0.0);
+    }
+
+    #[test]
+    fn test_batch_cost() {
+        let pricing = TextPricing {
+            batch_input_per1_m: Some(1.25),
+            batch_output_per1_k: Some(0.005),
+            ..pricing()
+        };
+        let mut usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+        assert_eq!(pricing.cost_for_usage(&usage).total, 12.5);
+
+        usage.mode = CostMode::Batch;
+        let cost = pricing.cost_for_usage(&usage);
+        assert_eq!(cost.input_cost, 1.25);
+        assert_eq!(cost.output_cost, 5.0);
+        assert_eq!(self::pricing().cost_for_usage(&usage).total, 12.5);
+    }
+
+    #[test]
+    fn test_tiered_cost() {
+        let pricing: TextPricing = serde_json::from_value(serde_json::json!({
+            "inputPer1M": 1.25,
+            "outputPer1M": 5.0,
+            "tiers": [
+                { "upToTokens": 128000, "inputPer1M": 1.25, "outputPer1M": 5.0 },
+                { "inputPer1M": 2.5, "outputPer1M": 10.0 }
+            ]
+        }))
+        .unwrap();
+
+        let mut usage = TokenUsage {
+            input_tokens: 100_000,
+            cached_input_tokens: 28_000,
+            output_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+        assert_eq!(pricing.cost_for_usage(&usage).output_cost, 5.0);
+
+        usage.input_tokens += 1;
+        let cost = pricing.cost_for_usage(&usage);
+        assert_eq!(cost.output_cost, 10.0);
+        assert_eq!(cost.input_cost, 100_001.0 * 2.5 / 1_000_000.0);
+        assert_eq!(pricing.tier(1_000_000).unwrap().up_to_tokens, None);
+    }
+
+    #[test]
+    fn test_huge_prompt_picks_top_tier() {
+        let pricing: TextPricing = serde_json::from_value(serde_json::json!({
+            "inputPer1M": 1.25,
+            "outputPer1M": 5.0,
+            "tiers": [
+                { "upToTokens": 128000, "inputPer1M": 1.25, "outputPer1M": 5.0 },
+                { "inputPer1M": 2.5, "outputPer1M": 10.0 }
+            ]
+        }))
+        .unwrap();
+        let usage: TokenUsage = serde_json::from_value(serde_json::json!({
+            "inputTokens": u64::MAX,
+            "cachedInputTokens": 1
+        }))
+        .unwrap();
+
+        assert_eq!(usage.prompt_tokens(), u64::MAX);
+        let cost = pricing.cost_for_usage(&usage);
+        assert!(cost.total.is_finite());
+        assert!(cost.input_cost > 0.0);
+    }
+
+    #[test]
+    fn test_request_and_tool_charges() {
+        let fixture = crate::test_fixtures::pricing();
+        let mut gpt = fixture.find_model("openai", "gpt-4o").unwrap().clone();
+        gpt.per_request = Some(0.001);
+        gpt.tool_pricing.insert("web_search".to_string(), 0.01);
+
+        let mut usage = TokenUsage {
+            input_tokens: 1_000_000,
+            requests: 10,
+            ..TokenUsage::default()
+        };
+        usage.tool_calls.insert("web_search".to_string(), 3);
+        let cost = gpt.cost_for_usage(&usage).unwrap();
+        assert_eq!(cost.input_cost, 2.5);
+        assert_eq!(cost.request_cost, 0.01);
+        assert_eq!(cost.tool_cost, 0.03);
+        assert_eq!(cost.total, 2.54);
+
+        usage.tool_calls.insert("code_interpreter".to_string(), 1);
+        assert!(gpt.cost_for_usage(&usage).is_err());
+    }
+
+    #[test]
+    fn test_cache_write_cost() {
+        let pricing: TextPricing = serde_json::from_value(serde_json::json!({
+            "inputPer1M": 3.0,
+            "outputPer1M": 15.0,
+            "cachedInputPer1M": 0.3,
+            "cacheWritePer1M": 3.75,
+            "cacheWritePer1MByTtl": { "1h": "6" }
+        }))
+        .unwrap();
+        assert_eq!(pricing.cache_write_per1_k, Some(0.00375));
+
+        let mut usage = TokenUsage {
+            cached_input_tokens: 1_000_000,
+            cache_write_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+        let cost = pricing.cost_for_usage(&usage);
+        assert_eq!(cost.cached_input_cost, 0.3);
+        assert_eq!(cost.cache_write_cost, 3.75);
+        assert_eq!(cost.total, 4.05);
+
+        usage.cache_write_ttl = Some("1h".to_string());
+        assert_eq!(pricing.cost_for_usage(&usage).cache_write_cost, 6.0);
+        usage.cache_write_ttl = Some("1d".to_string());
+        assert_eq!(pricing.cost_for_usage(&usage).cache_write_cost, 3.75);
+
+        usage.cache_write_ttl = None;
+        assert_eq!(self::pricing().cost_for_usage(&usage).cache_write_cost, 2.5);
+    }
+
+    #[test]
+    fn test_markup_apply() {
+        let markup = Markup {
+            image_percentage: 50.0,
+            text_percentage: 20.0,
+        };
+
+        let text = markup.apply(10.0, Modality::Text);
+        assert_eq!(text.provider_cost, 10.0);
+        assert_eq!(text.customer_price, 12.0);
+        assert_eq!(text.markup_amount, 2.0);
+        assert!((text.margin_pct - 100.0 / 6.0).abs() < 1e-12);
+
+        let image = markup.apply(0.04, Modality::Image);
+        assert!((image.customer_price - 0.06).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_provider_text_cost() {
+        let pricing = crate::test_fixtures::pricing();
+
+        let breakdown = pricing
+            .text_cost("openai", "gpt-4o", 1_000_000, 0, 0)
+            .unwrap();
+        assert_eq!(breakdown.provider_cost, 2.5);
+        assert!(breakdown.markup_amount > 0.0);
+
+        assert!(pricing.text_cost("openai", "dall-e-3", 1, 1, 0).is_err());
+        assert!(pricing.text_cost("openai", "gpt-5", 1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_model_markup_override() {
+        let mut pricing = crate::test_fixtures::pricing();
+        let openai = &mut pricing.providers[0];
+        openai.models[0].markup = Some(
+            serde_json::from_value(serde_json::json!({ "textPercentage": "40" }))
+                .expect("Failed to parse"),
+        );
+
+        let markup = effective_markup(openai, &openai.models[0]);
+        assert_eq!(markup.text_percentage, 40.0);
+        assert_eq!(markup.image_percentage, 50.0);
+        assert_eq!(
+            effective_markup(openai, &openai.models[1]).text_percentage,
+            20.0
+        );
+
+        let breakdown = pricing
+            .text_cost("openai", "gpt-4o", 1_000_000, 0, 0)
+            .unwrap();
+        assert!((breakdown.customer_price - 3.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_breakdown_of_free_charge() {
+        let free = CostBreakdown::new(0.0, 0.0);
+        assert_eq!(free.markup_amount, 0.0);
+        assert_eq!(free.margin_pct, 0.0);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{effective_markup, AiPricingJson, Modality, Pricing, RoundingPolicy};
+use std::io::{self, Write};
+
+const HEADER: [&str; 13] = [
+    "provider",
+    "model",
+    "type",
+    "deprecated",
+    "input_per_1m",
+    "output_per_1m",
+    "cached_input_per_1m",
+    "customer_input_per_1m",
+    "customer_output_per_1m",
+    "customer_cached_input_per_1m",
+    "input_price_id",
+    "output_price_id",
+    "cached_input_price_id",
+];
+
+// ------------------
+// CSV export
+// ------------------
+
+impl AiPricingJson {
+    /// Write one CSV row per model, in file order, for spreadsheets. Prices
+    /// are per 1M tokens, and the customer columns include the model's
+    /// [`effective_markup`], rounded with [`RoundingPolicy::MicroCents`]. Price
+    /// columns are left empty for models without text pricing.
+    pub fn to_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        write_row(&mut writer, HEADER.iter().map(|s| s.to_string()))?;
+
+        for (provider, model) in self.all_models() {
+            let markup = effective_markup(provider, model);
+            let text = match &model.pricing {
+                Some(Pricing::TextPricing(pricing)) => Some(pricing),
+                _ => None,
+            };
+            let rate = |rate: Option<f64>| rate.map(|r| r.to_string()).unwrap_or_default();
+            let customer = |rate: Option<f64>| {
+                rate.map(|r| markup.apply(r, Modality::Text).customer_price)
+                    .map(|price| RoundingPolicy::MicroCents.round(price).to_string())
+                    .unwrap_or_default()
+            };
+            let input = text.map(|p| p.input_per1_m);
+            let output = text.map(|p| p.output_per1_m);
+            let cached_input = text.and_then(|p| p.cached_input_per1_m);
+            let ids = model.prod_price_ids.clone().unwrap_or_default();
+
+            write_row(
+                &mut writer,
+                [
+                    provider.key.clone(),
+                    model.key.clone(),
+                    model.model_type.to_string(),
+                    model.is_deprecated().to_string(),
+                    rate(input),
+                    rate(output),
+                    rate(cached_input),
+                    customer(input),
+                    customer(output),
+                    customer(cached_input),
+                    ids.input.unwrap_or_default(),
+                    ids.output.unwrap_or_default(),
+                    ids.cached_input.unwrap_or_default(),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn write_row(writer: &mut impl Write, fields: impl IntoIterator<Item = String>) -> io::Result<()> {
+    let fields: Vec<String> = fields.into_iter().map(|field| escape(&field)).collect();
+    writeln!(writer, "{}", fields.join(","))
+}
+
+/// Quote a field per RFC 4180 if it holds a comma, quote, or line break.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_to_csv() {
+        let mut out = Vec::new();
+        pricing().to_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("provider,model,type,deprecated,input_per_1m,"));
+        assert_eq!(
+            lines[1],
+            "openai,gpt-4o,text,false,2.5,10,1.25,3,12,1.5,\
+             price_gpt4o_input,price_gpt4o_output,price_gpt4o_cached"
+        );
+        assert_eq!(lines[2], "openai,dall-e-3,image,false,,,,,,,,,");
+    }
+
+    #[test]
+    fn test_customer_prices_have_no_float_noise() {
+        let mut pricing = pricing();
+        pricing.providers[0].models[0].pricing = Some(Pricing::TextPricing(
+            crate::TextPricing::from_per1_m(3.0, 15.0),
+        ));
+        let mut out = Vec::new();
+        pricing.to_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+
+        // 3 * 1.2 is 3.5999999999999996 in f64.
+        assert!(csv.contains("openai,gpt-4o,text,false,3,15,,3.6,18,,"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("gpt-4o"), "gpt-4o");
+        assert_eq!(escape("Acme, Inc."), "\"Acme, Inc.\"");
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
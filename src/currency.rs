@@ -0,0 +1,169 @@
+use crate::{AiPricingJson, Cost, CostBreakdown, PricingError};
+use std::collections::HashMap;
+
+/// The currency prices are in when the pricing file doesn't say.
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+impl AiPricingJson {
+    /// ISO 4217 code the prices are in, [`DEFAULT_CURRENCY`] unless the file
+    /// says otherwise.
+    pub fn currency(&self) -> &str {
+        self.currency.as_deref().unwrap_or(DEFAULT_CURRENCY)
+    }
+}
+
+// ------------------
+// Currency conversion
+// ------------------
+
+/// Supplies exchange rates, so costs can be shown and invoiced in other
+/// currencies. Implement it over your rate source, or use [`FixedRates`].
+///
+/// Closures taking `(from, to)` and returning the rate implement it too.
+pub trait CurrencyConverter {
+    /// How many units of `to` one unit of `from` buys, if known.
+    fn rate(&self, from: &str, to: &str) -> Option<f64>;
+
+    /// Convert `amount` from one currency to another.
+    fn convert(&self, amount: f64, from: &str, to: &str) -> Result<f64, PricingError> {
+        if from.eq_ignore_ascii_case(to) {
+            return Ok(amount);
+        }
+        self.rate(from, to)
+            .map(|rate| amount * rate)
+            .ok_or_else(|| PricingError::UnknownExchangeRate {
+                from: from.to_string(),
+                to: to.to_string(),
+            })
+    }
+}
+
+impl<F: Fn(&str, &str) -> Option<f64>> CurrencyConverter for F {
+    fn rate(&self, from: &str, to: &str) -> Option<f64> {
+        self(from, to)
+    }
+}
+
+/// Exchange rates against a single base currency, e.g. a daily snapshot of
+/// rates from the finance team. Cross rates go through the base.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedRates {
+    base: String,
+    rates: HashMap<String, f64>,
+}
+
+impl FixedRates {
+    /// An empty table of rates against `base`.
+    pub fn new(base: impl Into<String>) -> Self {
+        FixedRates {
+            base: base.into().to_ascii_uppercase(),
+            rates: HashMap::new(),
+        }
+    }
+
+    /// Set how many units of `currency` one unit of the base buys.
+    pub fn with_rate(mut self, currency: impl Into<String>, rate: f64) -> Self {
+        self.rates
+            .insert(currency.into().to_ascii_uppercase(), rate);
+        self
+    }
+
+    fn per_base(&self, currency: &str) -> Option<f64> {
+        let currency = currency.to_ascii_uppercase();
+        if currency == self.base {
+            return Some(1.0);
+        }
+        self.rates.get(&currency).copied()
+    }
+}
+
+impl CurrencyConverter for FixedRates {
+    fn rate(&self, from: &str, to: &str) -> Option<f64> {
+        let from = self.per_base(from)?;
+        let to = self.per_base(to)?;
+        (from != 0.0).then(|| to / from)
+    }
+}
+
+impl Cost {
+    /// This cost in another currency.
+    pub fn converted(
+        &self,
+        converter: &impl CurrencyConverter,
+        from: &str,
+        to: &str,
+    ) -> Result<Cost, PricingError> {
+        let convert = |amount| converter.convert(amount, from, to);
+        Ok(Cost {
+            input_cost: convert(self.input_cost)?,
+            cached_input_cost: convert(self.cached_input_cost)?,
+            output_cost: convert(self.output_cost)?,
+            reasoning_output_cost: convert(self.reasoning_output_cost)?,
+            cache_write_cost: convert(self.cache_write_cost)?,
+            request_cost: convert(self.request_cost)?,
+            tool_cost: convert(self.tool_cost)?,
+            total: convert(self.total)?,
+        })
+    }
+}
+
+impl CostBreakdown {
+    /// This breakdown in another currency. The margin is unchanged.
+    pub fn converted(
+        &self,
+        converter: &impl CurrencyConverter,
+        from: &str,
+        to: &str,
+    ) -> Result<CostBreakdown, PricingError> {
+        Ok(CostBreakdown {
+            provider_cost: converter.convert(self.provider_cost, from, to)?,
+            markup_amount: converter.convert(self.markup_amount, from, to)?,
+            customer_price: converter.convert(self.customer_price, from, to)?,
+            margin_pct: self.margin_pct,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_currency_defaults_to_usd() {
+        let mut pricing = pricing();
+        assert_eq!(pricing.currency(), "USD");
+        pricing.currency = Some("EUR".to_string());
+        assert_eq!(pricing.currency(), "EUR");
+    }
+
+    #[test]
+    fn test_fixed_rates() {
+        let rates = FixedRates::new("USD")
+            .with_rate("EUR", 0.5)
+            .with_rate("gbp", 0.25);
+        assert_eq!(rates.convert(10.0, "USD", "EUR").unwrap(), 5.0);
+        assert_eq!(rates.convert(10.0, "EUR", "GBP").unwrap(), 5.0);
+        assert_eq!(rates.convert(10.0, "eur", "EUR").unwrap(), 10.0);
+        assert!(matches!(
+            rates.convert(10.0, "USD", "JPY"),
+            Err(PricingError::UnknownExchangeRate { .. })
+        ));
+
+        let breakdown = CostBreakdown::new(1.0, 1.25)
+            .converted(&rates, "USD", "EUR")
+            .unwrap();
+        assert_eq!(breakdown.customer_price, 0.625);
+        assert_eq!(breakdown.margin_pct, 20.0);
+    }
+
+    #[test]
+    fn test_closure_converter() {
+        let converter = |from: &str, to: &str| (from == "USD" && to == "EUR").then_some(0.9);
+        let cost = Cost {
+            total: 10.0,
+            ..Cost::default()
+        };
+        assert_eq!(cost.converted(&converter, "USD", "EUR").unwrap().total, 9.0);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{AiPricingJson, Model, Provider};
+use chrono::{DateTime, NaiveDate, Utc};
+
+// ------------------
+// Model dates
+// ------------------
+
+/// Parse a model date, either date-only (`2024-05-13`, taken as midnight UTC)
+/// or RFC 3339 (`2024-05-13T09:30:00Z`).
+pub fn parse_model_date(date: &str) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc());
+    }
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+impl Model {
+    /// When the model was added to the pricing file. `None` if `added` isn't
+    /// a date [`parse_model_date`] understands.
+    pub fn added_at(&self) -> Option<DateTime<Utc>> {
+        parse_model_date(&self.added)
+    }
+
+    /// When the model was released by its provider. `None` if `created`
+    /// isn't a date [`parse_model_date`] understands.
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        parse_model_date(&self.created)
+    }
+
+    /// When the provider stops serving the model. `None` if there's no
+    /// `deprecation_date` or [`parse_model_date`] doesn't understand it.
+    pub fn deprecation_at(&self) -> Option<DateTime<Utc>> {
+        parse_model_date(self.deprecation_date.as_deref()?)
+    }
+}
+
+impl AiPricingJson {
+    /// Every model, most recently created first. Models with an unparseable
+    /// `created` date come last, in file order.
+    pub fn models_by_recency(&self) -> Vec<(&Provider, &Model)> {
+        let mut models: Vec<_> = self.all_models().collect();
+        models.sort_by_key(|(_, model)| std::cmp::Reverse(model.created_at()));
+        models
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_model_date() {
+        let midnight = Utc.with_ymd_and_hms(2024, 5, 13, 0, 0, 0).unwrap();
+        assert_eq!(parse_model_date("2024-05-13"), Some(midnight));
+        assert_eq!(
+            parse_model_date("2024-05-13T11:30:00+02:00"),
+            Some(Utc.with_ymd_and_hms(2024, 5, 13, 9, 30, 0).unwrap())
+        );
+        assert_eq!(parse_model_date("May 13, 2024"), None);
+        assert_eq!(parse_model_date("2024-02-30"), None);
+    }
+
+    #[test]
+    fn test_models_by_recency() {
+        let mut pricing = pricing();
+        pricing.providers[0].models[1].created = "unknown".to_string();
+        let keys: Vec<&str> = pricing
+            .models_by_recency()
+            .iter()
+            .map(|(_, model)| model.key.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            ["claude-3-5-sonnet", "gpt-4o", "claude-3-sonnet", "dall-e-3"]
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{
+    Cost, CostBreakdown, CostMode, Markup, Modality, Model, Pricing, PricingError, TextPricing,
+    TokenUsage,
+};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const TOKENS_PER_1M: Decimal = Decimal::from_parts(1_000_000, 0, 0, false, 0);
+const ONE_HUNDRED: Decimal = Decimal::ONE_HUNDRED;
+
+/// Convert a price to a decimal, as the shortest decimal that round-trips to
+/// the same `f64`. A price published as `0.15` becomes exactly `0.15`, rather
+/// than the binary approximation `f64` stores. Values beyond the range of
+/// `Decimal`, infinities included, saturate to [`Decimal::MAX`] or
+/// [`Decimal::MIN`]; `NaN` becomes zero.
+pub fn to_decimal(value: f64) -> Decimal {
+    if value.is_nan() {
+        return Decimal::ZERO;
+    }
+    Decimal::from_str(&value.to_string())
+        .or_else(|_| Decimal::try_from(value))
+        .unwrap_or(if value > 0.0 {
+            Decimal::MAX
+        } else {
+            Decimal::MIN
+        })
+}
+
+pub(crate) fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+// ------------------
+// Decimal prices
+// ------------------
+
+impl TextPricing {
+    pub fn input_per1_m_decimal(&self) -> Decimal {
+        to_decimal(self.input_per1_m)
+    }
+
+    pub fn output_per1_m_decimal(&self) -> Decimal {
+        to_decimal(self.output_per1_m)
+    }
+
+    /// [`input_rate_per1_m`](Self::input_rate_per1_m) as a decimal.
+    pub fn input_rate_per1_m_decimal(&self, mode: CostMode) -> Decimal {
+        match (mode, self.batch_input_per1_m, self.batch_input_per1_k) {
+            (CostMode::Batch, Some(per1_m), _) => to_decimal(per1_m),
+            (CostMode::Batch, None, Some(per1_k)) => to_decimal(per1_k) * Decimal::ONE_THOUSAND,
+            _ => self.input_per1_m_decimal(),
+        }
+    }
+
+    /// [`output_rate_per1_m`](Self::output_rate_per1_m) as a decimal.
+    pub fn output_rate_per1_m_decimal(&self, mode: CostMode) -> Decimal {
+        match (mode, self.batch_output_per1_m, self.batch_output_per1_k) {
+            (CostMode::Batch, Some(per1_m), _) => to_decimal(per1_m),
+            (CostMode::Batch, None, Some(per1_k)) => to_decimal(per1_k) * Decimal::ONE_THOUSAND,
+            _ => self.output_per1_m_decimal(),
+        }
+    }
+
+    /// [`cached_input_rate_per1_m`](Self::cached_input_rate_per1_m) as a
+    /// decimal.
+    pub fn cached_input_rate_per1_m_decimal(&self) -> Decimal {
+        match (self.cached_input_per1_m, self.cached_input_per1_k) {
+            (Some(per1_m), _) => to_decimal(per1_m),
+            (None, Some(per1_k)) => to_decimal(per1_k) * Decimal::ONE_THOUSAND,
+            (None, None) => self.input_per1_m_decimal(),
+        }
+    }
+
+    /// [`reasoning_output_rate_per1_m`](Self::reasoning_output_rate_per1_m)
+    /// as a decimal.
+    pub fn reasoning_output_rate_per1_m_decimal(&self) -> Decimal {
+        match (self.reasoning_output_per1_m, self.reasoning_output_per1_k) {
+            (Some(per1_m), _) => to_decimal(per1_m),
+            (None, Some(per1_k)) => to_decimal(per1_k) * Decimal::ONE_THOUSAND,
+            (None, None) => self.output_per1_m_decimal(),
+        }
+    }
+
+    /// [`cache_write_rate_per1_m`](Self::cache_write_rate_per1_m) as a
+    /// decimal.
+    pub fn cache_write_rate_per1_m_decimal(&self, ttl: Option<&str>) -> Decimal {
+        let tiered = ttl.and_then(|ttl| self.cache_write_per1_m_by_ttl.get(ttl));
+        match (tiered, self.cache_write_per1_m, self.cache_write_per1_k) {
+            (Some(per1_m), _, _) => to_decimal(*per1_m),
+            (None, Some(per1_m), _) => to_decimal(per1_m),
+            (None, None, Some(per1_k)) => to_decimal(per1_k) * Decimal::ONE_THOUSAND,
+            (None, None, None) => self.input_per1_m_decimal(),
+        }
+    }
+
+    /// [`cost`](Self::cost), in decimal arithmetic.
+    pub fn cost_decimal(
+        &self,
+        input_tokens: u64,
+        output_tokens: u64,
+        cached_input_tokens: u64,
+    ) -> DecimalCost {
+        self.cost_with_reasoning_decimal(input_tokens, output_tokens, cached_input_tokens, 0)
+    }
+
+    /// [`cost_with_reasoning`](Self::cost_with_reasoning), in decimal
+    /// arithmetic.
+    pub fn cost_with_reasoning_decimal(
+        &self,
+        input_tokens: u64,
+        output_tokens: u64,
+        cached_input_tokens: u64,
+        reasoning_tokens: u64,
+    ) -> DecimalCost {
+        self.cost_for_usage_decimal(&TokenUsage {
+            input_tokens,
+            output_tokens,
+            cached_input_tokens,
+            reasoning_tokens,
+            ..TokenUsage::default()
+        })
+    }
+
+    /// [`cost_for_usage`](Self::cost_for_usage), in decimal arithmetic.
+    pub fn cost_for_usage_decimal(&self, usage: &TokenUsage) -> DecimalCost {
+        let per_token =
+            |tokens: u64, per1_m: Decimal| Decimal::from(tokens) * per1_m / TOKENS_PER_1M;
+        let tier = self.standard_tier(usage);
+        let input_rate = tier.map_or(self.input_rate_per1_m_decimal(usage.mode), |tier| {
+            to_decimal(tier.input_per1_m)
+        });
+        let output_rate = tier.map_or(self.output_rate_per1_m_decimal(usage.mode), |tier| {
+            to_decimal(tier.output_per1_m)
+        });
+        let input_cost = per_token(usage.input_tokens, input_rate);
+        let cached_input_cost = per_token(
+            usage.cached_input_tokens,
+            self.cached_input_rate_per1_m_decimal(),
+        );
+        let output_cost = per_token(usage.output_tokens, output_rate);
+        let reasoning_output_cost = per_token(
+            usage.reasoning_tokens,
+            self.reasoning_output_rate_per1_m_decimal(),
+        );
+        let cache_write_cost = per_token(
+            usage.cache_write_tokens,
+            self.cache_write_rate_per1_m_decimal(usage.cache_write_ttl.as_deref()),
+        );
+
+        DecimalCost {
+            input_cost,
+            cached_input_cost,
+            output_cost,
+            reasoning_output_cost,
+            cache_write_cost,
+            total: input_cost
+                + cached_input_cost
+                + output_cost
+                + reasoning_output_cost
+                + cache_write_cost,
+            ..DecimalCost::default()
+        }
+    }
+}
+
+impl Model {
+    /// [`cost_for_usage`](Self::cost_for_usage), in decimal arithmetic,
+    /// including the per-request and tool-call charges.
+    pub fn cost_for_usage_decimal(&self, usage: &TokenUsage) -> Result<DecimalCost, PricingError> {
+        let Some(Pricing::TextPricing(pricing)) = &self.pricing else {
+            return Err(PricingError::Validation(format!(
+                "model {} has no text pricing",
+                self.key
+            )));
+        };
+        self.cost_at_decimal(pricing, usage)
+    }
+
+    /// [`cost_for_usage_decimal`](Self::cost_for_usage_decimal) with `pricing`
+    /// in place of the model's own token prices.
+    pub(crate) fn cost_at_decimal(
+        &self,
+        pricing: &TextPricing,
+        usage: &TokenUsage,
+    ) -> Result<DecimalCost, PricingError> {
+        let mut cost = pricing.cost_for_usage_decimal(usage);
+        cost.request_cost =
+            Decimal::from(usage.requests) * to_decimal(self.per_request.unwrap_or_default());
+        for (tool, calls) in &usage.tool_calls {
+            let per_call = self.tool_pricing.get(tool).ok_or_else(|| {
+                PricingError::Validation(format!(
+                    "model {} has no price for tool {}",
+                    self.key, tool
+                ))
+            })?;
+            cost.tool_cost += Decimal::from(*calls) * to_decimal(*per_call);
+        }
+        cost.total += cost.request_cost + cost.tool_cost;
+        Ok(cost)
+    }
+}
+
+impl Pricing {
+    /// [`image_cost`](Self::image_cost), in decimal arithmetic.
+    pub fn image_cost_decimal(
+        &self,
+        size: &str,
+        quality: Option<&str>,
+        n: u32,
+    ) -> Result<Decimal, PricingError> {
+        let price = self.image_price(size, quality)?;
+        Ok(to_decimal(price.cost_per_image) * Decimal::from(n))
+    }
+}
+
+/// [`Cost`] in decimal. Amounts serialize as strings, so no precision is lost.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DecimalCost {
+    #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+    pub input_cost: Decimal,
+    #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+    pub cached_input_cost: Decimal,
+    #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+    pub output_cost: Decimal,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+    pub reasoning_output_cost: Decimal,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+    pub cache_write_cost: Decimal,
+    /// Flat per-request charges.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+    pub request_cost: Decimal,
+    /// Charges for tool calls.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+    pub tool_cost: Decimal,
+    #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+    pub total: Decimal,
+}
+
+impl From<DecimalCost> for Cost {
+    fn from(cost: DecimalCost) -> Self {
+        Cost {
+            input_cost: to_f64(cost.input_cost),
+            cached_input_cost: to_f64(cost.cached_input_cost),
+            output_cost: to_f64(cost.output_cost),
+            reasoning_output_cost: to_f64(cost.reasoning_output_cost),
+            cache_write_cost: to_f64(cost.cache_write_cost),
+            request_cost: to_f64(cost.request_cost),
+            tool_cost: to_f64(cost.tool_cost),
+            total: to_f64(cost.total),
+        }
+    }
+}
+
+// ------------------
+// Decimal markup
+// ------------------
+
+impl Markup {
+    /// [`apply`](Self::apply), with the customer price and margin computed in
+    /// decimal arithmetic.
+    pub fn apply_decimal(&self, provider_cost: Decimal, modality: Modality) -> CostBreakdown {
+        let percentage = to_decimal(self.percentage(modality));
+        let customer_price = provider_cost * (Decimal::ONE + percentage / ONE_HUNDRED);
+        breakdown(provider_cost, customer_price)
+    }
+}
+
+pub(crate) fn breakdown(provider_cost: Decimal, customer_price: Decimal) -> CostBreakdown {
+    let markup_amount = customer_price - provider_cost;
+    let margin_pct = if customer_price.is_zero() {
+        Decimal::ZERO
+    } else {
+        markup_amount / customer_price * ONE_HUNDRED
+    };
+
+    CostBreakdown {
+        provider_cost: to_f64(provider_cost),
+        markup_amount: to_f64(markup_amount),
+        customer_price: to_f64(customer_price),
+        margin_pct: to_f64(margin_pct),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::Pricing;
+
+    #[test]
+    fn test_cost_has_no_float_drift() {
+        let Some(Pricing::TextPricing(mut text)) = pricing().providers[0].models[0].pricing.clone()
+        else {
+            panic!("gpt-4o should have text pricing");
+        };
+        text.input_per1_m = 0.1;
+
+        // 3 * 0.1 is 0.30000000000000004 in f64.
+        let cost = text.cost_decimal(3_000_000, 0, 0);
+        assert_eq!(cost.input_cost, Decimal::from_str("0.3").unwrap());
+        assert_eq!(text.cost(3_000_000, 0, 0).input_cost, 0.3);
+    }
+
+    #[test]
+    fn test_out_of_range_prices_saturate() {
+        assert_eq!(to_decimal(0.15), Decimal::from_str("0.15").unwrap());
+        assert_eq!(to_decimal(1e30), Decimal::MAX);
+        assert_eq!(to_decimal(f64::INFINITY), Decimal::MAX);
+        assert_eq!(to_decimal(-1e30), Decimal::MIN);
+        assert_eq!(to_decimal(f64::NEG_INFINITY), Decimal::MIN);
+        assert_eq!(to_decimal(f64::NAN), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_image_cost_has_no_float_drift() {
+        let pricing = pricing().providers[0].models[1].pricing.clone().unwrap();
+        // 0.04 * 3 is 0.12000000000000001 in f64.
+        assert_eq!(
+            pricing.image_cost_decimal("1024x1024", None, 3).unwrap(),
+            Decimal::from_str("0.12").unwrap()
+        );
+        assert_eq!(pricing.image_cost("1024x1024", None, 3).unwrap(), 0.12);
+    }
+
+    #[test]
+    fn test_surcharges_have_no_float_drift() {
+        let mut model = pricing().providers[0].models[0].clone();
+        model.per_request = Some(0.1);
+        model.tool_pricing.insert("web_search".to_string(), 0.01);
+        let mut usage = TokenUsage {
+            requests: 3,
+            ..TokenUsage::default()
+        };
+        usage.tool_calls.insert("web_search".to_string(), 7);
+
+        let cost = model.cost_for_usage_decimal(&usage).unwrap();
+        assert_eq!(cost.request_cost, Decimal::from_str("0.3").unwrap());
+        assert_eq!(cost.tool_cost, Decimal::from_str("0.07").unwrap());
+        assert_eq!(cost.total, Decimal::from_str("0.37").unwrap());
+        assert_eq!(model.cost_for_usage(&usage).unwrap().total, 0.37);
+    }
+
+    #[test]
+    fn test_markup_breakdown_is_exact() {
+        let markup = Markup {
+            image_percentage: 50.0,
+            text_percentage: 20.0,
+        };
+        let image = markup.apply(0.04, Modality::Image);
+        assert_eq!(image.customer_price, 0.06);
+        assert_eq!(image.markup_amount, 0.02);
+    }
+}
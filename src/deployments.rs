@@ -0,0 +1,114 @@
+use crate::{AiPricingJson, CostBreakdown, Deployment, Model, PricingError, Provider, TokenUsage};
+
+// ------------------
+// Deployments
+// ------------------
+
+impl Provider {
+    /// The deployment named `name`, if there is one.
+    pub fn deployment(&self, name: &str) -> Option<&Deployment> {
+        self.deployments.iter().find(|d| d.name == name)
+    }
+
+    /// The model behind the deployment named `name`.
+    ///
+    /// Fails if there's no such deployment, or it names a model this provider
+    /// doesn't have.
+    pub fn resolve_deployment(&self, name: &str) -> Result<&Model, PricingError> {
+        let deployment = self.deployment(name).ok_or_else(|| {
+            PricingError::Validation(format!("provider {} has no deployment {}", self.key, name))
+        })?;
+        self.find_model(&deployment.model_key).ok_or_else(|| {
+            PricingError::Validation(format!(
+                "deployment {} names unknown model {}",
+                name, deployment.model_key
+            ))
+        })
+    }
+
+    /// Provider cost and marked-up customer price of a text request to the
+    /// deployment named `name`, at the prices in its region.
+    #[deprecated(note = "ignores promotions; use `AiPricingJson::deployment_text_cost`")]
+    pub fn deployment_text_cost(
+        &self,
+        name: &str,
+        usage: &TokenUsage,
+    ) -> Result<CostBreakdown, PricingError> {
+        let model = self.resolve_deployment(name)?;
+        let region = self
+            .deployment(name)
+            .and_then(|d| d.region.as_deref())
+            .unwrap_or_default();
+        #[allow(deprecated)]
+        self.text_cost_in_region(&model.key, region, usage)
+    }
+}
+
+impl AiPricingJson {
+    /// [`Provider::resolve_deployment`] for a deployment of the provider with
+    /// `provider_key`.
+    pub fn resolve_deployment(
+        &self,
+        provider_key: &str,
+        name: &str,
+    ) -> Result<&Model, PricingError> {
+        self.find_provider(provider_key)
+            .ok_or_else(|| PricingError::Validation(format!("no provider {}", provider_key)))?
+            .resolve_deployment(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use serde_json::json;
+
+    fn with_deployments() -> AiPricingJson {
+        let mut pricing = pricing();
+        let openai = &mut pricing.providers[0];
+        openai.deployments = serde_json::from_value(json!([
+            { "name": "prod-chat", "region": "swedencentral", "modelKey": "gpt-4o" },
+            { "name": "images", "modelKey": "dall-e-3" },
+            { "name": "legacy", "modelKey": "gpt-35-turbo" }
+        ]))
+        .expect("Failed to parse");
+        openai.models[0].regions = serde_json::from_value(json!({
+            "swedencentral": { "inputPer1M": 2.75, "outputPer1M": 11.0 }
+        }))
+        .expect("Failed to parse");
+        pricing
+    }
+
+    #[test]
+    fn test_resolve_deployment() {
+        let pricing = with_deployments();
+        assert_eq!(
+            pricing
+                .resolve_deployment("openai", "prod-chat")
+                .unwrap()
+                .key,
+            "gpt-4o"
+        );
+        assert_eq!(
+            pricing.resolve_deployment("openai", "images").unwrap().key,
+            "dall-e-3"
+        );
+        assert!(pricing.resolve_deployment("openai", "legacy").is_err());
+        assert!(pricing.resolve_deployment("openai", "staging").is_err());
+        assert!(pricing.resolve_deployment("bedrock", "prod-chat").is_err());
+    }
+
+    #[test]
+    fn test_deployment_text_cost() {
+        let pricing = with_deployments();
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+        let cost = pricing
+            .deployment_text_cost("openai", "prod-chat", &usage)
+            .unwrap();
+        assert_eq!(cost.provider_cost, 2.75);
+    }
+}
@@ -0,0 +1,496 @@
+use crate::{
+    AiPricingJson, Model, ModelStatus, ModerationThreshold, Pricing, Provider, TextPricing,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// ------------------
+// Diff types
+// ------------------
+
+/// A model, identified by its provider's key and its own.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ModelRef {
+    pub provider_key: String,
+    pub model_key: String,
+}
+
+impl ModelRef {
+    fn new(provider: &Provider, model: &Model) -> Self {
+        ModelRef {
+            provider_key: provider.key.clone(),
+            model_key: model.key.clone(),
+        }
+    }
+}
+
+/// A price that changed, appeared, or disappeared on a model present in both
+/// snapshots.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PriceChange {
+    pub model: ModelRef,
+    /// `inputPer1M`, `outputPer1M`, `cachedInputPer1M`,
+    /// `reasoningOutputPer1M`, `cacheWritePer1M`, `cacheWrite[{ttl}]`,
+    /// `batchInputPer1M`, `batchOutputPer1M`, `imageInputPer1M`,
+    /// `tier[{upToTokens}].inputPer1M`, `image[{size}]`,
+    /// `image[{size}/{quality}]`, `video[{resolution}]`,
+    /// `transcriptionPerMinute`, `ttsPer1KCharacters`, `audioInputPerMinute`,
+    /// `audioOutputPerMinute`, `perRequest`, `tool[{name}]`, a `fineTuning.`
+    /// price such as `fineTuning.trainingPer1M`, or a text price in a region
+    /// such as `region[{region}].inputPer1M`.
+    pub field: String,
+    pub old: Option<f64>,
+    pub new: Option<f64>,
+    /// Change relative to the old price, e.g. `10.0` for a 10% increase.
+    /// `None` unless both prices are present and the old one is non-zero.
+    pub delta_pct: Option<f64>,
+}
+
+impl PriceChange {
+    fn new(model: ModelRef, field: String, old: Option<f64>, new: Option<f64>) -> Self {
+        let delta_pct = match (old, new) {
+            (Some(old), Some(new)) if old != 0.0 => Some((new - old) / old * 100.0),
+            _ => None,
+        };
+        PriceChange {
+            model,
+            field,
+            old,
+            new,
+            delta_pct,
+        }
+    }
+
+    pub fn is_increase(&self) -> bool {
+        matches!((self.old, self.new), (Some(old), Some(new)) if new > old)
+    }
+}
+
+/// A model whose lifecycle `status` changed, e.g. from `ga` to `legacy`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct StatusChange {
+    pub model: ModelRef,
+    pub old: Option<ModelStatus>,
+    pub new: Option<ModelStatus>,
+}
+
+/// A Stripe price ID that changed. `model` is `None` for the top-level
+/// `meteredPriceId`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PriceIdChange {
+    pub model: Option<ModelRef>,
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A provider-level setting that changed, such as a markup percentage
+/// (`textPercentage`) or a moderation threshold (`categoryScore.illicit`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SettingChange {
+    pub provider_key: String,
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// Everything that differs between two pricing snapshots, as reported by
+/// [`AiPricingJson::diff`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PricingDiff {
+    pub added_providers: Vec<String>,
+    pub removed_providers: Vec<String>,
+    /// Models only in the new snapshot, including those of added providers.
+    pub added_models: Vec<ModelRef>,
+    /// Models only in the old snapshot, including those of removed providers.
+    pub removed_models: Vec<ModelRef>,
+    /// Models in both snapshots that are deprecated only in the new one, by
+    /// flag or status. See [`Model::is_deprecated`].
+    pub deprecated_models: Vec<ModelRef>,
+    pub status_changes: Vec<StatusChange>,
+    pub price_changes: Vec<PriceChange>,
+    pub price_id_changes: Vec<PriceIdChange>,
+    pub markup_changes: Vec<SettingChange>,
+    pub moderation_changes: Vec<SettingChange>,
+}
+
+impl PricingDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == PricingDiff::default()
+    }
+}
+
+// ------------------
+// Diffing
+// ------------------
+
+impl AiPricingJson {
+    /// Compare this snapshot (the old one) with `other` (the new one).
+    pub fn diff(&self, other: &AiPricingJson) -> PricingDiff {
+        let mut diff = PricingDiff::default();
+
+        if self.metered_price_id != other.metered_price_id {
+            diff.price_id_changes.push(PriceIdChange {
+                model: None,
+                field: "meteredPriceId".to_string(),
+                old: Some(self.metered_price_id.clone()),
+                new: Some(other.metered_price_id.clone()),
+            });
+        }
+
+        for old in &self.providers {
+            match other.find_provider(&old.key) {
+                Some(new) => diff_provider(old, new, &mut diff),
+                None => {
+                    diff.removed_providers.push(old.key.clone());
+                    let models = old.models.iter().map(|m| ModelRef::new(old, m));
+                    diff.removed_models.extend(models);
+                }
+            }
+        }
+        for new in &other.providers {
+            if self.find_provider(&new.key).is_none() {
+                diff.added_providers.push(new.key.clone());
+                let models = new.models.iter().map(|m| ModelRef::new(new, m));
+                diff.added_models.extend(models);
+            }
+        }
+
+        diff
+    }
+}
+
+fn diff_provider(old: &Provider, new: &Provider, diff: &mut PricingDiff) {
+    for (field, old_pct, new_pct) in [
+        (
+            "textPercentage",
+            old.markup.text_percentage,
+            new.markup.text_percentage,
+        ),
+        (
+            "imagePercentage",
+            old.markup.image_percentage,
+            new.markup.image_percentage,
+        ),
+    ] {
+        if old_pct != new_pct {
+            diff.markup_changes.push(SettingChange {
+                provider_key: old.key.clone(),
+                field: field.to_string(),
+                old: old_pct.into(),
+                new: new_pct.into(),
+            });
+        }
+    }
+
+    diff.moderation_changes.extend(
+        diff_maps(
+            moderation_settings(&old.moderation_threshold),
+            moderation_settings(&new.moderation_threshold),
+        )
+        .map(|(field, old_value, new_value)| SettingChange {
+            provider_key: old.key.clone(),
+            field,
+            old: old_value.unwrap_or(Value::Null),
+            new: new_value.unwrap_or(Value::Null),
+        }),
+    );
+
+    for old_model in &old.models {
+        let model = ModelRef::new(old, old_model);
+        match new.models.iter().find(|m| m.key == old_model.key) {
+            Some(new_model) => diff_model(model, old_model, new_model, diff),
+            None => diff.removed_models.push(model),
+        }
+    }
+    for new_model in &new.models {
+        if !old.models.iter().any(|m| m.key == new_model.key) {
+            diff.added_models.push(ModelRef::new(new, new_model));
+        }
+    }
+}
+
+fn diff_model(model: ModelRef, old: &Model, new: &Model, diff: &mut PricingDiff) {
+    if !old.is_deprecated() && new.is_deprecated() {
+        diff.deprecated_models.push(model.clone());
+    }
+    if old.status != new.status {
+        diff.status_changes.push(StatusChange {
+            model: model.clone(),
+            old: old.status.clone(),
+            new: new.status.clone(),
+        });
+    }
+
+    diff.price_changes.extend(
+        diff_maps(prices(old), prices(new))
+            .map(|(field, old, new)| PriceChange::new(model.clone(), field, old, new)),
+    );
+
+    diff.price_id_changes
+        .extend(
+            diff_maps(price_ids(old), price_ids(new)).map(|(field, old, new)| PriceIdChange {
+                model: Some(model.clone()),
+                field,
+                old,
+                new,
+            }),
+        );
+}
+
+/// A model's prices by field name.
+pub(crate) fn prices(model: &Model) -> BTreeMap<String, f64> {
+    let mut prices = BTreeMap::new();
+    match &model.pricing {
+        Some(Pricing::TextPricing(text)) => text_prices(text, &mut prices),
+        Some(Pricing::RealtimePricing(realtime)) => {
+            text_prices(&realtime.text, &mut prices);
+            prices.insert(
+                "audioInputPerMinute".to_string(),
+                realtime.audio_input_per_minute,
+            );
+            prices.insert(
+                "audioOutputPerMinute".to_string(),
+                realtime.audio_output_per_minute,
+            );
+        }
+        Some(Pricing::ImagePricingVec(images)) => {
+            for image in images {
+                prices.insert(format!("image[{}]", image.label()), image.cost_per_image);
+            }
+        }
+        Some(Pricing::VideoPricing(video)) => {
+            for (resolution, price) in &video.cost_per_second {
+                prices.insert(format!("video[{}]", resolution), *price);
+            }
+        }
+        Some(Pricing::AudioPricing(audio)) => {
+            if let Some(price) = audio.transcription_per_minute {
+                prices.insert("transcriptionPerMinute".to_string(), price);
+            }
+            if let Some(price) = audio.tts_per1_k_characters {
+                prices.insert("ttsPer1KCharacters".to_string(), price);
+            }
+        }
+        None => {}
+    }
+    for (region, text) in &model.regions {
+        let mut regional = BTreeMap::new();
+        text_prices(text, &mut regional);
+        prices.extend(
+            regional
+                .into_iter()
+                .map(|(field, price)| (format!("region[{}].{}", region, field), price)),
+        );
+    }
+    if let Some(price) = model.per_request {
+        prices.insert("perRequest".to_string(), price);
+    }
+    for (tool, price) in &model.tool_pricing {
+        prices.insert(format!("tool[{}]", tool), *price);
+    }
+    if let Some(fine_tune) = &model.fine_tuning {
+        prices.insert(
+            "fineTuning.trainingPer1M".to_string(),
+            fine_tune.training_per1_m,
+        );
+        prices.insert(
+            "fineTuning.hostedInputPer1M".to_string(),
+            fine_tune.hosted_input_per1_m,
+        );
+        prices.insert(
+            "fineTuning.hostedOutputPer1M".to_string(),
+            fine_tune.hosted_output_per1_m,
+        );
+        if let Some(price) = fine_tune.storage_per_hour {
+            prices.insert("fineTuning.storagePerHour".to_string(), price);
+        }
+    }
+    prices
+}
+
+fn text_prices(text: &TextPricing, prices: &mut BTreeMap<String, f64>) {
+    prices.insert("inputPer1M".to_string(), text.input_per1_m);
+    prices.insert("outputPer1M".to_string(), text.output_per1_m);
+    if let Some(cached) = text.cached_input_per1_m {
+        prices.insert("cachedInputPer1M".to_string(), cached);
+    }
+    if let Some(reasoning) = text.reasoning_output_per1_m {
+        prices.insert("reasoningOutputPer1M".to_string(), reasoning);
+    }
+    if let Some(cache_write) = text.cache_write_per1_m {
+        prices.insert("cacheWritePer1M".to_string(), cache_write);
+    }
+    if let Some(batch_input) = text.batch_input_per1_m {
+        prices.insert("batchInputPer1M".to_string(), batch_input);
+    }
+    if let Some(batch_output) = text.batch_output_per1_m {
+        prices.insert("batchOutputPer1M".to_string(), batch_output);
+    }
+    if let Some(image_input) = text.image_input_per1_m {
+        prices.insert("imageInputPer1M".to_string(), image_input);
+    }
+    for tier in &text.tiers {
+        let limit = tier
+            .up_to_tokens
+            .map_or("max".to_string(), |l| l.to_string());
+        prices.insert(format!("tier[{}].inputPer1M", limit), tier.input_per1_m);
+        prices.insert(format!("tier[{}].outputPer1M", limit), tier.output_per1_m);
+    }
+    for (ttl, price) in &text.cache_write_per1_m_by_ttl {
+        prices.insert(format!("cacheWrite[{}]", ttl), *price);
+    }
+}
+
+/// A model's Stripe price IDs by field name.
+pub(crate) fn price_ids(model: &Model) -> BTreeMap<String, String> {
+    let Some(ids) = &model.prod_price_ids else {
+        return BTreeMap::new();
+    };
+    [
+        ("prodPriceIds.input", &ids.input),
+        ("prodPriceIds.output", &ids.output),
+        ("prodPriceIds.cachedInput", &ids.cached_input),
+    ]
+    .into_iter()
+    .filter_map(|(field, id)| Some((field.to_string(), id.clone()?)))
+    .collect()
+}
+
+fn moderation_settings(threshold: &ModerationThreshold) -> BTreeMap<String, Value> {
+    let categories = threshold
+        .categories
+        .iter()
+        .map(|(category, blocked)| (format!("categories.{}", category), blocked.into()));
+    let scores = threshold
+        .category_score
+        .iter()
+        .map(|(category, score)| (format!("categoryScore.{}", category), score.into()));
+
+    categories
+        .chain(scores)
+        .chain([("general".to_string(), threshold.general.into())])
+        .collect()
+}
+
+/// Every key whose value differs between `old` and `new`, in key order.
+fn diff_maps<T: PartialEq>(
+    mut old: BTreeMap<String, T>,
+    mut new: BTreeMap<String, T>,
+) -> impl Iterator<Item = (String, Option<T>, Option<T>)> {
+    let mut keys: Vec<String> = old.keys().chain(new.keys()).cloned().collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter().filter_map(move |key| {
+        let old_value = old.remove(&key);
+        let new_value = new.remove(&key);
+        (old_value != new_value).then_some((key, old_value, new_value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_identical_snapshots() {
+        assert!(pricing().diff(&pricing()).is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = pricing();
+        let mut new = pricing();
+        let openai = &mut new.providers[0];
+        openai.markup.text_percentage = 25.0;
+        openai.moderation_threshold.general = 0.7;
+        if let Some(Pricing::TextPricing(text)) = &mut openai.models[0].pricing {
+            text.input_per1_m = 2.75;
+            text.cached_input_per1_m = None;
+        }
+        openai.models[0].prod_price_ids.as_mut().unwrap().input = Some("price_new".to_string());
+        openai.models.remove(1);
+        new.providers[1].models[0].deprecated = Some(true);
+        new.providers.remove(1);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.removed_providers, ["bedrock"]);
+        assert_eq!(diff.removed_models.len(), 3);
+        assert_eq!(diff.removed_models[0].model_key, "dall-e-3");
+        assert!(diff.added_models.is_empty());
+
+        assert_eq!(diff.price_changes.len(), 2);
+        let cached = &diff.price_changes[0];
+        assert_eq!(cached.field, "cachedInputPer1M");
+        assert_eq!((cached.old, cached.new), (Some(1.25), None));
+        let input = &diff.price_changes[1];
+        assert_eq!(input.field, "inputPer1M");
+        assert!(input.is_increase());
+        assert!((input.delta_pct.unwrap() - 10.0).abs() < 1e-9);
+
+        assert_eq!(diff.price_id_changes[0].new.as_deref(), Some("price_new"));
+        assert_eq!(diff.markup_changes[0].field, "textPercentage");
+        assert_eq!(diff.moderation_changes[0].field, "general");
+        assert_eq!(diff.moderation_changes[0].new, 0.7);
+    }
+
+    #[test]
+    fn test_added_and_deprecated_models() {
+        let old = pricing();
+        let mut new = pricing();
+        new.providers[1].models[0].deprecated = Some(true);
+        let mut added = new.providers[1].models[0].clone();
+        added.key = "claude-4".to_string();
+        new.providers[1].models.push(added);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.deprecated_models[0].model_key, "claude-3-5-sonnet");
+        assert_eq!(diff.added_models[0].model_key, "claude-4");
+    }
+
+    #[test]
+    fn test_status_changes() {
+        let old = pricing();
+        let mut new = pricing();
+        new.providers[0].models[0].status = Some(ModelStatus::Retired);
+        new.providers[0].models[1].status = Some(ModelStatus::Ga);
+
+        let diff = old.diff(&new);
+        let deprecated: Vec<&str> = diff
+            .deprecated_models
+            .iter()
+            .map(|model| model.model_key.as_str())
+            .collect();
+        assert_eq!(deprecated, ["gpt-4o"]);
+        assert_eq!(diff.status_changes.len(), 2);
+        assert_eq!(diff.status_changes[0].old, None);
+        assert_eq!(diff.status_changes[0].new, Some(ModelStatus::Retired));
+    }
+}
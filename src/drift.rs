@@ -0,0 +1,142 @@
+use crate::{AiPricingJson, ModelRef, PriceChange, PriceIdChange};
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+use crate::{Env, PricingClient, PricingError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// ------------------
+// Environment drift
+// ------------------
+
+/// Where two environments' pricing disagrees, from [`check_drift`].
+///
+/// Changes are oriented from the first environment to the second, so a
+/// [`PriceChange`]'s `old` value is the first environment's.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DriftReport {
+    pub only_in_first: Vec<ModelRef>,
+    pub only_in_second: Vec<ModelRef>,
+    pub price_id_mismatches: Vec<PriceIdChange>,
+    pub price_mismatches: Vec<PriceChange>,
+}
+
+impl DriftReport {
+    /// True when both environments carry the same models, prices, and price
+    /// IDs.
+    pub fn is_clean(&self) -> bool {
+        *self == DriftReport::default()
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for model in &self.only_in_first {
+            writeln!(
+                f,
+                "{}/{}: only in first",
+                model.provider_key, model.model_key
+            )?;
+        }
+        for model in &self.only_in_second {
+            writeln!(
+                f,
+                "{}/{}: only in second",
+                model.provider_key, model.model_key
+            )?;
+        }
+        for change in &self.price_id_mismatches {
+            let model = match &change.model {
+                Some(model) => format!("{}/{}", model.provider_key, model.model_key),
+                None => "pricing".to_string(),
+            };
+            writeln!(
+                f,
+                "{}: {} is {} vs {}",
+                model,
+                change.field,
+                show(&change.old),
+                show(&change.new)
+            )?;
+        }
+        for change in &self.price_mismatches {
+            writeln!(
+                f,
+                "{}/{}: {} is {} vs {}",
+                change.model.provider_key,
+                change.model.model_key,
+                change.field,
+                show(&change.old),
+                show(&change.new)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn show<T: fmt::Display>(value: &Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "missing".to_string(),
+    }
+}
+
+/// Compare two environments' pricing, e.g. dev against prod, for models
+/// present in only one, mismatched price IDs, and diverging prices.
+pub fn check_drift(first: &AiPricingJson, second: &AiPricingJson) -> DriftReport {
+    let diff = first.diff(second);
+    DriftReport {
+        only_in_first: diff.removed_models,
+        only_in_second: diff.added_models,
+        price_id_mismatches: diff.price_id_changes,
+        price_mismatches: diff.price_changes,
+    }
+}
+
+/// Fetch both environments' pricing and [`check_drift`] between them.
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+pub async fn check_env_drift(first: Env, second: Env) -> Result<DriftReport, PricingError> {
+    let first = PricingClient::builder().env(first).build()?.get().await?;
+    let second = PricingClient::builder().env(second).build()?.get().await?;
+    Ok(check_drift(&first, &second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::Pricing;
+
+    #[test]
+    fn test_check_drift() {
+        let prod = pricing();
+        assert!(check_drift(&prod, &prod).is_clean());
+
+        let mut dev = pricing();
+        dev.providers[1].models.remove(0);
+        if let Some(Pricing::TextPricing(text)) = &mut dev.providers[0].models[0].pricing {
+            text.output_per1_m = 12.0;
+        }
+        dev.providers[0].models[0]
+            .prod_price_ids
+            .as_mut()
+            .unwrap()
+            .output = None;
+
+        let report = check_drift(&dev, &prod);
+        assert!(!report.is_clean());
+        assert_eq!(report.only_in_second[0].model_key, "claude-3-5-sonnet");
+        assert_eq!(report.price_mismatches[0].field, "outputPer1M");
+        assert_eq!(report.price_id_mismatches[0].field, "prodPriceIds.output");
+        assert_eq!(report.price_id_mismatches[0].old, None);
+        assert!(report
+            .to_string()
+            .contains("bedrock/claude-3-5-sonnet: only in second"));
+        assert!(report
+            .to_string()
+            .contains("openai/gpt-4o: outputPer1M is 12 vs 10"));
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Where bookcicle publishes its pricing files.
+pub const DEFAULT_BASE_URL: &str = "https://images.bookcicle.com/ai";
+
+// ------------------
+// Environment
+// ------------------
+
+/// The pricing environment to fetch. Each environment is published as its own
+/// JSON file next to the others under the client's base URL.
+///
+/// Parsing with [`FromStr`] only accepts the well-known names, so a typo like
+/// `"pord"` is an error rather than a fetch of a file that doesn't exist.
+/// Other environments have to be spelled out with [`Env::Custom`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Env {
+    Prod,
+    Dev,
+    Staging,
+    Custom(String),
+}
+
+impl Env {
+    /// The environment name as used in the published file name.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Env::Prod => "prod",
+            Env::Dev => "dev",
+            Env::Staging => "staging",
+            Env::Custom(name) => name,
+        }
+    }
+
+    /// Build the pricing URL for this environment under `base_url`.
+    ///
+    /// Prod is published as `ai-pricing.json`; every other environment as
+    /// `ai-pricing-{env}.json`.
+    pub fn pricing_url(&self, base_url: &str) -> String {
+        let base_url = base_url.trim_end_matches('/');
+        match self {
+            Env::Prod => format!("{}/ai-pricing.json", base_url),
+            other => format!("{}/ai-pricing-{}.json", base_url, other.as_str()),
+        }
+    }
+}
+
+impl FromStr for Env {
+    type Err = ParseEnvError;
+
+    fn from_str(env: &str) -> Result<Self, Self::Err> {
+        match env {
+            "prod" => Ok(Env::Prod),
+            "dev" => Ok(Env::Dev),
+            "staging" => Ok(Env::Staging),
+            other => Err(ParseEnvError(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Returned when parsing an unknown environment name.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown pricing environment {0:?} (expected prod, dev, or staging)")]
+pub struct ParseEnvError(pub String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pricing_url() {
+        let base = "https://images.bookcicle.com/ai/";
+        assert_eq!(
+            Env::Prod.pricing_url(base),
+            "https://images.bookcicle.com/ai/ai-pricing.json"
+        );
+        assert_eq!(
+            Env::Dev.pricing_url(base),
+            "https://images.bookcicle.com/ai/ai-pricing-dev.json"
+        );
+        assert_eq!(
+            Env::Custom("qa".to_string()).pricing_url(base),
+            "https://images.bookcicle.com/ai/ai-pricing-qa.json"
+        );
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for env in [Env::Prod, Env::Dev, Env::Staging] {
+            assert_eq!(env.to_string().parse::<Env>(), Ok(env));
+        }
+        assert_eq!(
+            "pord".parse::<Env>(),
+            Err(ParseEnvError("pord".to_string()))
+        );
+        assert_eq!(Env::Custom("qa".to_string()).to_string(), "qa");
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(feature = "http")]
+use reqwest::StatusCode;
+use std::error::Error as StdError;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+// ------------------
+// Pricing errors
+// ------------------
+
+/// Everything that can go wrong while fetching, caching, or checking pricing data.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PricingError {
+    /// The server answered with a non-success status code.
+    #[cfg(feature = "http")]
+    #[error("HTTP {status} fetching pricing from {url}")]
+    Http { status: StatusCode, url: String },
+
+    /// The request never produced a response (DNS, TLS, connection reset, ...).
+    #[cfg(feature = "http")]
+    #[error("request for pricing from {url} failed: {source}")]
+    Request {
+        #[source]
+        source: reqwest::Error,
+        url: String,
+    },
+
+    /// A local pricing file could not be read.
+    #[error("failed to read pricing from {}: {source}", path.display())]
+    Io {
+        #[source]
+        source: io::Error,
+        path: PathBuf,
+    },
+
+    /// The response body was not valid pricing JSON.
+    #[error("failed to deserialize pricing from {url}: {source}")]
+    Deserialize {
+        #[source]
+        source: serde_json::Error,
+        url: String,
+    },
+
+    /// A pricing file was not valid YAML.
+    #[cfg(feature = "yaml")]
+    #[error("failed to deserialize YAML pricing from {url}: {source}")]
+    Yaml {
+        #[source]
+        source: serde_yaml::Error,
+        url: String,
+    },
+
+    /// A pricing file was not valid TOML.
+    #[cfg(feature = "toml")]
+    #[error("failed to deserialize TOML pricing from {url}: {source}")]
+    Toml {
+        #[source]
+        source: toml::de::Error,
+        url: String,
+    },
+
+    /// The underlying HTTP client could not be constructed.
+    #[cfg(feature = "http")]
+    #[error("failed to build HTTP client: {0}")]
+    Client(#[source] reqwest::Error),
+
+    /// A custom [`PricingSource`](crate::PricingSource) failed.
+    #[error("pricing source failed: {0}")]
+    Source(#[source] Box<dyn StdError + Send + Sync>),
+
+    /// The cache already holds a value that could not be replaced.
+    #[error("pricing cache was already initialized")]
+    CacheConflict,
+
+    /// An image model was asked for a size it doesn't offer.
+    #[error("no image pricing for size {size} (available: {})", available.join(", "))]
+    UnknownImageSize {
+        size: String,
+        available: Vec<String>,
+    },
+
+    /// An image model was asked for a quality it doesn't offer at a size.
+    #[error("no image pricing for size {size} at quality {quality} (available: {})", available.join(", "))]
+    UnknownImageQuality {
+        size: String,
+        quality: String,
+        available: Vec<String>,
+    },
+
+    /// Following a model's replacement chain led to a missing model or back
+    /// to a model already visited.
+    #[error("cannot resolve replacement for model {model}: {reason}")]
+    Replacement { model: String, reason: String },
+
+    /// A video model was asked for a resolution it doesn't offer.
+    #[error("no video pricing for resolution {resolution} (available: {})", available.join(", "))]
+    UnknownVideoResolution {
+        resolution: String,
+        available: Vec<String>,
+    },
+
+    /// A [`CurrencyConverter`](crate::CurrencyConverter) had no rate for a
+    /// currency pair.
+    #[error("no exchange rate from {from} to {to}")]
+    UnknownExchangeRate { from: String, to: String },
+
+    /// A configuration environment variable such as `AI_PRICING_TTL_SECS`
+    /// held a value that could not be parsed.
+    #[error("invalid value {value:?} for {var}: {reason}")]
+    Config {
+        var: &'static str,
+        value: String,
+        reason: String,
+    },
+
+    /// The pricing file declares a `schemaVersion` this crate can't read,
+    /// typically one published for a newer release.
+    #[error("unsupported pricing schema version {version} (newest supported: {supported})")]
+    UnsupportedSchemaVersion {
+        version: serde_json::Value,
+        supported: u32,
+    },
+
+    /// A JSON Patch operation could not be applied.
+    #[error("patch operation {index} failed: {reason}")]
+    Patch { index: usize, reason: String },
+
+    /// The pricing data was well-formed but semantically invalid.
+    #[error("invalid pricing data: {0}")]
+    Validation(String),
+}
+
+impl PricingError {
+    /// Whether retrying the same request might succeed: connection failures,
+    /// timeouts, rate limiting, and server errors.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            #[cfg(feature = "http")]
+            PricingError::Http { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            #[cfg(feature = "http")]
+            PricingError::Request { .. } => true,
+            _ => false,
+        }
+    }
+}
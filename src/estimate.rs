@@ -0,0 +1,269 @@
+use crate::{AiPricingJson, CostBreakdown, PricingError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Days in the month used for projections, unless the profile sets its own.
+pub const DEFAULT_DAYS_PER_MONTH: f64 = 30.0;
+
+// ------------------
+// Usage profile
+// ------------------
+
+/// Expected daily usage across models, to project monthly costs from.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UsageProfile {
+    #[serde(default = "default_days_per_month")]
+    pub days_per_month: f64,
+    #[serde(default)]
+    pub models: Vec<ModelUsage>,
+}
+
+fn default_days_per_month() -> f64 {
+    DEFAULT_DAYS_PER_MONTH
+}
+
+impl Default for UsageProfile {
+    fn default() -> Self {
+        UsageProfile {
+            days_per_month: DEFAULT_DAYS_PER_MONTH,
+            models: Vec::new(),
+        }
+    }
+}
+
+/// Expected daily usage of one model.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ModelUsage {
+    pub provider_key: String,
+    pub model_key: String,
+    #[serde(default)]
+    pub text: Option<TextUsage>,
+    /// Images generated per day, by size.
+    #[serde(default)]
+    pub images_per_day: BTreeMap<String, f64>,
+}
+
+/// Expected daily text requests to a model and their average size.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TextUsage {
+    pub requests_per_day: f64,
+    pub avg_input_tokens: u64,
+    pub avg_output_tokens: u64,
+    #[serde(default)]
+    pub avg_cached_input_tokens: u64,
+}
+
+impl UsageProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn days_per_month(mut self, days: f64) -> Self {
+        self.days_per_month = days;
+        self
+    }
+
+    /// Add text usage of a model.
+    pub fn text(mut self, provider_key: &str, model_key: &str, usage: TextUsage) -> Self {
+        self.model_mut(provider_key, model_key).text = Some(usage);
+        self
+    }
+
+    /// Add `per_day` images of `size` from a model.
+    pub fn images(mut self, provider_key: &str, model_key: &str, size: &str, per_day: f64) -> Self {
+        self.model_mut(provider_key, model_key)
+            .images_per_day
+            .insert(size.to_string(), per_day);
+        self
+    }
+
+    fn model_mut(&mut self, provider_key: &str, model_key: &str) -> &mut ModelUsage {
+        let index = self
+            .models
+            .iter()
+            .position(|m| m.provider_key == provider_key && m.model_key == model_key)
+            .unwrap_or_else(|| {
+                self.models.push(ModelUsage {
+                    provider_key: provider_key.to_string(),
+                    model_key: model_key.to_string(),
+                    ..ModelUsage::default()
+                });
+                self.models.len() - 1
+            });
+        &mut self.models[index]
+    }
+}
+
+// ------------------
+// Estimate
+// ------------------
+
+/// Projected monthly provider cost and customer revenue for a
+/// [`UsageProfile`], per model, per provider, and overall.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Estimate {
+    pub models: Vec<ModelEstimate>,
+    pub providers: Vec<ProviderEstimate>,
+    pub total: CostBreakdown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ModelEstimate {
+    pub provider_key: String,
+    pub model_key: String,
+    pub monthly: CostBreakdown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderEstimate {
+    pub provider_key: String,
+    pub monthly: CostBreakdown,
+}
+
+impl AiPricingJson {
+    /// Project monthly costs for `profile` at this pricing, with each
+    /// provider's markup applied and promotions running now taken off to get
+    /// customer revenue.
+    ///
+    /// Fails if the profile names a model or image size that isn't priced.
+    pub fn estimate(&self, profile: &UsageProfile) -> Result<Estimate, PricingError> {
+        let mut estimate = Estimate::default();
+
+        for usage in &profile.models {
+            let daily = self.daily_cost(usage)?;
+            let monthly = scale(daily, profile.days_per_month);
+
+            estimate.models.push(ModelEstimate {
+                provider_key: usage.provider_key.clone(),
+                model_key: usage.model_key.clone(),
+                monthly,
+            });
+            match estimate
+                .providers
+                .iter_mut()
+                .find(|p| p.provider_key == usage.provider_key)
+            {
+                Some(total) => total.monthly = sum(total.monthly, monthly),
+                None => estimate.providers.push(ProviderEstimate {
+                    provider_key: usage.provider_key.clone(),
+                    monthly,
+                }),
+            }
+            estimate.total = sum(estimate.total, monthly);
+        }
+
+        Ok(estimate)
+    }
+}
+
+impl AiPricingJson {
+    fn daily_cost(&self, usage: &ModelUsage) -> Result<CostBreakdown, PricingError> {
+        let mut daily = CostBreakdown::default();
+
+        if let Some(text) = &usage.text {
+            let per_request = self.text_cost(
+                &usage.provider_key,
+                &usage.model_key,
+                text.avg_input_tokens,
+                text.avg_output_tokens,
+                text.avg_cached_input_tokens,
+            )?;
+            daily = sum(daily, scale(per_request, text.requests_per_day));
+        }
+        for (size, &per_day) in &usage.images_per_day {
+            let per_image =
+                self.image_cost(&usage.provider_key, &usage.model_key, size, None, 1)?;
+            daily = sum(daily, scale(per_image, per_day));
+        }
+
+        Ok(daily)
+    }
+}
+
+fn scale(breakdown: CostBreakdown, factor: f64) -> CostBreakdown {
+    CostBreakdown::new(
+        breakdown.provider_cost * factor,
+        breakdown.customer_price * factor,
+    )
+}
+
+fn sum(a: CostBreakdown, b: CostBreakdown) -> CostBreakdown {
+    CostBreakdown::new(
+        a.provider_cost + b.provider_cost,
+        a.customer_price + b.customer_price,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_estimate() {
+        let profile = UsageProfile::new()
+            .text(
+                "openai",
+                "gpt-4o",
+                TextUsage {
+                    requests_per_day: 100.0,
+                    avg_input_tokens: 10_000,
+                    avg_output_tokens: 1_000,
+                    avg_cached_input_tokens: 0,
+                },
+            )
+            .images("openai", "dall-e-3", "1024x1024", 10.0)
+            .images("openai", "dall-e-3", "1792x1024", 5.0);
+
+        let estimate = pricing().estimate(&profile).unwrap();
+        assert_eq!(estimate.models.len(), 2);
+        assert_eq!(estimate.providers.len(), 1);
+
+        // 100 requests * (10K * $2.50/1M + 1K * $10/1M) * 30 days
+        let gpt = &estimate.models[0].monthly;
+        assert!((gpt.provider_cost - 105.0).abs() < 1e-9);
+        // (10 * $0.04 + 5 * $0.08) * 30 days
+        let dalle = &estimate.models[1].monthly;
+        assert!((dalle.provider_cost - 24.0).abs() < 1e-9);
+
+        assert!((estimate.total.provider_cost - 129.0).abs() < 1e-9);
+        assert!(estimate.total.customer_price > estimate.total.provider_cost);
+        assert_eq!(estimate.providers[0].monthly, estimate.total);
+    }
+
+    #[test]
+    fn test_estimate_unknown_model() {
+        let profile = UsageProfile::new().images("openai", "dall-e-3", "512x512", 1.0);
+        assert!(matches!(
+            pricing().estimate(&profile),
+            Err(PricingError::UnknownImageSize { .. })
+        ));
+
+        let profile = UsageProfile::new().text("anthropic", "claude", TextUsage::default());
+        assert!(pricing().estimate(&profile).is_err());
+    }
+}
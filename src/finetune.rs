@@ -0,0 +1,89 @@
+use crate::{Cost, FineTunePricing, Model, PricingError};
+use std::time::Duration;
+
+const TOKENS_PER_1M: f64 = 1_000_000.0;
+const SECONDS_PER_HOUR: f64 = 3_600.0;
+
+// ------------------
+// Fine-tuning costs
+// ------------------
+
+impl FineTunePricing {
+    /// Provider cost of a training job that processed `trained_tokens`, i.e.
+    /// the training set's tokens times the number of epochs.
+    pub fn training_cost(&self, trained_tokens: u64) -> f64 {
+        trained_tokens as f64 * self.training_per1_m / TOKENS_PER_1M
+    }
+
+    /// Provider cost of a request to the fine-tuned model.
+    pub fn hosted_cost(&self, input_tokens: u64, output_tokens: u64) -> Cost {
+        let input_cost = input_tokens as f64 * self.hosted_input_per1_m / TOKENS_PER_1M;
+        let output_cost = output_tokens as f64 * self.hosted_output_per1_m / TOKENS_PER_1M;
+
+        Cost {
+            input_cost,
+            output_cost,
+            total: input_cost + output_cost,
+            ..Cost::default()
+        }
+    }
+
+    /// Provider cost of hosting the fine-tuned model for `duration`. Zero for
+    /// providers that don't charge for hosting.
+    pub fn storage_cost(&self, duration: Duration) -> f64 {
+        let per_hour = self.storage_per_hour.unwrap_or_default();
+        duration.as_secs_f64() / SECONDS_PER_HOUR * per_hour
+    }
+}
+
+impl Model {
+    /// This model's fine-tuning prices.
+    pub fn fine_tune_pricing(&self) -> Result<&FineTunePricing, PricingError> {
+        self.fine_tuning.as_ref().ok_or_else(|| {
+            PricingError::Validation(format!("model {} cannot be fine-tuned", self.key))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use serde_json::json;
+
+    #[test]
+    fn test_fine_tune_costs() {
+        let mut pricing = pricing();
+        let gpt = &mut pricing.providers[0].models[0];
+        assert!(gpt.fine_tune_pricing().is_err());
+
+        gpt.fine_tuning = Some(
+            serde_json::from_value(json!({
+                "trainingPer1M": 25.0,
+                "hostedInputPer1M": "3.75",
+                "hostedOutputPer1M": 15.0
+            }))
+            .expect("Failed to parse"),
+        );
+        let fine_tune = gpt.fine_tune_pricing().unwrap();
+        assert_eq!(fine_tune.training_cost(2_000_000), 50.0);
+        let cost = fine_tune.hosted_cost(1_000_000, 100_000);
+        assert_eq!(cost.input_cost, 3.75);
+        assert_eq!(cost.total, 5.25);
+        assert_eq!(fine_tune.storage_cost(Duration::from_secs(7_200)), 0.0);
+
+        let json = serde_json::to_value(&*gpt).unwrap();
+        assert_eq!(json["fineTuning"]["hostedInputPer1M"], 3.75);
+        assert!(json["fineTuning"].get("storagePerHour").is_none());
+    }
+
+    #[test]
+    fn test_storage_cost() {
+        let fine_tune = FineTunePricing {
+            storage_per_hour: Some(1.7),
+            ..Default::default()
+        };
+        let cost = fine_tune.storage_cost(Duration::from_secs(5_400));
+        assert!((cost - 2.55).abs() < 1e-12);
+    }
+}
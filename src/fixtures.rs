@@ -0,0 +1,52 @@
+use crate::AiPricingJson;
+
+/// Sample pricing JSON embedded from `data/sample-pricing.json`.
+///
+/// Unlike the bundled `data/ai-pricing.json` it is not refreshed from
+/// published pricing, so tests built on it stay stable across releases.
+pub const SAMPLE_PRICING_JSON: &str = include_str!("../data/sample-pricing.json");
+
+impl AiPricingJson {
+    /// A small but representative dataset for downstream unit tests: OpenAI
+    /// and Anthropic text models with cached-input and cache-write prices,
+    /// deprecated models with replacements, aliases, and an image model.
+    /// Every text model carries prod price IDs.
+    pub fn sample() -> AiPricingJson {
+        serde_json::from_str(SAMPLE_PRICING_JSON).expect("sample pricing JSON is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Env, ModelType};
+
+    #[test]
+    fn test_sample_pricing_is_valid() {
+        let sample = AiPricingJson::sample();
+        let report = sample.validate_for_env(&Env::Prod);
+        assert!(report.is_ok(), "sample pricing has issues:\n{}", report);
+
+        let models: Vec<_> = sample.all_models().map(|(_, model)| model).collect();
+        assert!(models.iter().any(|m| m.model_type == ModelType::Image));
+        assert!(models.iter().any(|m| m.deprecated == Some(true)));
+        assert!(sample.providers.len() > 1);
+    }
+
+    #[test]
+    fn test_sample_costs() {
+        let sample = AiPricingJson::sample();
+        let anthropic = &sample.providers[1].key;
+        let cost = sample
+            .text_cost(
+                anthropic,
+                "claude-3-5-sonnet-20241022",
+                1_000_000,
+                1_000_000,
+                0,
+            )
+            .unwrap();
+        assert_eq!(cost.provider_cost, 18.0);
+        assert_eq!(cost.customer_price, 22.5);
+    }
+}
@@ -0,0 +1,772 @@
+use crate::{
+    AiPricingJson, AudioPricing, AuthScheme, Categories, CategoryScore, Deployment, Discount,
+    Encoder, FineTunePricing, ImagePricing, ImageSize, Markup, MarkupOverlay, MarkupOverride,
+    Model, ModelFeature, ModelStatus, ModelType, ModerationOverride, ModerationThreshold,
+    PlanMarkup, PriceTier, Pricing, ProdPriceIds, Promotion, Provider, ProviderAuth, RateLimits,
+    RealtimePricing, RoundingPolicy, TextPricing, TokenUsage, VideoPricing,
+};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use std::collections::{BTreeMap, HashSet};
+
+// Prices are whole millionths of a dollar, up to $100, so they survive a JSON
+// round-trip and the per-1K/per-1M halves of a pair always agree.
+const MAX_PRICE_MICROS: u64 = 100_000_000;
+
+// Token counts stay far enough below `u64::MAX` that sums and products of
+// them can't overflow, as a real request's never would.
+const MAX_TOKENS: u64 = 10_000_000_000;
+const MAX_CALLS: u64 = 1_000_000;
+
+const TOOL_NAMES: [&str; 3] = ["web_search", "file_search", "code_interpreter"];
+const PROVIDER_KEYS: [&str; 6] = [
+    "openai",
+    "anthropic",
+    "bedrock",
+    "azure",
+    "mistral",
+    "google",
+];
+const MODEL_NAMES: [&str; 6] = ["gpt", "claude", "llama", "mistral", "gemini", "titan"];
+const MODERATION_CATEGORIES: [&str; 6] = [
+    "hate",
+    "harassment/threatening",
+    "illicit",
+    "self-harm",
+    "sexual/minors",
+    "violence/graphic",
+];
+
+// ------------------
+// Arbitrary pricing data
+// ------------------
+
+// Generated data is structurally valid: it passes `AiPricingJson::validate`
+// without errors and round-trips through JSON unchanged. Prices and markups
+// are non-negative, keys are unique, each model's pricing matches its type,
+// and deployments and promotions only name models that exist.
+
+fn price(u: &mut Unstructured<'_>) -> Result<f64> {
+    Ok(u.int_in_range(0..=MAX_PRICE_MICROS)? as f64 / 1_000_000.0)
+}
+
+fn optional_price(u: &mut Unstructured<'_>) -> Result<Option<f64>> {
+    Ok(match u.arbitrary()? {
+        true => Some(price(u)?),
+        false => None,
+    })
+}
+
+/// A fraction in `0..=1` with two decimals.
+fn fraction(u: &mut Unstructured<'_>) -> Result<f64> {
+    Ok(u.int_in_range(0..=100u32)? as f64 / 100.0)
+}
+
+/// A markup percentage in `0..=100`, whole or with one decimal.
+fn percentage(u: &mut Unstructured<'_>) -> Result<f64> {
+    Ok(u.int_in_range(0..=1_000u32)? as f64 / 10.0)
+}
+
+/// A `YYYY-MM-DD` date from 2020 through 2029.
+fn date(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(format!(
+        "{}-{:02}-{:02}",
+        u.int_in_range(2020..=2029u32)?,
+        u.int_in_range(1..=12u32)?,
+        u.int_in_range(1..=28u32)?
+    ))
+}
+
+fn price_id(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(format!("price_{:08x}", u.arbitrary::<u32>()?))
+}
+
+/// `name` if unused, otherwise `name-2`, `name-3`, ...
+fn unique(taken: &mut HashSet<String>, name: &str) -> String {
+    let mut key = name.to_string();
+    let mut n = 1;
+    while !taken.insert(key.clone()) {
+        n += 1;
+        key = format!("{}-{}", name, n);
+    }
+    key
+}
+
+/// Both halves of a per-1K/per-1M pair, or neither.
+fn optional_pair(u: &mut Unstructured<'_>) -> Result<(Option<f64>, Option<f64>)> {
+    Ok(match optional_price(u)? {
+        Some(per1_m) => (Some(per1_m / 1_000.0), Some(per1_m)),
+        None => (None, None),
+    })
+}
+
+impl<'a> Arbitrary<'a> for AiPricingJson {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut provider_keys = HashSet::new();
+        let mut providers = Vec::new();
+        for _ in 0..u.int_in_range(1..=3)? {
+            let mut provider = Provider::arbitrary(u)?;
+            provider.key = unique(&mut provider_keys, &provider.key);
+            providers.push(provider);
+        }
+
+        let model_keys: Vec<String> = providers
+            .iter()
+            .flat_map(|provider| provider.models.iter().map(|model| model.key.clone()))
+            .collect();
+        let mut promotion_ids = HashSet::new();
+        let mut promotions = Vec::new();
+        for _ in 0..u.int_in_range(0..=2)? {
+            let mut promotion = Promotion::arbitrary(u)?;
+            promotion.id = unique(&mut promotion_ids, &promotion.id);
+            if !model_keys.is_empty() && u.arbitrary()? {
+                promotion.model_keys = vec![u.choose(&model_keys)?.clone()];
+            }
+            promotions.push(promotion);
+        }
+
+        Ok(AiPricingJson {
+            schema_version: crate::SCHEMA_VERSION,
+            metered_price_id: price_id(u)?,
+            providers,
+            currency: match u.arbitrary()? {
+                true => Some(u.choose(&["USD", "EUR", "GBP"])?.to_string()),
+                false => None,
+            },
+            markup_overlay: MarkupOverlay::arbitrary(u)?,
+            promotions,
+            rounding: RoundingPolicy::arbitrary(u)?,
+            extra: Default::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Provider {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let key = u.choose(&PROVIDER_KEYS)?.to_string();
+        let mut model_keys = HashSet::new();
+        let mut models = Vec::new();
+        for _ in 0..u.int_in_range(1..=4)? {
+            let mut model = Model::arbitrary(u)?;
+            model.key = unique(&mut model_keys, &model.key);
+            models.push(model);
+        }
+
+        let mut deployments = Vec::new();
+        for i in 0..u.int_in_range(0..=2)? {
+            let mut deployment = Deployment::arbitrary(u)?;
+            deployment.name = format!("{}-{}", deployment.name, i + 1);
+            deployment.model_key = u.choose(&models)?.key.clone();
+            deployments.push(deployment);
+        }
+
+        Ok(Provider {
+            description: format!("{} models", key),
+            label: key.clone(),
+            markup: Markup::arbitrary(u)?,
+            models,
+            moderation_threshold: ModerationThreshold::arbitrary(u)?,
+            provider_host: format!("api.{}.example.com", key),
+            website: format!("https://{}.example.com", key),
+            rate_limits: u.arbitrary()?,
+            auth: u.arbitrary()?,
+            deployments,
+            key,
+            extra: Default::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Deployment {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Deployment {
+            name: u.choose(&["prod", "staging", "eval"])?.to_string(),
+            region: match u.arbitrary()? {
+                true => Some(
+                    u.choose(&["eastus", "westeurope", "us-east-1"])?
+                        .to_string(),
+                ),
+                false => None,
+            },
+            model_key: String::new(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ProviderAuth {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let scheme = AuthScheme::arbitrary(u)?;
+        let mut required_headers = BTreeMap::new();
+        if u.arbitrary()? {
+            required_headers.insert("api-version".to_string(), date(u)?);
+        }
+        Ok(ProviderAuth {
+            header: match scheme {
+                AuthScheme::Header => Some("x-api-key".to_string()),
+                _ => None,
+            },
+            scheme,
+            env_vars: vec!["PROVIDER_API_KEY".to_string()],
+            required_headers,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AuthScheme {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(
+            u.choose(&[AuthScheme::Bearer, AuthScheme::Header, AuthScheme::AwsSigV4])?
+                .clone(),
+        )
+    }
+}
+
+impl<'a> Arbitrary<'a> for Markup {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Markup {
+            image_percentage: percentage(u)?,
+            text_percentage: percentage(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for MarkupOverride {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let optional_percentage = |u: &mut Unstructured<'a>| -> Result<Option<f64>> {
+            Ok(match u.arbitrary()? {
+                true => Some(percentage(u)?),
+                false => None,
+            })
+        };
+        Ok(MarkupOverride {
+            image_percentage: optional_percentage(u)?,
+            text_percentage: optional_percentage(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for MarkupOverlay {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut plans = BTreeMap::new();
+        for plan in ["free", "pro", "enterprise"] {
+            if u.arbitrary()? {
+                plans.insert(plan.to_string(), PlanMarkup::arbitrary(u)?);
+            }
+        }
+        Ok(MarkupOverlay { plans })
+    }
+}
+
+impl<'a> Arbitrary<'a> for PlanMarkup {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // Whole percentage points either way, so discounts are covered too.
+        Ok(PlanMarkup {
+            image_adjustment: u.int_in_range(-50..=50i32)? as f64,
+            text_adjustment: u.int_in_range(-50..=50i32)? as f64,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Promotion {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Promotion {
+            id: u.choose(&["launch", "holiday", "partner"])?.to_string(),
+            discount: Discount::arbitrary(u)?,
+            starts: None,
+            ends: None,
+            model_keys: vec![],
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Discount {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.arbitrary()? {
+            true => Discount::Percentage(percentage(u)?),
+            false => Discount::Fixed(price(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RateLimits {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let limit = |u: &mut Unstructured<'a>, max: u64| -> Result<Option<u64>> {
+            Ok(match u.arbitrary()? {
+                true => Some(u.int_in_range(1..=max)?),
+                false => None,
+            })
+        };
+        Ok(RateLimits {
+            tpm: limit(u, 10_000_000)?,
+            rpm: limit(u, 100_000)?,
+            concurrent: limit(u, 1_000)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ModerationThreshold {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ModerationThreshold {
+            categories: Categories::arbitrary(u)?,
+            category_score: CategoryScore::arbitrary(u)?,
+            general: fraction(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Categories {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut categories = Categories::default();
+        for category in MODERATION_CATEGORIES {
+            categories.set(category, u.arbitrary()?);
+        }
+        Ok(categories)
+    }
+}
+
+impl<'a> Arbitrary<'a> for CategoryScore {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut scores = CategoryScore::default();
+        for category in MODERATION_CATEGORIES {
+            if u.arbitrary()? {
+                scores.set(category, fraction(u)?);
+            }
+        }
+        Ok(scores)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ModerationOverride {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut categories = BTreeMap::new();
+        let mut category_score = BTreeMap::new();
+        for category in MODERATION_CATEGORIES {
+            match u.int_in_range(0..=2u8)? {
+                0 => {}
+                1 => {
+                    categories.insert(category.to_string(), u.arbitrary()?);
+                }
+                _ => {
+                    category_score.insert(category.to_string(), fraction(u)?);
+                }
+            }
+        }
+        Ok(ModerationOverride {
+            categories,
+            category_score,
+            general: match u.arbitrary()? {
+                true => Some(fraction(u)?),
+                false => None,
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Model {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let model_type = ModelType::arbitrary(u)?;
+        let pricing = match model_type {
+            ModelType::Image => Pricing::ImagePricingVec(arbitrary_image_sizes(u)?),
+            ModelType::Audio => Pricing::AudioPricing(AudioPricing::arbitrary(u)?),
+            ModelType::Video => Pricing::VideoPricing(VideoPricing::arbitrary(u)?),
+            ModelType::Realtime => Pricing::RealtimePricing(RealtimePricing::arbitrary(u)?),
+            _ => Pricing::TextPricing(TextPricing::arbitrary(u)?),
+        };
+        let is_text = model_type == ModelType::Text;
+
+        let mut features = Vec::new();
+        for feature in [
+            ModelFeature::Vision,
+            ModelFeature::FunctionCalling,
+            ModelFeature::JsonMode,
+            ModelFeature::StructuredOutputs,
+            ModelFeature::Streaming,
+        ] {
+            if is_text && u.arbitrary()? {
+                features.push(feature);
+            }
+        }
+
+        let context_window = match is_text && u.arbitrary()? {
+            true => Some(u.int_in_range(1_024..=2_000_000u64)?),
+            false => None,
+        };
+        let max_output_tokens = match context_window {
+            Some(window) if u.arbitrary()? => Some(u.int_in_range(1..=window)?),
+            _ => None,
+        };
+
+        let mut regions = BTreeMap::new();
+        if is_text && u.ratio(1, 4)? {
+            regions.insert("us-east-1".to_string(), TextPricing::arbitrary(u)?);
+        }
+
+        let mut tool_pricing = BTreeMap::new();
+        if is_text && u.ratio(1, 4)? {
+            tool_pricing.insert("web_search".to_string(), price(u)?);
+        }
+
+        let added = date(u)?;
+        Ok(Model {
+            created: added.clone(),
+            added,
+            features,
+            key: u.choose(&MODEL_NAMES)?.to_string(),
+            aliases: vec![],
+            model_id: None,
+            inference_profile_arn: None,
+            inference_profile_id: None,
+            pricing: Some(pricing),
+            regions,
+            streaming: match is_text {
+                true => Some(u.arbitrary()?),
+                false => None,
+            },
+            system_disabled: None,
+            deprecated: Some(u.ratio(1, 4)?),
+            status: u.arbitrary()?,
+            deprecation_date: None,
+            replacement_model_key: None,
+            encoder: match is_text {
+                true => u.arbitrary()?,
+                false => None,
+            },
+            context_window,
+            max_output_tokens,
+            prod_price_ids: match is_text {
+                true => Some(ProdPriceIds::arbitrary(u)?),
+                false => None,
+            },
+            moderation_threshold: u.arbitrary()?,
+            markup: u.arbitrary()?,
+            rate_limits: u.arbitrary()?,
+            fine_tuning: match is_text {
+                true => u.arbitrary()?,
+                false => None,
+            },
+            per_request: optional_price(u)?,
+            tool_pricing,
+            model_type,
+            extra: Default::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ModelType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // Mostly text, as in real pricing files.
+        Ok(match u.int_in_range(0..=7u8)? {
+            0 => ModelType::Image,
+            1 => ModelType::Audio,
+            2 => ModelType::Video,
+            3 => ModelType::Realtime,
+            _ => ModelType::Text,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ModelStatus {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(u.choose(&[
+            ModelStatus::Preview,
+            ModelStatus::Ga,
+            ModelStatus::Legacy,
+            ModelStatus::Retired,
+        ])?
+        .clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for ModelFeature {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(u.choose(&[
+            ModelFeature::Vision,
+            ModelFeature::FunctionCalling,
+            ModelFeature::JsonMode,
+            ModelFeature::StructuredOutputs,
+            ModelFeature::Streaming,
+        ])?
+        .clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Encoder {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(u.choose(&[
+            Encoder::O200kBase,
+            Encoder::Cl100kBase,
+            Encoder::P50kBase,
+            Encoder::P50kEdit,
+            Encoder::R50kBase,
+        ])?
+        .clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for TextPricing {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut pricing = TextPricing::from_per1_m(price(u)?, price(u)?);
+        // Discounted rates stay at or below the standard ones.
+        let discounted = |u: &mut Unstructured<'a>, base: f64| -> Result<_> {
+            Ok(match u.arbitrary()? {
+                true => {
+                    let per1_m = (base * fraction(u)? * 1_000_000.0).round() / 1_000_000.0;
+                    (Some(per1_m / 1_000.0), Some(per1_m))
+                }
+                false => (None, None),
+            })
+        };
+        (pricing.cached_input_per1_k, pricing.cached_input_per1_m) =
+            discounted(u, pricing.input_per1_m)?;
+        (pricing.batch_input_per1_k, pricing.batch_input_per1_m) =
+            discounted(u, pricing.input_per1_m)?;
+        (pricing.batch_output_per1_k, pricing.batch_output_per1_m) =
+            discounted(u, pricing.output_per1_m)?;
+        (
+            pricing.reasoning_output_per1_k,
+            pricing.reasoning_output_per1_m,
+        ) = optional_pair(u)?;
+        (pricing.cache_write_per1_k, pricing.cache_write_per1_m) = optional_pair(u)?;
+        (pricing.image_input_per1_k, pricing.image_input_per1_m) = optional_pair(u)?;
+        if pricing.cache_write_per1_m.is_some() && u.arbitrary()? {
+            pricing
+                .cache_write_per1_m_by_ttl
+                .insert("1h".to_string(), price(u)?);
+        }
+
+        let mut up_to_tokens = 0;
+        for _ in 0..u.int_in_range(0..=2)? {
+            up_to_tokens += u.int_in_range(1..=1_000_000u64)?;
+            let mut tier = PriceTier::arbitrary(u)?;
+            tier.up_to_tokens = Some(up_to_tokens);
+            pricing.tiers.push(tier);
+        }
+        Ok(pricing)
+    }
+}
+
+impl<'a> Arbitrary<'a> for PriceTier {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(PriceTier {
+            up_to_tokens: None,
+            input_per1_m: price(u)?,
+            output_per1_m: price(u)?,
+            extra: Default::default(),
+        })
+    }
+}
+
+/// One to three distinct image sizes, each at one or two qualities.
+fn arbitrary_image_sizes(u: &mut Unstructured<'_>) -> Result<Vec<ImagePricing>> {
+    let mut sizes = Vec::new();
+    for size in ["1024x1024", "1792x1024", "1024x1792"] {
+        if !sizes.is_empty() && u.arbitrary()? {
+            continue;
+        }
+        let mut image = ImagePricing::arbitrary(u)?;
+        image.size = size.to_string();
+        if u.arbitrary()? {
+            image.quality = Some("standard".to_string());
+            let mut hd = image.clone();
+            hd.quality = Some("hd".to_string());
+            hd.cost_per_image = price(u)?;
+            sizes.push(hd);
+        }
+        sizes.push(image);
+    }
+    Ok(sizes)
+}
+
+impl<'a> Arbitrary<'a> for ImagePricing {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let size = ImageSize::arbitrary(u)?;
+        Ok(ImagePricing {
+            cost_per_image: price(u)?,
+            description: format!("{} image", size),
+            size: size.to_string(),
+            quality: None,
+            extra: Default::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ImageSize {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ImageSize::new(
+            u.int_in_range(1..=4_096)?,
+            u.int_in_range(1..=4_096)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for AudioPricing {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // At least one of the two prices.
+        let (transcription, tts) = match u.int_in_range(0..=2u8)? {
+            0 => (true, false),
+            1 => (false, true),
+            _ => (true, true),
+        };
+        Ok(AudioPricing {
+            transcription_per_minute: if transcription { Some(price(u)?) } else { None },
+            tts_per1_k_characters: if tts { Some(price(u)?) } else { None },
+            extra: Default::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for VideoPricing {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut cost_per_second = BTreeMap::new();
+        cost_per_second.insert("720p".to_string(), price(u)?);
+        if u.arbitrary()? {
+            cost_per_second.insert("1080p".to_string(), price(u)?);
+        }
+        Ok(VideoPricing {
+            cost_per_second,
+            extra: Default::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RealtimePricing {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(RealtimePricing {
+            audio_input_per_minute: price(u)?,
+            audio_output_per_minute: price(u)?,
+            text: TextPricing::from_per1_m(price(u)?, price(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for FineTunePricing {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(FineTunePricing {
+            training_per1_m: price(u)?,
+            hosted_input_per1_m: price(u)?,
+            hosted_output_per1_m: price(u)?,
+            storage_per_hour: optional_price(u)?,
+            extra: Default::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ProdPriceIds {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ProdPriceIds {
+            cached_input: match u.arbitrary()? {
+                true => Some(price_id(u)?),
+                false => None,
+            },
+            input: Some(price_id(u)?),
+            output: Some(price_id(u)?),
+        })
+    }
+}
+
+// ------------------
+// Arbitrary usage
+// ------------------
+
+impl<'a> Arbitrary<'a> for TokenUsage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut tokens = || u.int_in_range(0..=MAX_TOKENS);
+        let mut usage = TokenUsage {
+            input_tokens: tokens()?,
+            output_tokens: tokens()?,
+            cached_input_tokens: tokens()?,
+            reasoning_tokens: tokens()?,
+            cache_write_tokens: tokens()?,
+            ..TokenUsage::default()
+        };
+        usage.cache_write_ttl = match u.int_in_range(0..=2u8)? {
+            0 => None,
+            1 => Some("5m".to_string()),
+            _ => Some("1h".to_string()),
+        };
+        usage.mode = u.arbitrary()?;
+        usage.requests = u.int_in_range(0..=MAX_CALLS)?;
+        for tool in TOOL_NAMES {
+            if u.arbitrary()? {
+                usage
+                    .tool_calls
+                    .insert(tool.to_string(), u.int_in_range(0..=MAX_CALLS)?);
+            }
+        }
+        Ok(usage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random bytes, so failures reproduce.
+    fn bytes(seed: u64, len: usize) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_arbitrary_pricing_is_valid() {
+        for seed in 0..200 {
+            let data = bytes(seed, 4_096);
+            let pricing = AiPricingJson::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            let report = pricing.validate();
+            assert!(report.is_ok(), "seed {}:\n{}", seed, report);
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_pricing_round_trips() {
+        for seed in 0..50 {
+            let data = bytes(seed, 4_096);
+            let pricing = AiPricingJson::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            let json = serde_json::to_value(&pricing).unwrap();
+            let round_trip: AiPricingJson = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(
+                serde_json::to_value(&round_trip).unwrap(),
+                json,
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_usage_costs_are_finite() {
+        for seed in 0..200 {
+            let data = bytes(seed, 4_096);
+            let mut u = Unstructured::new(&data);
+            let pricing = AiPricingJson::arbitrary(&mut u).unwrap();
+            let usage = TokenUsage::arbitrary(&mut u).unwrap();
+
+            let text = TextPricing::arbitrary(&mut u).unwrap();
+            let cost = text.cost_for_usage(&usage);
+            assert!(cost.total.is_finite() && cost.total >= 0.0, "seed {}", seed);
+            for (_, model) in pricing.all_models() {
+                if let Ok(cost) = model.cost_for_usage(&usage) {
+                    assert!(
+                        cost.total.is_finite() && cost.total >= 0.0,
+                        "seed {}: {}",
+                        seed,
+                        model.key
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_input_still_produces_data() {
+        let pricing = AiPricingJson::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(pricing.validate().is_ok());
+        assert!(!pricing.providers.is_empty());
+    }
+}
@@ -0,0 +1,372 @@
+use crate::{CostBreakdown, ImagePricing, Modality, Model, Pricing, PricingError, Provider};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+// ------------------
+// Image sizes
+// ------------------
+
+/// Image dimensions in pixels, written `{width}x{height}` as in pricing sizes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+#[serde(try_from = "String", into = "String")]
+pub struct ImageSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageSize {
+    pub fn new(width: u32, height: u32) -> Self {
+        ImageSize { width, height }
+    }
+
+    pub fn pixels(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    pub fn is_landscape(&self) -> bool {
+        self.width > self.height
+    }
+
+    pub fn is_portrait(&self) -> bool {
+        self.width < self.height
+    }
+
+    /// Whether an image of this size fits inside `other` without scaling.
+    pub fn fits_within(&self, other: &ImageSize) -> bool {
+        self.width <= other.width && self.height <= other.height
+    }
+
+    /// The size in `sizes` nearest to this one: the smallest that it fits
+    /// within, or the largest if it fits within none of them.
+    pub fn nearest<'a>(
+        &self,
+        sizes: impl IntoIterator<Item = &'a ImageSize>,
+    ) -> Option<&'a ImageSize> {
+        let sizes: Vec<&ImageSize> = sizes.into_iter().collect();
+        sizes
+            .iter()
+            .filter(|size| self.fits_within(size))
+            .min_by_key(|size| size.pixels())
+            .or_else(|| sizes.iter().max_by_key(|size| size.pixels()))
+            .copied()
+    }
+}
+
+impl FromStr for ImageSize {
+    type Err = ParseImageSizeError;
+
+    fn from_str(size: &str) -> Result<Self, Self::Err> {
+        let error = || ParseImageSizeError(size.to_string());
+        let (width, height) = size.trim().split_once(['x', 'X']).ok_or_else(error)?;
+        Ok(ImageSize {
+            width: width.trim().parse().map_err(|_| error())?,
+            height: height.trim().parse().map_err(|_| error())?,
+        })
+    }
+}
+
+impl TryFrom<String> for ImageSize {
+    type Error = ParseImageSizeError;
+
+    fn try_from(size: String) -> Result<Self, Self::Error> {
+        size.parse()
+    }
+}
+
+impl From<ImageSize> for String {
+    fn from(size: ImageSize) -> Self {
+        size.to_string()
+    }
+}
+
+impl fmt::Display for ImageSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// Returned when parsing a size that isn't `{width}x{height}`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid image size {0:?} (expected e.g. 1024x1024)")]
+pub struct ParseImageSizeError(pub String);
+
+/// Whether two pricing sizes name the same dimensions, e.g. `1024x1024` and
+/// `1024X1024`.
+fn same_size(a: &str, b: &str) -> bool {
+    a == b
+        || matches!(
+            (a.parse::<ImageSize>(), b.parse::<ImageSize>()),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+/// One size an image model can generate, and what it costs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageSizeOption<'a> {
+    pub size: &'a str,
+    pub quality: Option<&'a str>,
+    pub description: &'a str,
+    pub cost_per_image: f64,
+}
+
+impl ImageSizeOption<'_> {
+    /// The size's dimensions; `None` if it isn't `{width}x{height}`.
+    pub fn dimensions(&self) -> Option<ImageSize> {
+        self.size.parse().ok()
+    }
+}
+
+impl ImagePricing {
+    /// The size, and the quality if there is one, e.g. `1024x1024/hd`.
+    pub(crate) fn label(&self) -> String {
+        match &self.quality {
+            Some(quality) => format!("{}/{}", self.size, quality),
+            None => self.size.clone(),
+        }
+    }
+}
+
+impl Pricing {
+    /// The sizes offered by image pricing; empty for other pricing.
+    pub fn available_sizes(&self) -> Vec<ImageSizeOption<'_>> {
+        match self {
+            Pricing::ImagePricingVec(sizes) => sizes
+                .iter()
+                .map(|p| ImageSizeOption {
+                    size: &p.size,
+                    quality: p.quality.as_deref(),
+                    description: &p.description,
+                    cost_per_image: p.cost_per_image,
+                })
+                .collect(),
+            Pricing::TextPricing(_)
+            | Pricing::AudioPricing(_)
+            | Pricing::VideoPricing(_)
+            | Pricing::RealtimePricing(_) => Vec::new(),
+        }
+    }
+
+    /// The offered size nearest to `wanted`, as for [`ImageSize::nearest`].
+    /// Sizes that aren't `{width}x{height}` are never chosen.
+    pub fn nearest_size(&self, wanted: ImageSize) -> Option<ImageSizeOption<'_>> {
+        let options = self.available_sizes();
+        let sizes: Vec<ImageSize> = options.iter().filter_map(|o| o.dimensions()).collect();
+        let nearest = *wanted.nearest(&sizes)?;
+        options
+            .into_iter()
+            .find(|option| option.dimensions() == Some(nearest))
+    }
+
+    /// Provider cost of generating `n` images of `size` at `quality`. Sizes
+    /// match by dimensions, so `1024X1024` finds `1024x1024`.
+    ///
+    /// Prices without a quality apply to every quality. Without a `quality`,
+    /// the size's unqualified price is used, or else the first one listed.
+    ///
+    /// With the `decimal` feature this is computed in decimal arithmetic, as
+    /// [`image_cost_decimal`](Self::image_cost_decimal).
+    pub fn image_cost(
+        &self,
+        size: &str,
+        quality: Option<&str>,
+        n: u32,
+    ) -> Result<f64, PricingError> {
+        #[cfg(feature = "decimal")]
+        {
+            self.image_cost_decimal(size, quality, n)
+                .map(crate::decimal::to_f64)
+        }
+        #[cfg(not(feature = "decimal"))]
+        {
+            Ok(self.image_price(size, quality)?.cost_per_image * f64::from(n))
+        }
+    }
+
+    /// The price that applies to one image of `size` at `quality`, as for
+    /// [`image_cost`](Self::image_cost).
+    pub(crate) fn image_price(
+        &self,
+        size: &str,
+        quality: Option<&str>,
+    ) -> Result<&ImagePricing, PricingError> {
+        let Pricing::ImagePricingVec(sizes) = self else {
+            return Err(PricingError::Validation(
+                "only image pricing has image sizes".to_string(),
+            ));
+        };
+
+        let at_size: Vec<&ImagePricing> =
+            sizes.iter().filter(|p| same_size(&p.size, size)).collect();
+        if at_size.is_empty() {
+            return Err(PricingError::UnknownImageSize {
+                size: size.to_string(),
+                available: sizes.iter().map(|p| p.size.clone()).collect(),
+            });
+        }
+        let unqualified = at_size.iter().find(|p| p.quality.is_none());
+        let price = match quality {
+            Some(quality) => at_size
+                .iter()
+                .find(|p| p.quality.as_deref() == Some(quality))
+                .or(unqualified),
+            None => unqualified.or(at_size.first()),
+        };
+
+        price
+            .copied()
+            .ok_or_else(|| PricingError::UnknownImageQuality {
+                size: size.to_string(),
+                quality: quality.unwrap_or_default().to_string(),
+                available: at_size.iter().filter_map(|p| p.quality.clone()).collect(),
+            })
+    }
+}
+
+impl Model {
+    /// The sizes this model can generate; empty for non-image models.
+    pub fn available_sizes(&self) -> Vec<ImageSizeOption<'_>> {
+        self.pricing
+            .as_ref()
+            .map(Pricing::available_sizes)
+            .unwrap_or_default()
+    }
+
+    /// The size this model offers nearest to `wanted`; `None` for non-image
+    /// models.
+    pub fn nearest_image_size(&self, wanted: ImageSize) -> Option<ImageSizeOption<'_>> {
+        self.pricing.as_ref()?.nearest_size(wanted)
+    }
+
+    /// Provider cost of generating `n` images of `size` at `quality` with
+    /// this model.
+    pub fn image_cost(
+        &self,
+        size: &str,
+        quality: Option<&str>,
+        n: u32,
+    ) -> Result<f64, PricingError> {
+        self.pricing
+            .as_ref()
+            .ok_or_else(|| PricingError::Validation(format!("model {} has no pricing", self.key)))?
+            .image_cost(size, quality, n)
+    }
+}
+
+impl Provider {
+    /// Provider cost and marked-up customer price of generating `n` images of
+    /// `size` at `quality` with one of this provider's models.
+    #[deprecated(note = "ignores promotions; use `AiPricingJson::image_cost`")]
+    pub fn image_cost(
+        &self,
+        model_key: &str,
+        size: &str,
+        quality: Option<&str>,
+        n: u32,
+    ) -> Result<CostBreakdown, PricingError> {
+        let model = self.find_model(model_key).ok_or_else(|| {
+            PricingError::Validation(format!("provider {} has no model {}", self.key, model_key))
+        })?;
+
+        let cost = model.image_cost(size, quality, n)?;
+        Ok(self.apply_model_markup(model, cost, Modality::Image))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_image_cost() {
+        let pricing = pricing();
+        let dalle = pricing.find_model("openai", "dall-e-3").unwrap();
+
+        let sizes: Vec<&str> = dalle.available_sizes().iter().map(|s| s.size).collect();
+        assert_eq!(sizes, vec!["1024x1024", "1792x1024"]);
+
+        assert_eq!(dalle.image_cost("1792x1024", None, 2).unwrap(), 0.16);
+        assert_eq!(dalle.image_cost("1792x1024", Some("hd"), 1).unwrap(), 0.08);
+
+        let price = pricing
+            .image_cost("openai", "dall-e-3", "1024x1024", None, 1)
+            .unwrap();
+        assert_eq!(price.provider_cost, 0.04);
+        assert!((price.customer_price - 0.06).abs() < 1e-12);
+
+        assert!(matches!(
+            dalle.image_cost("512x512", None, 1),
+            Err(PricingError::UnknownImageSize { .. })
+        ));
+
+        let gpt = pricing.find_model("openai", "gpt-4o").unwrap();
+        assert!(gpt.available_sizes().is_empty());
+        assert!(gpt.image_cost("1024x1024", None, 1).is_err());
+    }
+
+    #[test]
+    fn test_image_size() {
+        let size: ImageSize = " 1792X1024 ".parse().unwrap();
+        assert_eq!(size, ImageSize::new(1792, 1024));
+        assert_eq!(size.to_string(), "1792x1024");
+        assert!(size.is_landscape());
+        assert!(ImageSize::new(1024, 1024).fits_within(&size));
+        assert_eq!(
+            "1024".parse::<ImageSize>(),
+            Err(ParseImageSizeError("1024".to_string()))
+        );
+        assert_eq!(serde_json::to_string(&size).unwrap(), r#""1792x1024""#);
+    }
+
+    #[test]
+    fn test_nearest_image_size() {
+        let pricing = pricing();
+        let dalle = pricing.find_model("openai", "dall-e-3").unwrap();
+
+        let nearest = |width, height| {
+            dalle
+                .nearest_image_size(ImageSize::new(width, height))
+                .map(|option| option.size)
+        };
+        assert_eq!(nearest(800, 600), Some("1024x1024"));
+        assert_eq!(nearest(1600, 900), Some("1792x1024"));
+        assert_eq!(nearest(4096, 4096), Some("1792x1024"));
+        assert_eq!(dalle.image_cost("1792X1024", None, 1).unwrap(), 0.08);
+
+        let gpt = pricing.find_model("openai", "gpt-4o").unwrap();
+        assert_eq!(gpt.nearest_image_size(ImageSize::new(512, 512)), None);
+    }
+
+    #[test]
+    fn test_image_cost_by_quality() {
+        let mut pricing = pricing();
+        let dalle = &mut pricing.providers[0].models[1];
+        let Some(Pricing::ImagePricingVec(sizes)) = &mut dalle.pricing else {
+            panic!("dall-e-3 should have image pricing");
+        };
+        sizes.retain(|p| p.size == "1024x1024");
+        sizes[0].quality = Some("standard".to_string());
+        let mut hd = sizes[0].clone();
+        hd.quality = Some("hd".to_string());
+        hd.cost_per_image = 0.08;
+        sizes.push(hd);
+
+        assert_eq!(dalle.image_cost("1024x1024", Some("hd"), 1).unwrap(), 0.08);
+        assert_eq!(dalle.image_cost("1024x1024", None, 1).unwrap(), 0.04);
+        match dalle.image_cost("1024x1024", Some("ultra"), 1) {
+            Err(PricingError::UnknownImageQuality { available, .. }) => {
+                assert_eq!(available, ["standard", "hd"])
+            }
+            other => panic!("expected an unknown quality, got {:?}", other),
+        }
+    }
+}
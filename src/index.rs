@@ -0,0 +1,168 @@
+use crate::{AiPricingJson, Model, Provider};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// ------------------
+// Index
+// ------------------
+
+/// Position of a model within the indexed pricing data.
+#[derive(Debug, Clone, Copy)]
+struct ModelPos {
+    provider: usize,
+    model: usize,
+}
+
+/// Constant-time lookups over a pricing snapshot.
+///
+/// Built once from an [`AiPricingJson`] for services that resolve pricing on
+/// every request. Model lookups accept aliases as well as keys, with keys taking
+/// precedence. Where several models share a key, alias, or `model_id`, the first
+/// in file order wins, matching [`AiPricingJson::find_model_anywhere`].
+#[derive(Debug, Clone)]
+pub struct AiPricingIndex {
+    pricing: Arc<AiPricingJson>,
+    providers: HashMap<String, usize>,
+    models: HashMap<(String, String), ModelPos>,
+    models_by_key: HashMap<String, ModelPos>,
+    models_by_id: HashMap<String, ModelPos>,
+}
+
+impl AiPricingIndex {
+    pub fn new(pricing: Arc<AiPricingJson>) -> Self {
+        let mut providers = HashMap::new();
+        let mut models = HashMap::new();
+        let mut models_by_key = HashMap::new();
+        let mut models_by_id = HashMap::new();
+
+        for (p, provider) in pricing.providers.iter().enumerate() {
+            providers.entry(provider.key.clone()).or_insert(p);
+
+            for (m, model) in provider.models.iter().enumerate() {
+                let pos = ModelPos {
+                    provider: p,
+                    model: m,
+                };
+                models
+                    .entry((provider.key.clone(), model.key.clone()))
+                    .or_insert(pos);
+                models_by_key.entry(model.key.clone()).or_insert(pos);
+                if let Some(model_id) = &model.model_id {
+                    models_by_id.entry(model_id.clone()).or_insert(pos);
+                }
+            }
+        }
+
+        // Aliases go in after every key so that a key always wins.
+        for (p, provider) in pricing.providers.iter().enumerate() {
+            for (m, model) in provider.models.iter().enumerate() {
+                let pos = ModelPos {
+                    provider: p,
+                    model: m,
+                };
+                for alias in &model.aliases {
+                    models
+                        .entry((provider.key.clone(), alias.clone()))
+                        .or_insert(pos);
+                    models_by_key.entry(alias.clone()).or_insert(pos);
+                }
+            }
+        }
+
+        AiPricingIndex {
+            pricing,
+            providers,
+            models,
+            models_by_key,
+            models_by_id,
+        }
+    }
+
+    /// The indexed pricing snapshot.
+    pub fn pricing(&self) -> &Arc<AiPricingJson> {
+        &self.pricing
+    }
+
+    pub fn provider(&self, provider_key: &str) -> Option<&Provider> {
+        self.providers
+            .get(provider_key)
+            .map(|&p| &self.pricing.providers[p])
+    }
+
+    pub fn model(&self, provider_key: &str, model_key: &str) -> Option<&Model> {
+        self.models
+            .get(&(provider_key.to_string(), model_key.to_string()))
+            .map(|&pos| self.resolve(pos).1)
+    }
+
+    pub fn model_by_key(&self, model_key: &str) -> Option<(&Provider, &Model)> {
+        self.models_by_key
+            .get(model_key)
+            .map(|&pos| self.resolve(pos))
+    }
+
+    /// Look up a model by its provider-specific `model_id`.
+    pub fn model_by_id(&self, model_id: &str) -> Option<(&Provider, &Model)> {
+        self.models_by_id
+            .get(model_id)
+            .map(|&pos| self.resolve(pos))
+    }
+
+    fn resolve(&self, pos: ModelPos) -> (&Provider, &Model) {
+        let provider = &self.pricing.providers[pos.provider];
+        (provider, &provider.models[pos.model])
+    }
+}
+
+impl From<AiPricingJson> for AiPricingIndex {
+    fn from(pricing: AiPricingJson) -> Self {
+        AiPricingIndex::new(Arc::new(pricing))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::ModelType;
+
+    #[test]
+    fn test_index_lookups() {
+        let index = AiPricingIndex::from(pricing());
+
+        assert_eq!(index.provider("bedrock").unwrap().label, "Bedrock");
+        assert!(index.provider("azure").is_none());
+
+        assert_eq!(
+            index.model("openai", "dall-e-3").unwrap().model_type,
+            ModelType::Image
+        );
+        assert!(index.model("bedrock", "gpt-4o").is_none());
+
+        let (provider, _) = index.model_by_key("gpt-4o").unwrap();
+        assert_eq!(provider.key, "openai");
+
+        let (_, model) = index
+            .model_by_id("anthropic.claude-3-sonnet-20240229-v1:0")
+            .unwrap();
+        assert_eq!(model.key, "claude-3-sonnet");
+    }
+
+    #[test]
+    fn test_index_aliases() {
+        let mut pricing = pricing();
+        pricing.providers[0].models[0].aliases = vec!["gpt-4o-2024-08-06".to_string()];
+        pricing.providers[1].models[0].aliases = vec!["gpt-4o".to_string()];
+        let index = AiPricingIndex::from(pricing);
+
+        let model = index.model("openai", "gpt-4o-2024-08-06").unwrap();
+        assert_eq!(model.key, "gpt-4o");
+        assert_eq!(
+            index.model("bedrock", "gpt-4o").unwrap().key,
+            "claude-3-5-sonnet"
+        );
+
+        let (provider, _) = index.model_by_key("gpt-4o").unwrap();
+        assert_eq!(provider.key, "openai");
+    }
+}
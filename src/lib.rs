@@ -1,24 +1,216 @@
-use once_cell::sync::OnceCell;
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use std::error::Error as StdError;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+use dashmap::DashMap;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+use once_cell::sync::Lazy;
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+use std::sync::Arc;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+use std::time::Duration;
+
+mod audio;
+mod auth;
+mod bedrock;
+mod billing;
+#[cfg(feature = "blocking")]
+mod blocking;
+mod builder;
+#[cfg(feature = "bundled")]
+mod bundled;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+mod cache;
+mod canonical;
+mod changelog;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+mod client;
+mod cost;
+mod csv;
+mod currency;
+#[cfg(feature = "chrono")]
+mod dates;
+#[cfg(feature = "decimal")]
+mod decimal;
+mod deployments;
+mod diff;
+mod drift;
+mod env;
+mod error;
+pub mod estimate;
+mod finetune;
+#[cfg(feature = "fixtures")]
+mod fixtures;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+mod image;
+mod index;
+mod lifecycle;
+mod limits;
+mod lookup;
+mod markdown;
+mod merge;
+mod moderation;
+mod number;
+#[cfg(feature = "utoipa")]
+mod openapi;
+#[cfg(all(
+    any(feature = "fetch", feature = "blocking"),
+    not(target_arch = "wasm32")
+))]
+mod overrides;
+mod parse;
+mod patch;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+mod persist;
+mod plans;
+mod promotions;
+mod realtime;
+mod regions;
+mod resolved;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+mod retry;
+mod rounding;
+mod schema;
+mod select;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+mod source;
+mod strict;
+pub mod stripe;
+mod summary;
+mod table;
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+mod telemetry;
+#[cfg(test)]
+mod test_fixtures;
+#[cfg(all(
+    any(test, feature = "testing"),
+    feature = "fetch",
+    not(target_arch = "wasm32")
+))]
+pub mod testing;
+#[cfg(feature = "tokenize")]
+mod tokenize;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "ts-rs")]
+mod typescript;
+mod validate;
+mod video;
+mod vision;
+#[cfg(all(feature = "fetch", target_arch = "wasm32"))]
+mod wasm;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+pub use bedrock::{inference_geography, InferenceProfileArn, ParseArnError};
+#[cfg(feature = "blocking")]
+pub use blocking::get_ai_pricing_blocking;
+pub use builder::{AiPricingJsonBuilder, ModelBuilder, ProviderBuilder};
+#[cfg(feature = "bundled")]
+pub use bundled::BUNDLED_PRICING_JSON;
+pub use changelog::{Changelog, ChangelogEntry, ChangelogItem};
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+pub use client::{FetchErrorPolicy, PricingClient, PricingClientBuilder};
+#[allow(deprecated)]
+pub use cost::MarkedUpPrice;
+pub use cost::{effective_markup, Cost, CostBreakdown, CostMode, Modality, TokenUsage};
+pub use currency::{CurrencyConverter, FixedRates, DEFAULT_CURRENCY};
+#[cfg(feature = "chrono")]
+pub use dates::parse_model_date;
+#[cfg(feature = "decimal")]
+pub use decimal::{to_decimal, DecimalCost};
+pub use diff::{ModelRef, PriceChange, PriceIdChange, PricingDiff, SettingChange, StatusChange};
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+pub use drift::check_env_drift;
+pub use drift::{check_drift, DriftReport};
+pub use env::{Env, ParseEnvError, DEFAULT_BASE_URL};
+pub use error::PricingError;
+#[cfg(feature = "fixtures")]
+pub use fixtures::SAMPLE_PRICING_JSON;
+pub use image::{ImageSize, ImageSizeOption, ParseImageSizeError};
+pub use index::AiPricingIndex;
+pub use merge::merge_patch;
+pub use moderation::{
+    evaluate_moderation, ModerationDecision, ModerationOverride, ModerationScores,
+    ModerationThresholdBuilder, DEFAULT_GENERAL_THRESHOLD,
+};
+#[cfg(all(
+    any(feature = "fetch", feature = "blocking"),
+    not(target_arch = "wasm32")
+))]
+pub use overrides::{ENV_VAR, TTL_SECS_VAR, URL_VAR};
+pub use realtime::{RealtimeCost, RealtimeSession};
+pub use resolved::{AudioModel, ImageModel, RealtimeModel, ResolvedModel, TextModel, VideoModel};
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+pub use retry::RetryPolicy;
+pub use rounding::RoundingPolicy;
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+pub use schema::SCHEMA_VERSION;
+pub use select::{ModelQuery, ModelRequirements, RankedModel, DEFAULT_INPUT_OUTPUT_RATIO};
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+pub use source::{FileSource, HttpSource, PricingSource, StaticSource};
+pub use table::{PriceColumn, PriceRow, PriceTable};
+#[cfg(feature = "ts-rs")]
+pub use typescript::export_typescript;
+pub use validate::{Severity, ValidationIssue, ValidationReport, MARKUP_WARNING_PERCENTAGE};
+pub use vision::{vision_tokens, ImageDetail};
+#[cfg(all(feature = "fetch", target_arch = "wasm32"))]
+pub use wasm::get_ai_pricing;
 
 // ------------------
 // Top-level JSON
 // ------------------
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AiPricingJson {
+    /// Format version of the file. Files without one are version 1; see
+    /// [`AiPricingJson::from_versioned_value`].
+    #[serde(default = "schema::default_schema_version")]
+    pub schema_version: u32,
+
     pub metered_price_id: String,
     pub providers: Vec<Provider>,
+
+    /// ISO 4217 code the prices are in. Absent means [`DEFAULT_CURRENCY`].
+    #[serde(default)]
+    pub currency: Option<String>,
+
+    /// Markup adjustments by customer plan. See [`AiPricingJson::cost_for_plan`].
+    #[serde(default, skip_serializing_if = "MarkupOverlay::is_empty")]
+    pub markup_overlay: MarkupOverlay,
+
+    /// Discounts on customer prices, applied by the cost APIs while active.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub promotions: Vec<Promotion>,
+
+    /// How the cost APIs round customer charges. Absent means
+    /// [`RoundingPolicy::Exact`].
+    #[serde(default, skip_serializing_if = "RoundingPolicy::is_exact")]
+    pub rounding: RoundingPolicy,
+
+    /// Fields this crate doesn't know about yet, kept so they survive a
+    /// round-trip.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 // ------------------
 // Provider
 // ------------------
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Provider {
     pub description: String,
@@ -29,71 +221,422 @@ pub struct Provider {
     pub moderation_threshold: ModerationThreshold,
     pub provider_host: String,
     pub website: String,
+    /// Default throttling limits for the provider's models. See
+    /// [`Provider::rate_limits_for`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<RateLimits>,
+    /// How clients authenticate with the provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<ProviderAuth>,
+    /// Named deployments of the provider's models, as on Azure OpenAI. See
+    /// [`Provider::resolve_deployment`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deployments: Vec<Deployment>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A named deployment of one of a provider's models, e.g. an Azure OpenAI
+/// deployment that usage events report by name rather than model.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Deployment {
+    pub name: String,
+    /// Region the deployment runs in, used to pick regional prices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Key or alias of the deployed model.
+    pub model_key: String,
+}
+
+// ------------------
+// Provider auth
+// ------------------
+
+/// How to authenticate with a provider. This holds only references to
+/// credentials, such as environment variable names, never the secrets.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderAuth {
+    pub scheme: AuthScheme,
+    /// Environment variables that may hold the credential, in order of
+    /// preference, e.g. `OPENAI_API_KEY`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_vars: Vec<String>,
+    /// Header the credential goes in, for [`AuthScheme::Header`], e.g.
+    /// `x-api-key`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    /// Headers every request must carry, e.g. `anthropic-version`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub required_headers: BTreeMap<String, String>,
+}
+
+/// How a provider expects credentials, from the JSON `scheme` field. Schemes
+/// this crate doesn't know about yet are preserved as `Other`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum AuthScheme {
+    /// `Authorization: Bearer <key>`.
+    Bearer,
+    /// The key as-is in the [`ProviderAuth::header`] header.
+    Header,
+    /// AWS Signature Version 4, from the standard AWS credential chain.
+    AwsSigV4,
+    Other(String),
+}
+
+impl AuthScheme {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuthScheme::Bearer => "bearer",
+            AuthScheme::Header => "header",
+            AuthScheme::AwsSigV4 => "awsSigV4",
+            AuthScheme::Other(other) => other,
+        }
+    }
+}
+
+impl From<String> for AuthScheme {
+    fn from(scheme: String) -> Self {
+        match scheme.as_str() {
+            "bearer" => AuthScheme::Bearer,
+            "header" => AuthScheme::Header,
+            "awsSigV4" => AuthScheme::AwsSigV4,
+            _ => AuthScheme::Other(scheme),
+        }
+    }
+}
+
+impl From<AuthScheme> for String {
+    fn from(scheme: AuthScheme) -> Self {
+        match scheme {
+            AuthScheme::Other(other) => other,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AuthScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 // ------------------
 // Markup
 // ------------------
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Markup {
+    #[serde(deserialize_with = "number::f64")]
     pub image_percentage: f64,
+    #[serde(deserialize_with = "number::f64")]
     pub text_percentage: f64,
 }
 
+/// A model's markup, layered over its provider's [`Markup`]. Percentages left
+/// out keep the provider's. See [`effective_markup`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MarkupOverride {
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub image_percentage: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub text_percentage: Option<f64>,
+}
+
+// ------------------
+// Plan markup
+// ------------------
+
+/// Markup adjustments by customer plan ID, e.g. `free`, `pro`, `enterprise`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(as = "BTreeMap<String, PlanMarkup>"))]
+#[serde(transparent)]
+pub struct MarkupOverlay {
+    pub plans: BTreeMap<String, PlanMarkup>,
+}
+
+/// How one plan's markup differs from the base markup, in percentage points.
+/// Negative adjustments are discounts.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PlanMarkup {
+    #[serde(default, deserialize_with = "number::f64")]
+    pub image_adjustment: f64,
+    #[serde(default, deserialize_with = "number::f64")]
+    pub text_adjustment: f64,
+}
+
+// ------------------
+// Promotions
+// ------------------
+
+/// A discount on customer prices, for a limited time or set of models.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Promotion {
+    pub id: String,
+    pub discount: Discount,
+    /// When the promotion starts, in the same format as a model's `added`.
+    /// Absent means it's already running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starts: Option<String>,
+    /// When the promotion stops, exclusive, so one ending `2025-01-01` runs
+    /// through December 31. Absent means it doesn't end.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ends: Option<String>,
+    /// Keys or aliases of the models it covers. Empty means every model.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_keys: Vec<String>,
+}
+
+/// How much a [`Promotion`] takes off a customer price.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", tag = "type", content = "value")]
+pub enum Discount {
+    /// A percentage off, e.g. `10.0` for 10%.
+    Percentage(f64),
+    /// A fixed amount off each charge.
+    Fixed(f64),
+}
+
+// ------------------
+// Rate limits
+// ------------------
+
+/// Throttling limits for calls to a provider or model. Limits missing from
+/// the JSON are unknown, not unlimited.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimits {
+    /// Tokens per minute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tpm: Option<u64>,
+    /// Requests per minute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpm: Option<u64>,
+    /// Requests in flight at once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrent: Option<u64>,
+}
+
 // ------------------
 // Moderation Threshold
 // ------------------
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ModerationThreshold {
     pub categories: Categories,
     pub category_score: CategoryScore,
+    #[serde(deserialize_with = "number::f64")]
     pub general: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Which moderation categories block content outright when the moderation
+/// API flags them. Categories missing from the JSON are not blocked.
+///
+/// Categories this crate doesn't know about yet are kept in `extra`, so they
+/// survive a round-trip and still take part in moderation decisions.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Categories {
+    #[serde(default)]
+    pub harassment: bool,
+    #[serde(rename = "harassment/threatening", default)]
+    pub harassment_threatening: bool,
+    #[serde(default)]
     pub hate: bool,
-    #[serde(rename = "hate/threatening")]
+    #[serde(rename = "hate/threatening", default)]
     pub hate_threatening: bool,
-    #[serde(rename = "self-harm")]
+    #[serde(default)]
+    pub illicit: bool,
+    #[serde(rename = "illicit/violent", default)]
+    pub illicit_violent: bool,
+    #[serde(rename = "self-harm", default)]
     pub self_harm: bool,
-    #[serde(rename = "self-harm/instructions")]
+    #[serde(rename = "self-harm/instructions", default)]
     pub self_harm_instructions: bool,
-    #[serde(rename = "self-harm/intent")]
+    #[serde(rename = "self-harm/intent", default)]
     pub self_harm_intent: bool,
-    #[serde(rename = "sexual/minors")]
+    #[serde(default)]
+    pub sexual: bool,
+    #[serde(rename = "sexual/minors", default)]
     pub sexual_minors: bool,
+    #[serde(default)]
+    pub violence: bool,
+    #[serde(rename = "violence/graphic", default)]
+    pub violence_graphic: bool,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Per-category score thresholds at or above which content is blocked.
+/// Categories without a threshold are only subject to the `general` one.
+///
+/// As with [`Categories`], unknown categories are kept in `extra`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct CategoryScore {
-    #[serde(rename = "harassment/threatening")]
-    pub harassment_threatening: f64,
-    pub illicit: f64,
-    #[serde(rename = "illicit/violent")]
-    pub illicit_violent: f64,
-    #[serde(rename = "violence/graphic")]
-    pub violence_graphic: f64,
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub harassment: Option<f64>,
+    #[serde(
+        rename = "harassment/threatening",
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub harassment_threatening: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub hate: Option<f64>,
+    #[serde(
+        rename = "hate/threatening",
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub hate_threatening: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub illicit: Option<f64>,
+    #[serde(
+        rename = "illicit/violent",
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub illicit_violent: Option<f64>,
+    #[serde(
+        rename = "self-harm",
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub self_harm: Option<f64>,
+    #[serde(
+        rename = "self-harm/instructions",
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub self_harm_instructions: Option<f64>,
+    #[serde(
+        rename = "self-harm/intent",
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub self_harm_intent: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sexual: Option<f64>,
+    #[serde(
+        rename = "sexual/minors",
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sexual_minors: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub violence: Option<f64>,
+    #[serde(
+        rename = "violence/graphic",
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub violence_graphic: Option<f64>,
+    #[serde(flatten, deserialize_with = "number::map_f64")]
+    pub extra: BTreeMap<String, f64>,
 }
 
 // ------------------
 // Model (text/image)
 // ------------------
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
     pub added: String,
     pub created: String,
 
     #[serde(default)]
-    pub features: Vec<String>,
+    pub features: Vec<ModelFeature>,
     #[serde(default)]
     pub key: String,
+    /// Other names the model answers to, e.g. dated snapshots or short
+    /// forms. Lookups try keys before aliases.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
 
     #[serde(default)]
     pub model_id: Option<String>,
@@ -105,63 +648,766 @@ pub struct Model {
     // This can be an object (for text models) or an array (for image models).
     #[serde(default)]
     pub pricing: Option<Pricing>,
+    /// Text prices that differ by region, e.g. `us-east-1` on Bedrock, keyed
+    /// by region. See [`Model::pricing_for_region`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub regions: BTreeMap<String, TextPricing>,
 
     #[serde(default)]
     pub streaming: Option<bool>,
     #[serde(default)]
     pub system_disabled: Option<bool>,
 
-    // e.g. "text" or "image"
     #[serde(rename = "type")]
-    pub model_type: String,
+    pub model_type: ModelType,
 
     #[serde(default)]
     pub deprecated: Option<bool>,
+    /// Where the model is in its provider's lifecycle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ModelStatus>,
+    /// When the provider stops (or stopped) serving the model, in the same
+    /// format as `added`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_date: Option<String>,
+    /// Key of the provider's recommended successor to this model. See
+    /// [`Provider::resolve_replacement`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement_model_key: Option<String>,
     #[serde(default)]
-    pub encoder: Option<String>,
+    pub encoder: Option<Encoder>,
+    /// Most tokens, prompt and output together, one request can use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u64>,
+    /// Most output tokens one request can produce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
 
     #[serde(default)]
     pub prod_price_ids: Option<ProdPriceIds>,
+
+    /// Moderation settings layered over the provider's, e.g. stricter limits
+    /// for image generation. See [`Provider::moderation_threshold_for`].
+    #[serde(default)]
+    pub moderation_threshold: Option<ModerationOverride>,
+    /// Markup layered over the provider's, e.g. a wider margin on a flagship
+    /// model. See [`effective_markup`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markup: Option<MarkupOverride>,
+    /// Throttling limits layered over the provider's. See
+    /// [`Provider::rate_limits_for`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<RateLimits>,
+    /// Prices for fine-tuning this model, if the provider offers it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fine_tuning: Option<FineTunePricing>,
+    /// Flat charge per request, on top of token costs.
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub per_request: Option<f64>,
+    /// Charge per call of a provider-run tool, e.g. `web_search`, by tool name.
+    #[serde(
+        default,
+        deserialize_with = "number::map_f64",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub tool_pricing: BTreeMap<String, f64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+// ------------------
+// Model type
+// ------------------
+
+/// The kind of model, from the JSON `type` field. Types this crate doesn't
+/// know about yet are preserved as `Other`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum ModelType {
+    Text,
+    Image,
+    Audio,
+    Video,
+    Realtime,
+    Other(String),
+}
+
+impl ModelType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModelType::Text => "text",
+            ModelType::Image => "image",
+            ModelType::Audio => "audio",
+            ModelType::Video => "video",
+            ModelType::Realtime => "realtime",
+            ModelType::Other(other) => other,
+        }
+    }
+}
+
+impl From<String> for ModelType {
+    fn from(model_type: String) -> Self {
+        match model_type.as_str() {
+            "text" => ModelType::Text,
+            "image" => ModelType::Image,
+            "audio" => ModelType::Audio,
+            "video" => ModelType::Video,
+            "realtime" => ModelType::Realtime,
+            _ => ModelType::Other(model_type),
+        }
+    }
+}
+
+impl From<ModelType> for String {
+    fn from(model_type: ModelType) -> Self {
+        match model_type {
+            ModelType::Other(other) => other,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ModelType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ------------------
+// Model status
+// ------------------
+
+/// A model's lifecycle stage, from the JSON `status` field. Stages this crate
+/// doesn't know about yet are preserved as `Other`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum ModelStatus {
+    Preview,
+    Ga,
+    Legacy,
+    Retired,
+    Other(String),
+}
+
+impl ModelStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModelStatus::Preview => "preview",
+            ModelStatus::Ga => "ga",
+            ModelStatus::Legacy => "legacy",
+            ModelStatus::Retired => "retired",
+            ModelStatus::Other(other) => other,
+        }
+    }
+}
+
+impl From<String> for ModelStatus {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "preview" => ModelStatus::Preview,
+            "ga" => ModelStatus::Ga,
+            "legacy" => ModelStatus::Legacy,
+            "retired" => ModelStatus::Retired,
+            _ => ModelStatus::Other(status),
+        }
+    }
+}
+
+impl From<ModelStatus> for String {
+    fn from(status: ModelStatus) -> Self {
+        match status {
+            ModelStatus::Other(other) => other,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ModelStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ------------------
+// Model features
+// ------------------
+
+/// A capability listed in a model's `features`. Features this crate doesn't
+/// know about yet are preserved as `Other`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum ModelFeature {
+    Vision,
+    FunctionCalling,
+    JsonMode,
+    StructuredOutputs,
+    Streaming,
+    Other(String),
+}
+
+impl ModelFeature {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModelFeature::Vision => "vision",
+            ModelFeature::FunctionCalling => "function_calling",
+            ModelFeature::JsonMode => "json_mode",
+            ModelFeature::StructuredOutputs => "structured_outputs",
+            ModelFeature::Streaming => "streaming",
+            ModelFeature::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for ModelFeature {
+    fn from(feature: &str) -> Self {
+        match feature {
+            "vision" => ModelFeature::Vision,
+            "function_calling" => ModelFeature::FunctionCalling,
+            "json_mode" => ModelFeature::JsonMode,
+            "structured_outputs" => ModelFeature::StructuredOutputs,
+            "streaming" => ModelFeature::Streaming,
+            _ => ModelFeature::Other(feature.to_string()),
+        }
+    }
+}
+
+impl From<String> for ModelFeature {
+    fn from(feature: String) -> Self {
+        match ModelFeature::from(feature.as_str()) {
+            ModelFeature::Other(_) => ModelFeature::Other(feature),
+            known => known,
+        }
+    }
+}
+
+impl From<ModelFeature> for String {
+    fn from(feature: ModelFeature) -> Self {
+        match feature {
+            ModelFeature::Other(other) => other,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ModelFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ------------------
+// Encoder
+// ------------------
+
+/// The tokenizer a text model counts tokens with, from the JSON `encoder`
+/// field. Encoders this crate doesn't know about yet are preserved as
+/// `Other`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum Encoder {
+    O200kBase,
+    Cl100kBase,
+    P50kBase,
+    P50kEdit,
+    R50kBase,
+    Other(String),
+}
+
+impl Encoder {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Encoder::Other(other) => other,
+            known => known.tiktoken_name().unwrap_or_default(),
+        }
+    }
+
+    /// The tiktoken encoding to count this model's tokens with, e.g.
+    /// `o200k_base`. `None` for encoders tiktoken doesn't ship.
+    pub fn tiktoken_name(&self) -> Option<&'static str> {
+        match self {
+            Encoder::O200kBase => Some("o200k_base"),
+            Encoder::Cl100kBase => Some("cl100k_base"),
+            Encoder::P50kBase => Some("p50k_base"),
+            Encoder::P50kEdit => Some("p50k_edit"),
+            Encoder::R50kBase => Some("r50k_base"),
+            Encoder::Other(_) => None,
+        }
+    }
+}
+
+impl From<String> for Encoder {
+    fn from(encoder: String) -> Self {
+        match encoder.as_str() {
+            "o200k_base" => Encoder::O200kBase,
+            "cl100k_base" => Encoder::Cl100kBase,
+            "p50k_base" => Encoder::P50kBase,
+            "p50k_edit" => Encoder::P50kEdit,
+            "r50k_base" => Encoder::R50kBase,
+            _ => Encoder::Other(encoder),
+        }
+    }
+}
+
+impl From<Encoder> for String {
+    fn from(encoder: Encoder) -> Self {
+        match encoder {
+            Encoder::Other(other) => other,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Encoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 // ------------------
 // Pricing: text vs. image
 // ------------------
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// Text pricing is the common case, so it stays unboxed despite its size.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(untagged)]
 pub enum Pricing {
     TextPricing(TextPricing),
     ImagePricingVec(Vec<ImagePricing>),
+    AudioPricing(AudioPricing),
+    VideoPricing(VideoPricing),
+    RealtimePricing(RealtimePricing),
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
+// Picks the variant from the JSON shape rather than trying each in turn like
+// `untagged` would, so errors inside the pricing point at the failing field.
+impl<'de> Deserialize<'de> for Pricing {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PricingVisitor;
+
+        impl<'de> Visitor<'de> for PricingVisitor {
+            type Value = Pricing;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(
+                    "a text, audio, video, or realtime pricing object, or an array of image prices",
+                )
+            }
+
+            // Audio and video prices land in the text pricing's unknown
+            // fields, so an object with no text prices but one of theirs is
+            // audio or video pricing. Realtime pricing has both text prices
+            // and audio rates.
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Pricing, A::Error> {
+                let raw = RawTextPricing::deserialize(MapAccessDeserializer::new(map))?;
+                if raw.has_any(&RealtimePricing::FIELDS) {
+                    return RealtimePricing::try_from(raw)
+                        .map(Pricing::RealtimePricing)
+                        .map_err(de::Error::custom);
+                }
+                if !raw.has_text_prices() {
+                    if raw.has_any(&VideoPricing::FIELDS) {
+                        return VideoPricing::deserialize(Value::Object(raw.extra))
+                            .map(Pricing::VideoPricing)
+                            .map_err(de::Error::custom);
+                    }
+                    if raw.has_any(&AudioPricing::FIELDS) {
+                        return AudioPricing::deserialize(Value::Object(raw.extra))
+                            .map(Pricing::AudioPricing)
+                            .map_err(de::Error::custom);
+                    }
+                }
+                TextPricing::try_from(raw)
+                    .map(Pricing::TextPricing)
+                    .map_err(de::Error::custom)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Pricing, A::Error> {
+                Vec::deserialize(SeqAccessDeserializer::new(seq)).map(Pricing::ImagePricingVec)
+            }
+        }
+
+        deserializer.deserialize_any(PricingVisitor)
+    }
+}
+
+/// Per-token text pricing.
+///
+/// The JSON may give each price per 1K tokens, per 1M tokens, or both; a
+/// missing half of a pair is derived from the other during deserialization.
+/// Pairs that disagree are left as-is and reported by
+/// [`AiPricingJson::validate`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", try_from = "RawTextPricing")]
 pub struct TextPricing {
     #[serde(default)]
     pub cached_input_per1_k: Option<f64>,
     #[serde(default)]
     pub cached_input_per1_m: Option<f64>,
+    /// Price of reasoning tokens, for models that bill them separately from
+    /// visible output. Absent when they are billed at the output price.
+    #[serde(default)]
+    pub reasoning_output_per1_k: Option<f64>,
+    #[serde(default)]
+    pub reasoning_output_per1_m: Option<f64>,
+    /// Price of writing tokens to the prompt cache, for providers that bill
+    /// cache writes separately from cached reads.
+    #[serde(default)]
+    pub cache_write_per1_k: Option<f64>,
+    #[serde(default)]
+    pub cache_write_per1_m: Option<f64>,
+    /// Per-1M cache-write prices by cache TTL, e.g. `5m` or `1h`, for
+    /// providers that charge more for longer-lived entries.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cache_write_per1_m_by_ttl: BTreeMap<String, f64>,
+    /// Discounted prices for asynchronous batch requests.
+    #[serde(default)]
+    pub batch_input_per1_k: Option<f64>,
+    #[serde(default)]
+    pub batch_input_per1_m: Option<f64>,
+    #[serde(default)]
+    pub batch_output_per1_k: Option<f64>,
+    #[serde(default)]
+    pub batch_output_per1_m: Option<f64>,
+    /// Price of image input tokens, for models that bill them differently
+    /// from text input. See [`Provider::vision_input_cost`].
+    #[serde(default)]
+    pub image_input_per1_k: Option<f64>,
+    #[serde(default)]
+    pub image_input_per1_m: Option<f64>,
+    /// Input and output prices by prompt size, in ascending order, for models
+    /// that charge more for long contexts. The base prices apply when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiers: Vec<PriceTier>,
 
     pub input_per1_k: f64,
     pub input_per1_m: f64,
     pub output_per1_k: f64,
     pub output_per1_m: f64,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `TextPricing` as published, before missing per-1K/per-1M halves are derived.
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct RawTextPricing {
+    #[serde(default, deserialize_with = "number::option_f64")]
+    cached_input_per1_k: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    cached_input_per1_m: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    reasoning_output_per1_k: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    reasoning_output_per1_m: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    cache_write_per1_k: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    cache_write_per1_m: Option<f64>,
+    #[serde(default, deserialize_with = "number::map_f64")]
+    cache_write_per1_m_by_ttl: BTreeMap<String, f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    batch_input_per1_k: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    batch_input_per1_m: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    batch_output_per1_k: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    batch_output_per1_m: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    image_input_per1_k: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    image_input_per1_m: Option<f64>,
+    #[serde(default)]
+    tiers: Vec<PriceTier>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    input_per1_k: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    input_per1_m: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    output_per1_k: Option<f64>,
+    #[serde(default, deserialize_with = "number::option_f64")]
+    output_per1_m: Option<f64>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl RawTextPricing {
+    fn has_text_prices(&self) -> bool {
+        [
+            self.cached_input_per1_k,
+            self.cached_input_per1_m,
+            self.reasoning_output_per1_k,
+            self.reasoning_output_per1_m,
+            self.cache_write_per1_k,
+            self.cache_write_per1_m,
+            self.batch_input_per1_k,
+            self.batch_input_per1_m,
+            self.batch_output_per1_k,
+            self.batch_output_per1_m,
+            self.image_input_per1_k,
+            self.image_input_per1_m,
+            self.input_per1_k,
+            self.input_per1_m,
+            self.output_per1_k,
+            self.output_per1_m,
+        ]
+        .iter()
+        .any(Option::is_some)
+            || !self.cache_write_per1_m_by_ttl.is_empty()
+            || !self.tiers.is_empty()
+    }
+
+    fn has_any(&self, fields: &[&str]) -> bool {
+        fields.iter().any(|field| self.extra.contains_key(*field))
+    }
+}
+
+/// Fill in whichever of a per-1K/per-1M pair is missing.
+fn derive_per1_pair(per1_k: Option<f64>, per1_m: Option<f64>) -> (Option<f64>, Option<f64>) {
+    match (per1_k, per1_m) {
+        (Some(k), None) => (Some(k), Some(k * 1_000.0)),
+        (None, Some(m)) => (Some(m / 1_000.0), Some(m)),
+        pair => pair,
+    }
+}
+
+impl TryFrom<RawTextPricing> for TextPricing {
+    type Error = String;
+
+    fn try_from(raw: RawTextPricing) -> Result<Self, Self::Error> {
+        let (cached_input_per1_k, cached_input_per1_m) =
+            derive_per1_pair(raw.cached_input_per1_k, raw.cached_input_per1_m);
+        let (reasoning_output_per1_k, reasoning_output_per1_m) =
+            derive_per1_pair(raw.reasoning_output_per1_k, raw.reasoning_output_per1_m);
+        let (cache_write_per1_k, cache_write_per1_m) =
+            derive_per1_pair(raw.cache_write_per1_k, raw.cache_write_per1_m);
+        let (batch_input_per1_k, batch_input_per1_m) =
+            derive_per1_pair(raw.batch_input_per1_k, raw.batch_input_per1_m);
+        let (batch_output_per1_k, batch_output_per1_m) =
+            derive_per1_pair(raw.batch_output_per1_k, raw.batch_output_per1_m);
+        let (image_input_per1_k, image_input_per1_m) =
+            derive_per1_pair(raw.image_input_per1_k, raw.image_input_per1_m);
+        let (Some(input_per1_k), Some(input_per1_m)) =
+            derive_per1_pair(raw.input_per1_k, raw.input_per1_m)
+        else {
+            return Err("missing inputPer1K or inputPer1M".to_string());
+        };
+        let (Some(output_per1_k), Some(output_per1_m)) =
+            derive_per1_pair(raw.output_per1_k, raw.output_per1_m)
+        else {
+            return Err("missing outputPer1K or outputPer1M".to_string());
+        };
+
+        Ok(TextPricing {
+            cached_input_per1_k,
+            cached_input_per1_m,
+            reasoning_output_per1_k,
+            reasoning_output_per1_m,
+            cache_write_per1_k,
+            cache_write_per1_m,
+            cache_write_per1_m_by_ttl: raw.cache_write_per1_m_by_ttl,
+            batch_input_per1_k,
+            batch_input_per1_m,
+            batch_output_per1_k,
+            batch_output_per1_m,
+            image_input_per1_k,
+            image_input_per1_m,
+            tiers: raw.tiers,
+            input_per1_k,
+            input_per1_m,
+            output_per1_k,
+            output_per1_m,
+            extra: raw.extra,
+        })
+    }
+}
+
+/// Text prices for requests with at most `up_to_tokens` prompt tokens.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PriceTier {
+    /// The largest prompt this tier covers; `None` for no limit.
+    #[serde(default)]
+    pub up_to_tokens: Option<u64>,
+    #[serde(deserialize_with = "number::f64")]
+    pub input_per1_m: f64,
+    #[serde(deserialize_with = "number::f64")]
+    pub output_per1_m: f64,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ImagePricing {
+    #[serde(deserialize_with = "number::f64")]
     pub cost_per_image: f64,
     pub description: String,
     pub size: String,
+    /// Quality tier, e.g. `standard` or `hd`, for models that price the same
+    /// size differently by quality.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Audio pricing: speech-to-text by the minute, text-to-speech by the
+/// character. A model may offer either or both.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AudioPricing {
+    /// Price per minute of transcribed audio.
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transcription_per_minute: Option<f64>,
+    /// Price per 1K characters of synthesized speech.
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tts_per1_k_characters: Option<f64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl AudioPricing {
+    const FIELDS: [&'static str; 2] = ["transcriptionPerMinute", "ttsPer1KCharacters"];
+}
+
+/// Video generation pricing: cost per second of generated video, by
+/// resolution or quality tier such as `720p` or `1080p`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct VideoPricing {
+    #[serde(deserialize_with = "number::map_f64")]
+    pub cost_per_second: BTreeMap<String, f64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl VideoPricing {
+    const FIELDS: [&'static str; 1] = ["costPerSecond"];
+}
+
+/// Realtime (speech-to-speech) pricing: audio by the minute in each
+/// direction, plus per-token prices for text in the same session.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", try_from = "RawTextPricing")]
+pub struct RealtimePricing {
+    pub audio_input_per_minute: f64,
+    pub audio_output_per_minute: f64,
+    #[serde(flatten)]
+    pub text: TextPricing,
+}
+
+impl RealtimePricing {
+    const FIELDS: [&'static str; 2] = ["audioInputPerMinute", "audioOutputPerMinute"];
+}
+
+impl TryFrom<RawTextPricing> for RealtimePricing {
+    type Error = String;
+
+    fn try_from(mut raw: RawTextPricing) -> Result<Self, Self::Error> {
+        let mut rate = |field: &str| {
+            let value = raw
+                .extra
+                .remove(field)
+                .ok_or_else(|| format!("missing {}", field))?;
+            number::f64(value).map_err(|err| format!("{}: {}", field, err))
+        };
+        let audio_input_per_minute = rate("audioInputPerMinute")?;
+        let audio_output_per_minute = rate("audioOutputPerMinute")?;
+
+        Ok(RealtimePricing {
+            audio_input_per_minute,
+            audio_output_per_minute,
+            text: TextPricing::try_from(raw)?,
+        })
+    }
+}
+
+/// Prices for fine-tuning a model and for running the resulting custom model.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FineTunePricing {
+    /// Price per 1M training tokens, counted across all epochs.
+    #[serde(deserialize_with = "number::f64")]
+    pub training_per1_m: f64,
+    #[serde(deserialize_with = "number::f64")]
+    pub hosted_input_per1_m: f64,
+    #[serde(deserialize_with = "number::f64")]
+    pub hosted_output_per1_m: f64,
+    /// Price per hour of hosting the fine-tuned model, for providers that
+    /// charge for it.
+    #[serde(
+        default,
+        deserialize_with = "number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub storage_per_hour: Option<f64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 // ------------------
 // Product Price IDs
 // ------------------
 
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ProdPriceIds {
     #[serde(default)]
@@ -173,88 +1419,236 @@ pub struct ProdPriceIds {
 }
 
 // ------------------
-// Global cache
+// Default clients
 // ------------------
 
-static AI_PRICING: OnceCell<AiPricingJson> = OnceCell::new();
+/// One default-configured client per environment, backing the free functions
+/// below. Each has its own cache, so fetching dev never satisfies a later
+/// request for prod.
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+static DEFAULT_CLIENTS: Lazy<DashMap<Env, PricingClient>> = Lazy::new(DashMap::new);
 
-// ------------------
-// Fetch function
-// ------------------
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+fn default_client(env: Env) -> Result<PricingClient, PricingError> {
+    if let Some(client) = DEFAULT_CLIENTS.get(&env) {
+        return Ok(client.clone());
+    }
 
-/// Fetch pricing JSON from the given URL and deserialize it.
-async fn fetch_pricing_json(url: &str) -> Result<AiPricingJson, Box<dyn StdError + Send + Sync>> {
-    let client = Client::new();
-    let resp = client.get(url).send().await?.error_for_status()?;
-    let json = resp.json::<AiPricingJson>().await?;
-    Ok(json)
+    let client = PricingClient::builder().default_env(env.clone()).build()?;
+    Ok(DEFAULT_CLIENTS.entry(env).or_insert(client).clone())
 }
 
 /// Public function that returns the AI pricing data, with optional cache-busting.
 ///
-/// **Important**: Because `OnceCell` is strictly synchronous, we cannot directly
-/// store an `async` closure in it. Instead, we do the async work ourselves, then
-/// store the result if the cell is empty.
+/// Each environment has its own cache entry, so fetching dev never satisfies a
+/// later request for prod. A cache-busting call fetches fresh data and
+/// atomically replaces that environment's entry. Callers holding an `Arc` from
+/// before the refresh keep their snapshot; it is freed once the last clone is
+/// dropped.
+///
+/// Cached entries never expire unless `AI_PRICING_TTL_SECS` is set; see
+/// [`get_ai_pricing_with_ttl`] to choose a TTL in code. Setting
+/// `AI_PRICING_URL` fetches that URL instead, whatever `env` is.
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
 pub async fn get_ai_pricing(
-    env: &str,
+    env: Env,
     bust_cache: bool,
-) -> Result<&'static AiPricingJson, Box<dyn StdError + Send + Sync>> {
-    // Determine which URL to use based on environment.
-    let pricing_url = if env == "prod" {
-        "https://images.bookcicle.com/ai/ai-pricing.json".to_string()
-    } else {
-        format!("https://images.bookcicle.com/ai/ai-pricing-{}.json", env)
-    };
-
-    // If we are busting the cache, just fetch fresh data and return it
-    // by leaking a Box. This won't overwrite the cell's existing value.
-    if bust_cache {
-        let fresh_data = fetch_pricing_json(&pricing_url).await?;
-        let boxed = Box::new(fresh_data);
-        let leaked_ref = Box::leak(boxed);
-        return Ok(leaked_ref);
-    }
+) -> Result<Arc<AiPricingJson>, PricingError> {
+    get_cached_pricing(env, None, bust_cache).await
+}
 
-    // If the cell is already set, just return a reference.
-    if let Some(cached_ref) = AI_PRICING.get() {
-        return Ok(cached_ref);
-    }
+/// Like [`get_ai_pricing`], but cached entries older than `ttl` are re-fetched.
+///
+/// The first caller to see an expired entry performs the refresh; callers that
+/// arrive while it is in flight are served the old value.
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+pub async fn get_ai_pricing_with_ttl(
+    env: Env,
+    ttl: Duration,
+) -> Result<Arc<AiPricingJson>, PricingError> {
+    get_cached_pricing(env, Some(ttl), false).await
+}
 
-    // Otherwise, fetch once, store in the cell, and return a reference.
-    let data = fetch_pricing_json(&pricing_url).await?;
-    AI_PRICING
-        .set(data)
-        .map_err(|_| "Cell was already initialized")?;
+/// Pre-populate the cache behind [`get_ai_pricing`] for `env`, so unit tests of
+/// code calling it never perform network IO and control exactly which prices
+/// are in effect. See [`PricingClient::set_pricing`].
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+pub fn set_ai_pricing_for_tests(env: Env, data: AiPricingJson) -> Result<(), PricingError> {
+    default_client(env)?.set_pricing(data);
+    Ok(())
+}
 
-    // Safe to unwrap: it was just set.
-    Ok(AI_PRICING.get().unwrap())
+#[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+async fn get_cached_pricing(
+    env: Env,
+    ttl: Option<Duration>,
+    bust_cache: bool,
+) -> Result<Arc<AiPricingJson>, PricingError> {
+    let client = default_client(env)?;
+    let ttl = ttl.or(client.ttl());
+    client.load(ttl, bust_cache).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio::runtime::Runtime;
 
+    #[test]
+    fn test_model_type_round_trip() {
+        let types: Vec<ModelType> =
+            serde_json::from_str(r#"["text", "image", "audio", "embedding"]"#)
+                .expect("Failed to parse");
+        assert_eq!(
+            types,
+            vec![
+                ModelType::Text,
+                ModelType::Image,
+                ModelType::Audio,
+                ModelType::Other("embedding".to_string())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&types).unwrap(),
+            r#"["text","image","audio","embedding"]"#
+        );
+    }
+
+    #[test]
+    fn test_model_feature_round_trip() {
+        let features: Vec<ModelFeature> =
+            serde_json::from_str(r#"["vision", "json_mode", "web_search"]"#)
+                .expect("Failed to parse");
+        assert_eq!(
+            features,
+            vec![
+                ModelFeature::Vision,
+                ModelFeature::JsonMode,
+                ModelFeature::Other("web_search".to_string())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&features).unwrap(),
+            r#"["vision","json_mode","web_search"]"#
+        );
+    }
+
+    #[test]
+    fn test_encoder_round_trip() {
+        let encoders: Vec<Encoder> =
+            serde_json::from_str(r#"["o200k_base", "cl100k_base", "claude"]"#)
+                .expect("Failed to parse");
+        assert_eq!(encoders[0].tiktoken_name(), Some("o200k_base"));
+        assert_eq!(encoders[1], Encoder::Cl100kBase);
+        assert_eq!(encoders[2], Encoder::Other("claude".to_string()));
+        assert_eq!(encoders[2].tiktoken_name(), None);
+        assert_eq!(
+            serde_json::to_string(&encoders).unwrap(),
+            r#"["o200k_base","cl100k_base","claude"]"#
+        );
+    }
+
+    #[test]
+    fn test_text_pricing_derives_missing_halves() {
+        let pricing: TextPricing = serde_json::from_str(
+            r#"{"cachedInputPer1M": 1.25, "inputPer1M": 2.5, "outputPer1K": 0.01}"#,
+        )
+        .expect("Failed to parse");
+        assert_eq!(pricing.cached_input_per1_k, Some(0.00125));
+        assert_eq!(pricing.input_per1_k, 0.0025);
+        assert_eq!(pricing.output_per1_m, 10.0);
+
+        assert!(serde_json::from_str::<TextPricing>(r#"{"inputPer1M": 2.5}"#).is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_round_trip() {
+        let mut json: Value = serde_json::from_str(test_fixtures::PRICING_JSON).unwrap();
+        json["publishedAt"] = "2024-06-01".into();
+        json["providers"][0]["region"] = "us".into();
+        json["providers"][0]["models"][0]["knowledgeCutoff"] = "2023-10".into();
+        json["providers"][0]["models"][0]["pricing"]["audioInputPer1M"] = 40.0.into();
+        json["providers"][0]["models"][1]["pricing"][0]["style"] = "vivid".into();
+
+        let pricing: AiPricingJson = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(pricing.extra["publishedAt"], "2024-06-01");
+        assert_eq!(
+            pricing.providers[0].models[0].extra["knowledgeCutoff"],
+            "2023-10"
+        );
+
+        let round_tripped = serde_json::to_value(&pricing).unwrap();
+        assert_eq!(round_tripped["providers"][0]["region"], "us");
+        assert_eq!(
+            round_tripped["providers"][0]["models"][0]["pricing"]["audioInputPer1M"],
+            40.0
+        );
+        assert_eq!(
+            round_tripped["providers"][0]["models"][1]["pricing"][0]["style"],
+            "vivid"
+        );
+    }
+
+    #[test]
+    fn test_pricing_equality() {
+        let pricing = test_fixtures::pricing();
+        let reparsed: AiPricingJson =
+            serde_json::from_str(&serde_json::to_string(&pricing).unwrap()).unwrap();
+        assert_eq!(pricing, reparsed);
+
+        let mut changed = pricing.clone();
+        changed.providers[0].models[0].status = Some(ModelStatus::Retired);
+        assert_ne!(pricing, changed);
+
+        let price_ids: std::collections::HashSet<ProdPriceIds> = pricing
+            .providers
+            .iter()
+            .flat_map(|provider| &provider.models)
+            .filter_map(|model| model.prod_price_ids.clone())
+            .collect();
+        assert!(!price_ids.is_empty());
+    }
+
+    #[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
+    #[tokio::test]
+    async fn test_set_ai_pricing_for_tests() {
+        let mut pricing = test_fixtures::pricing();
+        pricing.metered_price_id = "price_injected".to_string();
+        set_ai_pricing_for_tests(Env::Staging, pricing).unwrap();
+
+        let cached = get_ai_pricing(Env::Staging, false).await.unwrap();
+        assert_eq!(cached.metered_price_id, "price_injected");
+    }
+
+    #[cfg(all(feature = "fetch", not(target_arch = "wasm32")))]
     #[test]
     fn test_ai_pricing_cache() {
-        let rt = Runtime::new().expect("Failed to create Tokio runtime");
+        let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
         rt.block_on(async {
+            let server = testing::MockPricingServer::start(&test_fixtures::pricing()).await;
+            let client = server.client(Env::Dev).expect("Failed to build client");
+
             // Fetch from "dev" environment normally (caches result).
-            let response = get_ai_pricing("dev", false)
+            let response = client
+                .get()
                 .await
                 .expect("Failed to fetch dev environment data");
             assert!(
                 !response.metered_price_id.is_empty(),
                 "metered_price_id should not be empty"
             );
+            let cached = client.get().await.expect("Failed to read cached data");
+            assert!(Arc::ptr_eq(&response, &cached));
+            assert_eq!(server.request_count().await, 1);
 
-            let fresh = get_ai_pricing("dev", true)
+            let fresh = client
+                .refresh()
                 .await
                 .expect("Failed to fetch dev environment data with bust_cache=true");
             assert_eq!(
                 response.metered_price_id, fresh.metered_price_id,
                 "Metered price IDs should match (the data is presumably the same JSON)."
             );
+            assert_eq!(server.request_count().await, 2);
         });
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,113 @@
+use crate::{AiPricingJson, Model, ModelStatus, PricingError, Provider};
+
+// ------------------
+// Model lifecycle
+// ------------------
+
+impl Model {
+    /// Whether the model is on its way out: flagged `deprecated`, or with a
+    /// `legacy` or `retired` status.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated.unwrap_or(false)
+            || matches!(
+                self.status,
+                Some(ModelStatus::Legacy | ModelStatus::Retired)
+            )
+    }
+
+    /// Whether the model can still be routed to: not deprecated (see
+    /// [`is_deprecated`](Self::is_deprecated)) or disabled.
+    pub fn is_available(&self) -> bool {
+        !self.is_deprecated() && !self.system_disabled.unwrap_or(false)
+    }
+}
+
+impl Provider {
+    /// The model to use in place of `model_key`: the end of its chain of
+    /// `replacement_model_key`s, or the model itself if it has no replacement.
+    ///
+    /// Fails if the chain names a model this provider doesn't have, or loops.
+    pub fn resolve_replacement(&self, model_key: &str) -> Result<&Model, PricingError> {
+        let find = |key: &str| self.find_model(key);
+        let mut model = find(model_key).ok_or_else(|| {
+            PricingError::Validation(format!("provider {} has no model {}", self.key, model_key))
+        })?;
+
+        let mut visited = vec![model.key.as_str()];
+        while let Some(next) = &model.replacement_model_key {
+            let error = |reason: String| PricingError::Replacement {
+                model: model_key.to_string(),
+                reason,
+            };
+            if visited.contains(&next.as_str()) {
+                return Err(error(format!("{} -> {} loops", visited.join(" -> "), next)));
+            }
+            model = find(next).ok_or_else(|| error(format!("no model {}", next)))?;
+            visited.push(&model.key);
+        }
+        Ok(model)
+    }
+}
+
+impl AiPricingJson {
+    /// [`Provider::resolve_replacement`] for a model of the provider with
+    /// `provider_key`.
+    pub fn resolve_replacement(
+        &self,
+        provider_key: &str,
+        model_key: &str,
+    ) -> Result<&Model, PricingError> {
+        self.find_provider(provider_key)
+            .ok_or_else(|| PricingError::Validation(format!("no provider {}", provider_key)))?
+            .resolve_replacement(model_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_resolve_replacement() {
+        let mut pricing = pricing();
+        let bedrock = &mut pricing.providers[1];
+        bedrock.models[1].replacement_model_key = Some("claude-3-5-sonnet".to_string());
+
+        let successor = pricing
+            .resolve_replacement("bedrock", "claude-3-sonnet")
+            .unwrap();
+        assert_eq!(successor.key, "claude-3-5-sonnet");
+        let current = pricing
+            .resolve_replacement("bedrock", "claude-3-5-sonnet")
+            .unwrap();
+        assert_eq!(current.key, "claude-3-5-sonnet");
+
+        pricing.providers[1].models[0].replacement_model_key = Some("claude-3-sonnet".to_string());
+        match pricing.resolve_replacement("bedrock", "claude-3-sonnet") {
+            Err(PricingError::Replacement { reason, .. }) => assert_eq!(
+                reason,
+                "claude-3-sonnet -> claude-3-5-sonnet -> claude-3-sonnet loops"
+            ),
+            other => panic!("expected a loop, got {:?}", other),
+        }
+
+        pricing.providers[1].models[0].replacement_model_key = Some("claude-4".to_string());
+        assert!(pricing
+            .resolve_replacement("bedrock", "claude-3-sonnet")
+            .is_err());
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        let statuses: Vec<ModelStatus> =
+            serde_json::from_str(r#"["preview", "ga", "retired", "sunset"]"#).unwrap();
+        assert_eq!(statuses[1], ModelStatus::Ga);
+        assert_eq!(statuses[3], ModelStatus::Other("sunset".to_string()));
+
+        let mut model = pricing().providers[0].models[0].clone();
+        assert!(model.is_available());
+        model.status = Some(ModelStatus::Retired);
+        assert!(!model.is_available());
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{AiPricingJson, Model, Provider, RateLimits};
+
+// ------------------
+// Rate limits
+// ------------------
+
+impl RateLimits {
+    /// These limits, with any that are missing taken from `fallback`.
+    pub fn or(self, fallback: RateLimits) -> RateLimits {
+        RateLimits {
+            tpm: self.tpm.or(fallback.tpm),
+            rpm: self.rpm.or(fallback.rpm),
+            concurrent: self.concurrent.or(fallback.concurrent),
+        }
+    }
+
+    /// Whether no limit is known.
+    pub fn is_empty(&self) -> bool {
+        self.tpm.is_none() && self.rpm.is_none() && self.concurrent.is_none()
+    }
+}
+
+impl Provider {
+    /// The limits that apply to `model`: its own, with the provider's filling
+    /// in any it doesn't set.
+    pub fn rate_limits_for(&self, model: &Model) -> RateLimits {
+        model
+            .rate_limits
+            .unwrap_or_default()
+            .or(self.rate_limits.unwrap_or_default())
+    }
+}
+
+impl AiPricingJson {
+    /// [`Provider::rate_limits_for`] by provider and model key. `None` if
+    /// either is missing.
+    pub fn rate_limits_for(&self, provider_key: &str, model_key: &str) -> Option<RateLimits> {
+        let provider = self.find_provider(provider_key)?;
+        Some(provider.rate_limits_for(provider.find_model(model_key)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use serde_json::json;
+
+    #[test]
+    fn test_rate_limits_for() {
+        let mut pricing = pricing();
+        assert!(pricing
+            .rate_limits_for("openai", "gpt-4o")
+            .unwrap()
+            .is_empty());
+
+        let openai = &mut pricing.providers[0];
+        openai.rate_limits = Some(RateLimits {
+            tpm: Some(30_000),
+            rpm: Some(500),
+            concurrent: None,
+        });
+        openai.models[0].rate_limits = Some(
+            serde_json::from_value(json!({ "tpm": 800_000, "concurrent": 10 }))
+                .expect("Failed to parse"),
+        );
+
+        let limits = pricing.rate_limits_for("openai", "gpt-4o").unwrap();
+        assert_eq!(
+            limits,
+            RateLimits {
+                tpm: Some(800_000),
+                rpm: Some(500),
+                concurrent: Some(10),
+            }
+        );
+        assert_eq!(
+            pricing.rate_limits_for("openai", "dall-e-3").unwrap().tpm,
+            Some(30_000)
+        );
+        assert!(pricing.rate_limits_for("openai", "missing").is_none());
+
+        let json = serde_json::to_value(&pricing.providers[0]).unwrap();
+        assert_eq!(json["rateLimits"], json!({ "tpm": 30_000, "rpm": 500 }));
+        assert!(json["models"][1].get("rateLimits").is_none());
+    }
+}
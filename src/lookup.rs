@@ -0,0 +1,99 @@
+use crate::{AiPricingJson, Model, Provider};
+
+// ------------------
+// Lookups
+// ------------------
+
+impl AiPricingJson {
+    /// Find a provider by its key.
+    pub fn find_provider(&self, provider_key: &str) -> Option<&Provider> {
+        self.providers.iter().find(|p| p.key == provider_key)
+    }
+
+    /// Find a model by key or alias within the given provider.
+    pub fn find_model(&self, provider_key: &str, model_key: &str) -> Option<&Model> {
+        self.find_provider(provider_key)?.find_model(model_key)
+    }
+
+    /// Find the first model with the given key across all providers, falling
+    /// back to the first with it as an alias.
+    pub fn find_model_anywhere(&self, model_key: &str) -> Option<(&Provider, &Model)> {
+        self.all_models()
+            .find(|(_, m)| m.key == model_key)
+            .or_else(|| self.all_models().find(|(_, m)| m.is_alias(model_key)))
+    }
+
+    /// Iterate over every model along with the provider that offers it.
+    pub fn all_models(&self) -> impl Iterator<Item = (&Provider, &Model)> {
+        self.providers
+            .iter()
+            .flat_map(|p| p.models.iter().map(move |m| (p, m)))
+    }
+}
+
+impl Provider {
+    /// Find one of this provider's models by key, falling back to aliases.
+    pub fn find_model(&self, model_key: &str) -> Option<&Model> {
+        self.models
+            .iter()
+            .find(|m| m.key == model_key)
+            .or_else(|| self.models.iter().find(|m| m.is_alias(model_key)))
+    }
+}
+
+impl Model {
+    /// Whether `name` is one of this model's aliases.
+    pub fn is_alias(&self, name: &str) -> bool {
+        self.aliases.iter().any(|alias| alias == name)
+    }
+
+    /// Whether `name` is this model's key or one of its aliases.
+    pub fn answers_to(&self, name: &str) -> bool {
+        self.key == name || self.is_alias(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_lookups() {
+        let pricing = pricing();
+
+        assert_eq!(pricing.find_provider("openai").unwrap().label, "OpenAI");
+        assert!(pricing.find_provider("anthropic").is_none());
+
+        assert!(pricing.find_model("openai", "gpt-4o").is_some());
+        assert!(pricing.find_model("openai", "claude-3-5-sonnet").is_none());
+
+        let (provider, model) = pricing.find_model_anywhere("claude-3-5-sonnet").unwrap();
+        assert_eq!(provider.key, "bedrock");
+        assert_eq!(model.key, "claude-3-5-sonnet");
+
+        assert_eq!(pricing.all_models().count(), 4);
+    }
+
+    #[test]
+    fn test_alias_lookups() {
+        let mut pricing = pricing();
+        pricing.providers[0].models[0].aliases = vec!["gpt-4o-2024-08-06".to_string()];
+        pricing.providers[1].models[0].aliases = vec!["gpt-4o".to_string()];
+
+        let model = pricing.find_model("openai", "gpt-4o-2024-08-06").unwrap();
+        assert_eq!(model.key, "gpt-4o");
+        assert!(model.answers_to("gpt-4o-2024-08-06"));
+        assert!(pricing.find_model("bedrock", "gpt-4o-2024-08-06").is_none());
+
+        // A key beats an alias, even from a later provider.
+        let (provider, _) = pricing.find_model_anywhere("gpt-4o").unwrap();
+        assert_eq!(provider.key, "openai");
+        let (provider, _) = pricing.find_model_anywhere("gpt-4o-2024-08-06").unwrap();
+        assert_eq!(provider.key, "openai");
+
+        let json = serde_json::to_value(&pricing.providers[0].models[0]).unwrap();
+        assert_eq!(json["aliases"][0], "gpt-4o-2024-08-06");
+        let json = serde_json::to_value(&pricing.providers[0].models[1]).unwrap();
+        assert!(json.get("aliases").is_none());
+    }
+}
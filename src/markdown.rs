@@ -0,0 +1,89 @@
+use crate::{AiPricingJson, PriceColumn, PriceRow};
+use std::fmt::Write;
+
+// ------------------
+// Markdown tables
+// ------------------
+
+impl AiPricingJson {
+    /// Render every text model's prices as markdown, for docs and changelog
+    /// PRs: a section per provider in file order, each a table sorted by
+    /// blended price, cheapest first. Deprecated models are marked rather
+    /// than left out.
+    pub fn render_markdown(&self) -> String {
+        let table = self.price_table().sorted_by(PriceColumn::Blended);
+        let mut out = format!(
+            "Prices per 1M tokens in {}, blended at {}:1 input:output.\n",
+            self.currency(),
+            table.blend_ratio
+        );
+
+        for provider in &self.providers {
+            let rows: Vec<&PriceRow> = table
+                .rows
+                .iter()
+                .filter(|row| row.provider_key == provider.key)
+                .collect();
+            if rows.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(out, "\n### {}\n", provider.label);
+            out.push_str("| Model | Input | Output | Cached input | Blended |\n");
+            out.push_str("| --- | ---: | ---: | ---: | ---: |\n");
+            for row in rows {
+                let model = match row.deprecated {
+                    true => format!("`{}` (deprecated)", row.model_key),
+                    false => format!("`{}`", row.model_key),
+                };
+                let cached = row.cached_input_per1_m.map_or("-".to_string(), price);
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {} | {} |",
+                    model,
+                    price(row.input_per1_m),
+                    price(row.output_per1_m),
+                    cached,
+                    price(row.blended_per1_m)
+                );
+            }
+        }
+        out
+    }
+}
+
+/// A price with at least two decimals, and up to four for sub-cent prices.
+pub(crate) fn price(value: f64) -> String {
+    let mut s = format!("{:.4}", value);
+    while s.ends_with('0') && s.len() - s.find('.').unwrap_or(s.len()) > 3 {
+        s.pop();
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = pricing().render_markdown();
+        assert!(markdown.starts_with("Prices per 1M tokens in USD, blended at 3:1"));
+        assert!(markdown.contains(
+            "### OpenAI\n\n\
+             | Model | Input | Output | Cached input | Blended |\n\
+             | --- | ---: | ---: | ---: | ---: |\n\
+             | `gpt-4o` | 2.50 | 10.00 | 1.25 | 4.375 |\n"
+        ));
+        assert!(markdown.contains("| `claude-3-sonnet` (deprecated) | 3.00 | 15.00 | - | 6.00 |"));
+        assert!(!markdown.contains("dall-e-3"));
+    }
+
+    #[test]
+    fn test_price() {
+        assert_eq!(price(3.0), "3.00");
+        assert_eq!(price(0.075), "0.075");
+        assert_eq!(price(0.00125), "0.0013");
+    }
+}
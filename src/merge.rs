@@ -0,0 +1,180 @@
+use crate::{AiPricingJson, PricingError};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+// ------------------
+// Overlays
+// ------------------
+
+impl AiPricingJson {
+    /// Apply a partial pricing file on top of this one, e.g. tenant-specific
+    /// markups or a temporary promotional price. The overlay wins.
+    ///
+    /// Objects merge as in JSON merge patch (RFC 7386): fields in the overlay
+    /// replace the base's, `null` removes a field, and fields the overlay
+    /// leaves out are kept. Arrays of objects with a `key` (providers and
+    /// models) merge element-wise by key instead of being replaced, and
+    /// overlay elements with a new key are appended. Setting one half of a
+    /// per-1K/per-1M price pair re-derives the other.
+    pub fn merge(&self, overlay: &Value) -> Result<AiPricingJson, PricingError> {
+        let mut merged = serde_json::to_value(self).map_err(deserialize_error)?;
+        merge_patch(&mut merged, overlay);
+        crate::parse::from_value(merged).map_err(deserialize_error)
+    }
+
+    /// Read a base pricing file and merge each overlay file onto it in turn,
+    /// so later files take precedence over earlier ones.
+    pub fn from_layers<P: AsRef<Path>>(
+        base: P,
+        overlays: impl IntoIterator<Item = P>,
+    ) -> Result<AiPricingJson, PricingError> {
+        let mut pricing = AiPricingJson::from_path(base)?;
+        for overlay in overlays {
+            let path = overlay.as_ref();
+            let body = std::fs::read(path).map_err(|source| PricingError::Io {
+                source,
+                path: path.to_path_buf(),
+            })?;
+            let overlay: Value =
+                serde_json::from_slice(&body).map_err(|source| PricingError::Deserialize {
+                    source,
+                    url: path.display().to_string(),
+                })?;
+            pricing = pricing.merge(&overlay)?;
+        }
+        Ok(pricing)
+    }
+}
+
+fn deserialize_error(source: serde_json::Error) -> PricingError {
+    PricingError::Deserialize {
+        source,
+        url: "merged pricing overlay".to_string(),
+    }
+}
+
+/// Merge `patch` into `target` with the semantics described on
+/// [`AiPricingJson::merge`].
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => merge_objects(target, patch),
+        (Value::Array(target), Value::Array(patch)) if is_keyed(target) && is_keyed(patch) => {
+            for element in patch {
+                let existing = target
+                    .iter_mut()
+                    .find(|t| t.get("key") == element.get("key"));
+                match existing {
+                    Some(existing) => merge_patch(existing, element),
+                    None => target.push(without_nulls(element)),
+                }
+            }
+        }
+        (target, patch) => *target = without_nulls(patch),
+    }
+}
+
+fn merge_objects(target: &mut Map<String, Value>, patch: &Map<String, Value>) {
+    // A price given only per-1K or only per-1M drops the other half, so it's
+    // derived again rather than left stale.
+    for counterpart in patch.keys().filter_map(|field| counterpart(field)) {
+        if !patch.contains_key(&counterpart) {
+            target.remove(&counterpart);
+        }
+    }
+
+    for (field, value) in patch {
+        if value.is_null() {
+            target.remove(field);
+        } else {
+            merge_patch(target.entry(field.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// The other half of a per-1K/per-1M price pair, e.g. `inputPer1K` for
+/// `inputPer1M`.
+pub(crate) fn counterpart(field: &str) -> Option<String> {
+    match (field.strip_suffix("Per1K"), field.strip_suffix("Per1M")) {
+        (Some(name), _) => Some(format!("{}Per1M", name)),
+        (_, Some(name)) => Some(format!("{}Per1K", name)),
+        _ => None,
+    }
+}
+
+fn is_keyed(array: &[Value]) -> bool {
+    !array.is_empty()
+        && array
+            .iter()
+            .all(|v| v.get("key").is_some_and(Value::is_string))
+}
+
+/// A value being inserted fresh has no base to remove fields from, so any
+/// nulls in it are dropped rather than kept.
+fn without_nulls(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k.clone(), without_nulls(v)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::Pricing;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_overlay() {
+        let overlay = json!({
+            "providers": [
+                {
+                    "key": "openai",
+                    "markup": { "textPercentage": 10.0 },
+                    "models": [
+                        { "key": "gpt-4o", "pricing": { "inputPer1M": 2.0 }, "streaming": null }
+                    ]
+                }
+            ]
+        });
+        let merged = pricing().merge(&overlay).expect("Failed to merge");
+
+        let openai = merged.find_provider("openai").unwrap();
+        assert_eq!(openai.markup.text_percentage, 10.0);
+        assert_eq!(openai.markup.image_percentage, 50.0);
+        assert_eq!(openai.models.len(), 2);
+        assert_eq!(merged.providers.len(), 2);
+
+        let gpt = merged.find_model("openai", "gpt-4o").unwrap();
+        assert_eq!(gpt.streaming, None);
+        let Some(Pricing::TextPricing(text)) = &gpt.pricing else {
+            panic!("gpt-4o should have text pricing");
+        };
+        assert_eq!(text.input_per1_m, 2.0);
+        assert_eq!(text.input_per1_k, 0.002);
+        assert_eq!(text.output_per1_m, 10.0);
+        assert!(merged.validate().is_ok());
+    }
+
+    #[test]
+    fn test_merge_appends_new_keys() {
+        let mut extra = serde_json::to_value(&pricing().providers[1]).unwrap();
+        extra["key"] = json!("bedrock-eu");
+        let merged = pricing()
+            .merge(&json!({ "providers": [extra], "meteredPriceId": "price_promo" }))
+            .unwrap();
+        assert_eq!(merged.metered_price_id, "price_promo");
+        assert_eq!(merged.providers[2].key, "bedrock-eu");
+
+        assert!(matches!(
+            pricing().merge(&json!({ "providers": "none" })),
+            Err(PricingError::Deserialize { .. })
+        ));
+    }
+}
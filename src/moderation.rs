@@ -0,0 +1,475 @@
+use crate::{Categories, CategoryScore, Model, ModerationThreshold, Provider};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+// ------------------
+// Moderation scores
+// ------------------
+
+/// One result from OpenAI's moderation API, as returned under `results[]`.
+///
+/// Categories are keyed by their API names (`"hate/threatening"`,
+/// `"self-harm/intent"`, ...), so categories this crate doesn't know about
+/// still take part in the `general` threshold.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModerationScores {
+    #[serde(default)]
+    pub flagged: bool,
+    #[serde(default)]
+    pub categories: BTreeMap<String, bool>,
+    #[serde(default)]
+    pub category_scores: BTreeMap<String, f64>,
+}
+
+impl Categories {
+    /// Each category by its moderation API name, with whether it is blocked.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, bool)> + '_ {
+        [
+            ("harassment", self.harassment),
+            ("harassment/threatening", self.harassment_threatening),
+            ("hate", self.hate),
+            ("hate/threatening", self.hate_threatening),
+            ("illicit", self.illicit),
+            ("illicit/violent", self.illicit_violent),
+            ("self-harm", self.self_harm),
+            ("self-harm/instructions", self.self_harm_instructions),
+            ("self-harm/intent", self.self_harm_intent),
+            ("sexual", self.sexual),
+            ("sexual/minors", self.sexual_minors),
+            ("violence", self.violence),
+            ("violence/graphic", self.violence_graphic),
+        ]
+        .into_iter()
+        .chain(
+            self.extra
+                .iter()
+                .map(|(category, &blocked)| (category.as_str(), blocked)),
+        )
+    }
+
+    /// Set whether a category, by its moderation API name, is blocked.
+    /// Unknown categories go into `extra`.
+    pub fn set(&mut self, category: &str, blocked: bool) {
+        let field = match category {
+            "harassment" => &mut self.harassment,
+            "harassment/threatening" => &mut self.harassment_threatening,
+            "hate" => &mut self.hate,
+            "hate/threatening" => &mut self.hate_threatening,
+            "illicit" => &mut self.illicit,
+            "illicit/violent" => &mut self.illicit_violent,
+            "self-harm" => &mut self.self_harm,
+            "self-harm/instructions" => &mut self.self_harm_instructions,
+            "self-harm/intent" => &mut self.self_harm_intent,
+            "sexual" => &mut self.sexual,
+            "sexual/minors" => &mut self.sexual_minors,
+            "violence" => &mut self.violence,
+            "violence/graphic" => &mut self.violence_graphic,
+            _ => {
+                self.extra.insert(category.to_string(), blocked);
+                return;
+            }
+        };
+        *field = blocked;
+    }
+}
+
+impl CategoryScore {
+    /// Each category that has a blocking threshold, by its moderation API name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> + '_ {
+        [
+            ("harassment", self.harassment),
+            ("harassment/threatening", self.harassment_threatening),
+            ("hate", self.hate),
+            ("hate/threatening", self.hate_threatening),
+            ("illicit", self.illicit),
+            ("illicit/violent", self.illicit_violent),
+            ("self-harm", self.self_harm),
+            ("self-harm/instructions", self.self_harm_instructions),
+            ("self-harm/intent", self.self_harm_intent),
+            ("sexual", self.sexual),
+            ("sexual/minors", self.sexual_minors),
+            ("violence", self.violence),
+            ("violence/graphic", self.violence_graphic),
+        ]
+        .into_iter()
+        .filter_map(|(category, threshold)| Some((category, threshold?)))
+        .chain(
+            self.extra
+                .iter()
+                .map(|(category, &threshold)| (category.as_str(), threshold)),
+        )
+    }
+
+    /// Set a category's blocking threshold by its moderation API name.
+    /// Unknown categories go into `extra`.
+    pub fn set(&mut self, category: &str, threshold: f64) {
+        let field = match category {
+            "harassment" => &mut self.harassment,
+            "harassment/threatening" => &mut self.harassment_threatening,
+            "hate" => &mut self.hate,
+            "hate/threatening" => &mut self.hate_threatening,
+            "illicit" => &mut self.illicit,
+            "illicit/violent" => &mut self.illicit_violent,
+            "self-harm" => &mut self.self_harm,
+            "self-harm/instructions" => &mut self.self_harm_instructions,
+            "self-harm/intent" => &mut self.self_harm_intent,
+            "sexual" => &mut self.sexual,
+            "sexual/minors" => &mut self.sexual_minors,
+            "violence" => &mut self.violence,
+            "violence/graphic" => &mut self.violence_graphic,
+            _ => {
+                self.extra.insert(category.to_string(), threshold);
+                return;
+            }
+        };
+        *field = Some(threshold);
+    }
+}
+
+// ------------------
+// Defaults and builder
+// ------------------
+
+/// Default score threshold for the `general` check.
+pub const DEFAULT_GENERAL_THRESHOLD: f64 = 0.8;
+
+impl Default for ModerationThreshold {
+    /// Blocks hate, self-harm, and sexual content involving minors whenever
+    /// the moderation API flags them, blocks threatening, illicit, and graphic
+    /// content past a per-category score, and flags anything else scoring
+    /// [`DEFAULT_GENERAL_THRESHOLD`] or more.
+    fn default() -> Self {
+        let mut threshold = ModerationThreshold {
+            categories: Categories::default(),
+            category_score: CategoryScore::default(),
+            general: DEFAULT_GENERAL_THRESHOLD,
+        };
+        for category in [
+            "hate",
+            "hate/threatening",
+            "self-harm",
+            "self-harm/instructions",
+            "self-harm/intent",
+            "sexual/minors",
+        ] {
+            threshold.categories.set(category, true);
+        }
+        for (category, score) in [
+            ("harassment/threatening", 0.5),
+            ("illicit", 0.5),
+            ("illicit/violent", 0.4),
+            ("violence/graphic", 0.6),
+        ] {
+            threshold.category_score.set(category, score);
+        }
+        threshold
+    }
+}
+
+impl ModerationThreshold {
+    /// Start from the [`Default`] thresholds, e.g. to build a fallback when
+    /// pricing JSON is unavailable.
+    pub fn builder() -> ModerationThresholdBuilder {
+        ModerationThresholdBuilder::default()
+    }
+}
+
+/// Builds a [`ModerationThreshold`]. Categories are named as in the
+/// moderation API (`"hate/threatening"`, `"self-harm/intent"`, ...).
+#[derive(Debug, Clone, Default)]
+pub struct ModerationThresholdBuilder {
+    threshold: ModerationThreshold,
+}
+
+impl ModerationThresholdBuilder {
+    /// Block content whenever the moderation API flags `category`.
+    pub fn block(mut self, category: &str) -> Self {
+        self.threshold.categories.set(category, true);
+        self
+    }
+
+    /// Stop blocking `category` on the API's flag alone. Its score threshold,
+    /// if any, still applies.
+    pub fn allow(mut self, category: &str) -> Self {
+        self.threshold.categories.set(category, false);
+        self
+    }
+
+    /// Block content when `category` scores `threshold` or more.
+    pub fn category_score(mut self, category: &str, threshold: f64) -> Self {
+        self.threshold.category_score.set(category, threshold);
+        self
+    }
+
+    /// Flag content when any category scores `threshold` or more. Defaults to
+    /// [`DEFAULT_GENERAL_THRESHOLD`].
+    pub fn general(mut self, threshold: f64) -> Self {
+        self.threshold.general = threshold;
+        self
+    }
+
+    pub fn build(self) -> ModerationThreshold {
+        self.threshold
+    }
+}
+
+// ------------------
+// Per-model overrides
+// ------------------
+
+/// A model's moderation settings, layered over its provider's
+/// [`ModerationThreshold`]. Only the categories listed here change; the rest
+/// keep the provider's settings.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ModerationOverride {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub categories: BTreeMap<String, bool>,
+    #[serde(
+        default,
+        deserialize_with = "crate::number::map_f64",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub category_score: BTreeMap<String, f64>,
+    #[serde(
+        default,
+        deserialize_with = "crate::number::option_f64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub general: Option<f64>,
+}
+
+impl ModerationThreshold {
+    /// These thresholds with `overrides` applied on top.
+    pub fn merged(&self, overrides: &ModerationOverride) -> ModerationThreshold {
+        let mut merged = self.clone();
+        for (category, &blocked) in &overrides.categories {
+            merged.categories.set(category, blocked);
+        }
+        for (category, &threshold) in &overrides.category_score {
+            merged.category_score.set(category, threshold);
+        }
+        if let Some(general) = overrides.general {
+            merged.general = general;
+        }
+        merged
+    }
+}
+
+impl Provider {
+    /// The moderation thresholds that apply to `model`: the provider's, with
+    /// the model's own overrides merged on top.
+    pub fn moderation_threshold_for(&self, model: &Model) -> Cow<'_, ModerationThreshold> {
+        match &model.moderation_threshold {
+            Some(overrides) => Cow::Owned(self.moderation_threshold.merged(overrides)),
+            None => Cow::Borrowed(&self.moderation_threshold),
+        }
+    }
+}
+
+// ------------------
+// Moderation decision
+// ------------------
+
+/// What to do with moderated content, with the categories that caused it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", tag = "action", content = "categories")]
+pub enum ModerationDecision {
+    Allow,
+    /// Some category scored at or above the `general` threshold.
+    Flag(Vec<String>),
+    /// A blocked category was flagged, or scored at or above its
+    /// per-category threshold.
+    Block(Vec<String>),
+}
+
+impl ModerationDecision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, ModerationDecision::Allow)
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        matches!(self, ModerationDecision::Block(_))
+    }
+
+    /// The categories that triggered the decision; empty for `Allow`.
+    pub fn categories(&self) -> &[String] {
+        match self {
+            ModerationDecision::Allow => &[],
+            ModerationDecision::Flag(categories) | ModerationDecision::Block(categories) => {
+                categories
+            }
+        }
+    }
+}
+
+/// Compare a moderation API result against a provider's thresholds.
+///
+/// Content is blocked when the API flags a category that `categories` marks as
+/// blocked, or when a category's score reaches its `category_score` threshold.
+/// Otherwise it is flagged when any category's score reaches `general`, and
+/// allowed if none does. Triggering categories are listed in API name order.
+pub fn evaluate_moderation(
+    provider: &ModerationThreshold,
+    scores: &ModerationScores,
+) -> ModerationDecision {
+    let flagged = |category: &str| scores.categories.get(category).copied().unwrap_or(false);
+    let score = |category: &str| scores.category_scores.get(category).copied();
+
+    let mut blocked: Vec<String> = provider
+        .categories
+        .iter()
+        .filter(|&(category, blocked)| blocked && flagged(category))
+        .map(|(category, _)| category.to_string())
+        .collect();
+    blocked.extend(
+        provider
+            .category_score
+            .iter()
+            .filter(|&(category, threshold)| score(category).is_some_and(|s| s >= threshold))
+            .map(|(category, _)| category.to_string()),
+    );
+    if !blocked.is_empty() {
+        blocked.sort();
+        blocked.dedup();
+        return ModerationDecision::Block(blocked);
+    }
+
+    let flagged: Vec<String> = scores
+        .category_scores
+        .iter()
+        .filter(|&(_, &score)| score >= provider.general)
+        .map(|(category, _)| category.clone())
+        .collect();
+    if flagged.is_empty() {
+        ModerationDecision::Allow
+    } else {
+        ModerationDecision::Flag(flagged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    fn scores(json: &str) -> ModerationScores {
+        serde_json::from_str(json).expect("Failed to parse moderation result")
+    }
+
+    #[test]
+    fn test_evaluate_moderation() {
+        let threshold = pricing().providers[0].moderation_threshold.clone();
+
+        let clean = scores(r#"{"flagged": false, "category_scores": {"hate": 0.01}}"#);
+        assert_eq!(
+            evaluate_moderation(&threshold, &clean),
+            ModerationDecision::Allow
+        );
+
+        let general = scores(r#"{"category_scores": {"sexual": 0.9, "violence": 0.2}}"#);
+        assert_eq!(
+            evaluate_moderation(&threshold, &general),
+            ModerationDecision::Flag(vec!["sexual".to_string()])
+        );
+
+        let blocked = scores(
+            r#"{
+                "flagged": true,
+                "categories": {"hate": true, "sexual": true},
+                "category_scores": {"hate": 0.3, "illicit": 0.55, "sexual": 0.9}
+            }"#,
+        );
+        let decision = evaluate_moderation(&threshold, &blocked);
+        assert!(decision.is_blocked());
+        assert_eq!(decision.categories(), ["hate", "illicit"]);
+    }
+
+    #[test]
+    fn test_unknown_categories_round_trip() {
+        let json = r#"{
+            "categories": {"hate": true, "violence": true, "deepfakes": true},
+            "categoryScore": {"illicit": 0.5, "deepfakes": 0.3},
+            "general": 0.8
+        }"#;
+        let threshold: ModerationThreshold = serde_json::from_str(json).expect("Failed to parse");
+        assert!(threshold.categories.violence);
+        assert!(!threshold.categories.sexual);
+        assert_eq!(threshold.category_score.illicit, Some(0.5));
+        assert_eq!(threshold.category_score.violence, None);
+        assert_eq!(threshold.categories.extra.get("deepfakes"), Some(&true));
+
+        let value = serde_json::to_value(&threshold).unwrap();
+        assert_eq!(value["categories"]["deepfakes"], true);
+        assert_eq!(value["categoryScore"]["deepfakes"], 0.3);
+        assert!(value["categoryScore"].get("violence").is_none());
+
+        let scores = scores(r#"{"category_scores": {"deepfakes": 0.35}}"#);
+        assert_eq!(
+            evaluate_moderation(&threshold, &scores),
+            ModerationDecision::Block(vec!["deepfakes".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_model_override_merges_onto_provider() {
+        let provider = pricing().providers.remove(1);
+        let mut model = provider.models[0].clone();
+        assert!(matches!(
+            provider.moderation_threshold_for(&model),
+            Cow::Borrowed(_)
+        ));
+
+        model.moderation_threshold = Some(
+            serde_json::from_value(serde_json::json!({
+                "categories": {"sexual": true, "hate": false, "deepfakes": true},
+                "categoryScore": {"violence/graphic": 0.2},
+                "general": 0.5
+            }))
+            .expect("Failed to parse override"),
+        );
+        let merged = provider.moderation_threshold_for(&model);
+        assert!(merged.categories.sexual);
+        assert!(!merged.categories.hate);
+        assert!(merged.categories.self_harm);
+        assert_eq!(merged.categories.extra.get("deepfakes"), Some(&true));
+        assert_eq!(merged.category_score.violence_graphic, Some(0.2));
+        assert_eq!(
+            merged.category_score.illicit,
+            provider.moderation_threshold.category_score.illicit
+        );
+        assert_eq!(merged.general, 0.5);
+    }
+
+    #[test]
+    fn test_builder() {
+        let threshold = ModerationThreshold::builder()
+            .block("violence")
+            .allow("hate")
+            .category_score("sexual", 0.3)
+            .general(0.7)
+            .build();
+        assert!(threshold.categories.violence);
+        assert!(!threshold.categories.hate);
+        assert!(threshold.categories.sexual_minors);
+        assert_eq!(threshold.category_score.sexual, Some(0.3));
+        assert_eq!(threshold.category_score.illicit, Some(0.5));
+        assert_eq!(threshold.general, 0.7);
+
+        let scores = scores(r#"{"categories": {"hate": true}, "category_scores": {"hate": 0.75}}"#);
+        assert_eq!(
+            evaluate_moderation(&ModerationThreshold::default(), &scores),
+            ModerationDecision::Block(vec!["hate".to_string()])
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+// ------------------
+// Lenient numbers
+// ------------------
+
+// Some upstream tooling exports prices as strings such as "0.0025". These
+// `deserialize_with` helpers accept either form; anything else fails with the
+// usual serde error, which names the field's path.
+
+struct LenientF64;
+
+impl Visitor<'_> for LenientF64 {
+    type Value = f64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a number or a numeric string")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<f64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<f64, E> {
+        Ok(value as f64)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<f64, E> {
+        Ok(value as f64)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<f64, E> {
+        value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .ok_or_else(|| E::invalid_value(Unexpected::Str(value), &self))
+    }
+}
+
+#[derive(Deserialize)]
+struct Lenient(#[serde(deserialize_with = "f64")] f64);
+
+pub(crate) fn f64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    deserializer.deserialize_any(LenientF64)
+}
+
+pub(crate) fn option_f64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    Ok(Option::<Lenient>::deserialize(deserializer)?.map(|number| number.0))
+}
+
+pub(crate) fn map_f64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, f64>, D::Error> {
+    let map = BTreeMap::<String, Lenient>::deserialize(deserializer)?;
+    Ok(map
+        .into_iter()
+        .map(|(key, number)| (key, number.0))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::PRICING_JSON;
+    use crate::{AiPricingJson, ModerationOverride, Pricing};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_accepts_numeric_strings() {
+        let mut json: Value = serde_json::from_str(PRICING_JSON).unwrap();
+        let openai = &mut json["providers"][0];
+        openai["markup"]["textPercentage"] = "20".into();
+        openai["moderationThreshold"]["general"] = " 0.8 ".into();
+        openai["moderationThreshold"]["categoryScore"]["illicit"] = "0.5".into();
+        openai["moderationThreshold"]["categoryScore"]["deepfakes"] = "0.3".into();
+        openai["models"][0]["pricing"]["inputPer1K"] = "0.0025".into();
+        openai["models"][0]["pricing"]["inputPer1M"] = Value::Null;
+        openai["models"][1]["pricing"][0]["costPerImage"] = "0.04".into();
+
+        let pricing: AiPricingJson = serde_json::from_value(json).expect("Failed to parse");
+        let openai = &pricing.providers[0];
+        assert_eq!(openai.markup.text_percentage, 20.0);
+        let threshold = &openai.moderation_threshold;
+        assert_eq!(threshold.general, 0.8);
+        assert_eq!(threshold.category_score.illicit, Some(0.5));
+        assert_eq!(threshold.category_score.extra["deepfakes"], 0.3);
+        let Some(Pricing::TextPricing(text)) = &openai.models[0].pricing else {
+            panic!("gpt-4o should have text pricing");
+        };
+        assert_eq!(text.input_per1_m, 2.5);
+
+        let overrides: ModerationOverride = serde_json::from_value(
+            json!({ "categoryScore": { "sexual": "0.2" }, "general": "0.5" }),
+        )
+        .unwrap();
+        assert_eq!(overrides.category_score["sexual"], 0.2);
+        assert_eq!(overrides.general, Some(0.5));
+    }
+
+    #[test]
+    fn test_rejects_non_numeric_strings() {
+        let mut json: Value = serde_json::from_str(PRICING_JSON).unwrap();
+        json["providers"][1]["models"][0]["pricing"]["outputPer1M"] = "fifteen".into();
+        let err = crate::parse::parse(&serde_json::to_vec(&json).unwrap(), "test").unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "providers[1].models[0].pricing.outputPer1M: invalid value: string \"fifteen\""
+            ),
+            "{}",
+            err
+        );
+
+        json["providers"][1]["models"][0]["pricing"]["outputPer1M"] = "NaN".into();
+        assert!(serde_json::from_value::<AiPricingJson>(json).is_err());
+    }
+}
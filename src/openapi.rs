@@ -0,0 +1,138 @@
+use crate::{
+    AuthScheme, Encoder, ImageSize, MarkupOverlay, ModelFeature, ModelStatus, ModelType, PlanMarkup,
+};
+use std::borrow::Cow;
+use utoipa::openapi::schema::{AdditionalProperties, ObjectBuilder, Schema, Type};
+use utoipa::openapi::{Ref, RefOr};
+use utoipa::{PartialSchema, ToSchema};
+
+// ------------------
+// OpenAPI schemas
+// ------------------
+
+// Types that serialize as something other than their Rust shape, e.g. enums
+// with an `Other(String)` fallback, describe themselves by hand.
+
+fn string_schema(description: &str) -> RefOr<Schema> {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .description(Some(description))
+        .into()
+}
+
+impl PartialSchema for ModelType {
+    fn schema() -> RefOr<Schema> {
+        string_schema("The kind of model, e.g. `text` or `image`.")
+    }
+}
+
+impl ToSchema for ModelType {}
+
+impl PartialSchema for ModelStatus {
+    fn schema() -> RefOr<Schema> {
+        string_schema("A model's lifecycle stage, e.g. `preview` or `ga`.")
+    }
+}
+
+impl ToSchema for ModelStatus {}
+
+impl PartialSchema for ModelFeature {
+    fn schema() -> RefOr<Schema> {
+        string_schema("A model capability, e.g. `vision`.")
+    }
+}
+
+impl ToSchema for ModelFeature {}
+
+impl PartialSchema for Encoder {
+    fn schema() -> RefOr<Schema> {
+        string_schema("The tokenizer a model counts tokens with, e.g. `o200k_base`.")
+    }
+}
+
+impl ToSchema for Encoder {}
+
+impl PartialSchema for AuthScheme {
+    fn schema() -> RefOr<Schema> {
+        string_schema("How a provider expects credentials, e.g. `bearer`.")
+    }
+}
+
+impl ToSchema for AuthScheme {}
+
+impl PartialSchema for ImageSize {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some("Image dimensions in pixels, e.g. `1024x1024`."))
+            .pattern(Some(r"^\s*\d+\s*[xX]\s*\d+\s*$"))
+            .into()
+    }
+}
+
+impl ToSchema for ImageSize {}
+
+impl PartialSchema for MarkupOverlay {
+    fn schema() -> RefOr<Schema> {
+        let plan = Ref::from_schema_name(PlanMarkup::name());
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .description(Some("Markup adjustments by customer plan ID."))
+            .additional_properties(Some(AdditionalProperties::RefOr(plan.into())))
+            .into()
+    }
+}
+
+impl ToSchema for MarkupOverlay {
+    fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+        schemas.push((PlanMarkup::name().into_owned(), PlanMarkup::schema()));
+        PlanMarkup::schemas(schemas);
+    }
+
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("MarkupOverlay")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AiPricingJson;
+    use serde_json::json;
+
+    fn to_json(schema: RefOr<Schema>) -> serde_json::Value {
+        serde_json::to_value(schema).unwrap()
+    }
+
+    #[test]
+    fn test_string_schemas() {
+        assert_eq!(to_json(ModelType::schema())["type"], "string");
+        assert_eq!(to_json(ImageSize::schema())["type"], "string");
+        assert_eq!(
+            to_json(MarkupOverlay::schema())["additionalProperties"],
+            json!({ "$ref": "#/components/schemas/PlanMarkup" })
+        );
+    }
+
+    #[test]
+    fn test_component_schemas() {
+        let mut schemas = Vec::new();
+        AiPricingJson::schemas(&mut schemas);
+        let names: Vec<&str> = schemas.iter().map(|(name, _)| name.as_str()).collect();
+        for expected in [
+            "Provider",
+            "Model",
+            "TextPricing",
+            "PlanMarkup",
+            "ModelType",
+        ] {
+            assert!(names.contains(&expected), "{} not in {:?}", expected, names);
+        }
+
+        let model = to_json(crate::Model::schema());
+        assert_eq!(
+            model["properties"]["type"],
+            json!({ "$ref": "#/components/schemas/ModelType" })
+        );
+    }
+}
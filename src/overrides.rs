@@ -0,0 +1,124 @@
+use crate::{Env, PricingError};
+use std::time::Duration;
+
+/// Full pricing URL to fetch, for clients configured with neither a URL, a
+/// base URL, nor an environment, including the default clients behind
+/// [`get_ai_pricing`](crate::get_ai_pricing).
+pub const URL_VAR: &str = "AI_PRICING_URL";
+/// Environment to fetch, parsed with [`Env`]'s `FromStr`, for clients
+/// configured without one. The default clients always fetch the environment
+/// they were asked for.
+pub const ENV_VAR: &str = "AI_PRICING_ENV";
+/// Cache TTL in whole seconds.
+pub const TTL_SECS_VAR: &str = "AI_PRICING_TTL_SECS";
+
+// ------------------
+// Environment variable overrides
+// ------------------
+
+/// Defaults read from the process environment. Each one only fills in a
+/// setting the caller left unset; anything configured in code wins. Unset and
+/// empty variables are ignored, and values are only parsed when used, so a
+/// malformed variable that something in code overrides is never an error.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Overrides {
+    pub(crate) url: Option<String>,
+    pub(crate) env: Option<String>,
+    pub(crate) ttl: Option<String>,
+}
+
+impl Overrides {
+    pub(crate) fn from_process() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |name| {
+            lookup(name)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Overrides {
+            url: var(URL_VAR),
+            env: var(ENV_VAR),
+            ttl: var(TTL_SECS_VAR),
+        }
+    }
+
+    /// [`ENV_VAR`], parsed.
+    pub(crate) fn env(&self) -> Result<Option<Env>, PricingError> {
+        self.env
+            .as_ref()
+            .map(|value| {
+                value.parse().map_err(|err| PricingError::Config {
+                    var: ENV_VAR,
+                    value: value.clone(),
+                    reason: format!("{err}"),
+                })
+            })
+            .transpose()
+    }
+
+    /// [`TTL_SECS_VAR`], parsed.
+    pub(crate) fn ttl(&self) -> Result<Option<Duration>, PricingError> {
+        self.ttl
+            .as_ref()
+            .map(|value| {
+                value
+                    .parse()
+                    .map(Duration::from_secs)
+                    .map_err(|err| PricingError::Config {
+                        var: TTL_SECS_VAR,
+                        value: value.clone(),
+                        reason: format!("{err}"),
+                    })
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn overrides(vars: &[(&str, &str)]) -> Overrides {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        Overrides::from_lookup(|name| vars.get(name).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn test_reads_overrides() {
+        assert_eq!(overrides(&[]), Overrides::default());
+        let read = overrides(&[
+            (URL_VAR, "https://mirror.example.com/pricing.json"),
+            (ENV_VAR, "staging"),
+            (TTL_SECS_VAR, " 300 "),
+        ]);
+        assert_eq!(
+            read.url.as_deref(),
+            Some("https://mirror.example.com/pricing.json")
+        );
+        assert_eq!(read.env().unwrap(), Some(Env::Staging));
+        assert_eq!(read.ttl().unwrap(), Some(Duration::from_secs(300)));
+        assert_eq!(
+            overrides(&[(URL_VAR, ""), (ENV_VAR, "  ")]),
+            Overrides::default()
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_values() {
+        let err = overrides(&[(ENV_VAR, "pord")]).env().unwrap_err();
+        assert!(matches!(err, PricingError::Config { var: ENV_VAR, .. }));
+
+        let err = overrides(&[(TTL_SECS_VAR, "5m")]).ttl().unwrap_err();
+        assert!(matches!(
+            err,
+            PricingError::Config {
+                var: TTL_SECS_VAR,
+                ..
+            }
+        ));
+    }
+}
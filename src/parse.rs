@@ -0,0 +1,107 @@
+use crate::{AiPricingJson, PricingError};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::path::Path;
+
+// ------------------
+// Parsing
+// ------------------
+
+impl AiPricingJson {
+    /// Read and deserialize a pricing file from disk, e.g. a candidate file
+    /// being authored or staged for publishing.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<AiPricingJson, PricingError> {
+        let path = path.as_ref();
+        let body = std::fs::read(path).map_err(|source| PricingError::Io {
+            source,
+            path: path.to_path_buf(),
+        })?;
+        parse_file(&body, path)
+    }
+}
+
+/// Deserialize a pricing file read from `path`, picking the format from its
+/// extension. Anything but `.yaml`/`.yml` or `.toml` is read as JSON.
+pub(crate) fn parse_file(body: &[u8], path: &Path) -> Result<AiPricingJson, PricingError> {
+    let origin = path.display().to_string();
+    match path.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "yaml")]
+        Some("yaml" | "yml") => crate::yaml::parse_yaml(body, &origin),
+        #[cfg(feature = "toml")]
+        Some("toml") => crate::toml::parse_toml(body, &origin),
+        _ => parse(body, &origin),
+    }
+}
+
+/// Deserialize pricing JSON of any supported schema version, attributing
+/// failures to `origin`.
+pub(crate) fn parse(body: &[u8], origin: &str) -> Result<AiPricingJson, PricingError> {
+    let deserialize_error = |source| PricingError::Deserialize {
+        source,
+        url: origin.to_string(),
+    };
+    let value = serde_json::from_slice(body).map_err(deserialize_error)?;
+    parse_value(value, origin)
+}
+
+/// [`AiPricingJson::from_versioned_value`], attributing failures to `origin`.
+pub(crate) fn parse_value(value: Value, origin: &str) -> Result<AiPricingJson, PricingError> {
+    AiPricingJson::from_versioned_value(value).map_err(|err| match err {
+        PricingError::Deserialize { source, .. } => PricingError::Deserialize {
+            source,
+            url: origin.to_string(),
+        },
+        err => err,
+    })
+}
+
+/// `serde_json::from_value`, naming where in the document deserialization
+/// failed, e.g. `providers[2].models[7].pricing.outputPer1M: invalid type`.
+pub(crate) fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, serde_json::Error> {
+    serde_path_to_error::deserialize(value).map_err(|err| {
+        let path = err.path().to_string();
+        let source = err.into_inner();
+        match path.as_str() {
+            "." => source,
+            _ => serde::de::Error::custom(format!("{}: {}", path, source)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::PRICING_JSON;
+
+    #[test]
+    fn test_from_path() {
+        let path =
+            std::env::temp_dir().join(format!("ai-pricing-source-{}.json", std::process::id()));
+        std::fs::write(&path, PRICING_JSON).unwrap();
+
+        let pricing = AiPricingJson::from_path(&path).expect("Failed to read pricing");
+        assert_eq!(pricing.providers.len(), 2);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            AiPricingJson::from_path(&path),
+            Err(PricingError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_errors_name_the_failing_field() {
+        let mut json: Value = serde_json::from_str(PRICING_JSON).unwrap();
+        json["providers"][1]["models"][0]["pricing"]["outputPer1M"] = true.into();
+        let body = serde_json::to_vec(&json).unwrap();
+
+        let err = parse(&body, "test").unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "providers[1].models[0].pricing.outputPer1M: invalid type: boolean `true`"
+            ),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::merge::counterpart;
+use crate::{merge_patch, AiPricingJson, PricingError};
+use serde::Deserialize;
+use serde_json::Value;
+
+// ------------------
+// Patching
+// ------------------
+
+impl AiPricingJson {
+    /// Apply a small correction in place, without shipping a whole new file.
+    ///
+    /// An object is applied as a merge patch with the semantics of
+    /// [`merge`](Self::merge). An array is applied as a JSON Patch (RFC 6902)
+    /// of `add`, `remove`, `replace`, `move`, `copy`, and `test` operations,
+    /// where `add` or `replace` of one half of a per-1K/per-1M price pair
+    /// re-derives the other.
+    ///
+    /// The patch applies atomically: on error, `self` is left unchanged.
+    pub fn apply_patch(&mut self, patch: Value) -> Result<(), PricingError> {
+        let mut document = serde_json::to_value(&*self).map_err(deserialize_error)?;
+        match patch {
+            Value::Object(_) => merge_patch(&mut document, &patch),
+            Value::Array(operations) => {
+                for (index, operation) in operations.into_iter().enumerate() {
+                    let fail = |reason: String| PricingError::Patch { index, reason };
+                    let operation: Operation =
+                        serde_json::from_value(operation).map_err(|e| fail(e.to_string()))?;
+                    operation.apply(&mut document).map_err(fail)?;
+                }
+            }
+            other => {
+                return Err(PricingError::Patch {
+                    index: 0,
+                    reason: format!("expected an object or array, got {}", other),
+                })
+            }
+        }
+        *self = crate::parse::from_value(document).map_err(deserialize_error)?;
+        Ok(())
+    }
+}
+
+fn deserialize_error(source: serde_json::Error) -> PricingError {
+    PricingError::Deserialize {
+        source,
+        url: "patched pricing".to_string(),
+    }
+}
+
+/// One RFC 6902 operation.
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Operation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+impl Operation {
+    fn apply(self, document: &mut Value) -> Result<(), String> {
+        match self {
+            Operation::Add { path, value } => add(document, &path, value),
+            Operation::Remove { path } => remove(document, &path).map(drop),
+            Operation::Replace { path, value } => {
+                remove(document, &path)?;
+                add(document, &path, value)
+            }
+            Operation::Move { from, path } => {
+                if path.starts_with(&format!("{}/", from)) {
+                    return Err(format!("cannot move {} into its own child {}", from, path));
+                }
+                let value = remove(document, &from)?;
+                add(document, &path, value)
+            }
+            Operation::Copy { from, path } => {
+                let value = document
+                    .pointer(&from)
+                    .cloned()
+                    .ok_or_else(|| format!("no value at {}", from))?;
+                add(document, &path, value)
+            }
+            Operation::Test { path, value } => match document.pointer(&path) {
+                Some(actual) if *actual == value => Ok(()),
+                Some(actual) => Err(format!("{} is {}, expected {}", path, actual, value)),
+                None => Err(format!("no value at {}", path)),
+            },
+        }
+    }
+}
+
+/// Split a JSON pointer into its parent pointer and unescaped last token.
+fn split(path: &str) -> Result<(&str, String), String> {
+    let (parent, last) = path
+        .rsplit_once('/')
+        .ok_or_else(|| format!("invalid JSON pointer {:?}", path))?;
+    Ok((parent, last.replace("~1", "/").replace("~0", "~")))
+}
+
+fn parent<'a>(document: &'a mut Value, pointer: &str) -> Result<&'a mut Value, String> {
+    document
+        .pointer_mut(pointer)
+        .ok_or_else(|| format!("no value at {}", pointer))
+}
+
+fn index(token: &str, len: usize) -> Result<usize, String> {
+    token
+        .parse()
+        .ok()
+        .filter(|i| *i < len)
+        .ok_or_else(|| format!("array index {} out of bounds", token))
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> Result<(), String> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+    let (pointer, token) = split(path)?;
+    match parent(document, pointer)? {
+        Value::Object(object) => {
+            if let Some(counterpart) = counterpart(&token) {
+                object.remove(&counterpart);
+            }
+            object.insert(token, value);
+        }
+        Value::Array(array) if token == "-" => array.push(value),
+        Value::Array(array) => {
+            let i = index(&token, array.len() + 1)?;
+            array.insert(i, value);
+        }
+        _ => return Err(format!("{} is not a container", pointer)),
+    }
+    Ok(())
+}
+
+fn remove(document: &mut Value, path: &str) -> Result<Value, String> {
+    let (pointer, token) = split(path)?;
+    match parent(document, pointer)? {
+        Value::Object(object) => object
+            .remove(&token)
+            .ok_or_else(|| format!("no value at {}", path)),
+        Value::Array(array) => {
+            let i = index(&token, array.len())?;
+            Ok(array.remove(i))
+        }
+        _ => Err(format!("{} is not a container", pointer)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::Pricing;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_json_patch() {
+        let mut pricing = pricing();
+        pricing
+            .apply_patch(json!([
+                { "op": "test", "path": "/providers/0/models/0/key", "value": "gpt-4o" },
+                { "op": "replace", "path": "/providers/0/models/0/pricing/outputPer1M", "value": 8.0 },
+                { "op": "remove", "path": "/providers/1/models/1" },
+                { "op": "copy", "from": "/meteredPriceId", "path": "/providers/0/models/0/prodPriceIds/cachedInput" }
+            ]))
+            .expect("Failed to apply patch");
+
+        let gpt = pricing.find_model("openai", "gpt-4o").unwrap();
+        let Some(Pricing::TextPricing(text)) = &gpt.pricing else {
+            panic!("gpt-4o should have text pricing");
+        };
+        assert_eq!(text.output_per1_m, 8.0);
+        assert_eq!(text.output_per1_k, 0.008);
+        let ids = gpt.prod_price_ids.as_ref().unwrap();
+        assert_eq!(ids.cached_input.as_deref(), Some("price_metered"));
+        assert_eq!(pricing.providers[1].models.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_merge_patch() {
+        let mut pricing = pricing();
+        pricing
+            .apply_patch(json!({ "meteredPriceId": "price_fixed" }))
+            .unwrap();
+        assert_eq!(pricing.metered_price_id, "price_fixed");
+    }
+
+    #[test]
+    fn test_failed_patch_is_atomic() {
+        let mut patched = pricing();
+        let err = patched
+            .apply_patch(json!([
+                { "op": "replace", "path": "/meteredPriceId", "value": "price_new" },
+                { "op": "test", "path": "/providers/0/key", "value": "bedrock" }
+            ]))
+            .unwrap_err();
+        assert!(matches!(err, PricingError::Patch { index: 1, .. }));
+        assert_eq!(patched.metered_price_id, "price_metered");
+    }
+}
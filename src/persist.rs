@@ -0,0 +1,77 @@
+use crate::AiPricingJson;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+// ------------------
+// On-disk snapshot
+// ------------------
+
+/// Write a pricing snapshot to `path`, replacing any previous one.
+///
+/// The snapshot is written to a sibling temp file first and renamed into
+/// place, so a crash mid-write never leaves a truncated file behind.
+pub(crate) async fn write_snapshot(path: &Path, pricing: &AiPricingJson) -> io::Result<()> {
+    let body = serde_json::to_vec(pricing)?;
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&tmp, body).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+/// Read the snapshot at `path` along with its age, unless it is older than
+/// `max_age`. Returns `Ok(None)` when there is no usable snapshot.
+pub(crate) async fn read_snapshot(
+    path: &Path,
+    max_age: Option<Duration>,
+) -> io::Result<Option<(AiPricingJson, Duration)>> {
+    let modified = match tokio::fs::metadata(path).await {
+        Ok(metadata) => metadata.modified()?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    // A modification time in the future is treated as brand new.
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    if max_age.is_some_and(|max_age| age > max_age) {
+        return Ok(None);
+    }
+
+    let body = tokio::fs::read(path).await?;
+    let pricing = serde_json::from_slice(&body)?;
+    Ok(Some((pricing, age)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let dir = std::env::temp_dir().join(format!("ai-pricing-persist-{}", std::process::id()));
+        let path = dir.join("pricing.json");
+
+        assert!(read_snapshot(&path, None).await.unwrap().is_none());
+
+        write_snapshot(&path, &pricing()).await.unwrap();
+        let (read, age) = read_snapshot(&path, Some(Duration::from_secs(60)))
+            .await
+            .unwrap()
+            .expect("snapshot should be usable");
+        assert_eq!(read.metered_price_id, "price_metered");
+        assert!(age < Duration::from_secs(60));
+
+        assert!(read_snapshot(&path, Some(Duration::ZERO))
+            .await
+            .unwrap()
+            .is_none());
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
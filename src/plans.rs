@@ -0,0 +1,135 @@
+use crate::{
+    effective_markup, AiPricingJson, CostBreakdown, Markup, MarkupOverlay, Modality, PlanMarkup,
+    PricingError, TokenUsage,
+};
+
+// ------------------
+// Plan markup
+// ------------------
+
+impl PlanMarkup {
+    /// `markup` adjusted for this plan. Percentages are floored at zero, so a
+    /// discount never prices below provider cost.
+    pub fn apply_to(&self, markup: &Markup) -> Markup {
+        Markup {
+            image_percentage: (markup.image_percentage + self.image_adjustment).max(0.0),
+            text_percentage: (markup.text_percentage + self.text_adjustment).max(0.0),
+        }
+    }
+}
+
+impl MarkupOverlay {
+    pub fn is_empty(&self) -> bool {
+        self.plans.is_empty()
+    }
+
+    /// `markup` adjusted for `plan`. Fails if the plan isn't listed.
+    pub fn markup_for(&self, plan: &str, markup: &Markup) -> Result<Markup, PricingError> {
+        let adjustment = self
+            .plans
+            .get(plan)
+            .ok_or_else(|| PricingError::Validation(format!("no markup for plan {}", plan)))?;
+        Ok(adjustment.apply_to(markup))
+    }
+}
+
+impl AiPricingJson {
+    /// Provider cost and customer price of a text request by a customer on
+    /// `plan`: the model's cost for `usage`, as for
+    /// [`Model::cost_for_usage`](crate::Model::cost_for_usage), marked up by
+    /// its [`effective_markup`] adjusted for the plan. Promotions running now
+    /// are taken off the customer price, which is then rounded with
+    /// [`rounding`](AiPricingJson::rounding).
+    pub fn cost_for_plan(
+        &self,
+        plan: &str,
+        provider_key: &str,
+        model_key: &str,
+        usage: &TokenUsage,
+    ) -> Result<CostBreakdown, PricingError> {
+        let provider = self
+            .find_provider(provider_key)
+            .ok_or_else(|| PricingError::Validation(format!("no provider {}", provider_key)))?;
+        let model = provider.find_model(model_key).ok_or_else(|| {
+            PricingError::Validation(format!(
+                "provider {} has no model {}",
+                provider_key, model_key
+            ))
+        })?;
+
+        let cost = model.cost_for_usage(usage)?;
+        let markup = self
+            .markup_overlay
+            .markup_for(plan, &effective_markup(provider, model))?;
+        let breakdown = markup.apply(cost.total, Modality::Text);
+        Ok(self.billed(model, breakdown))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use serde_json::json;
+
+    fn with_plans() -> AiPricingJson {
+        let mut pricing = pricing();
+        pricing.markup_overlay = serde_json::from_value(json!({
+            "free": { "textAdjustment": 10.0 },
+            "pro": {},
+            "enterprise": { "textAdjustment": "-30", "imageAdjustment": -10.0 }
+        }))
+        .expect("Failed to parse");
+        pricing
+    }
+
+    #[test]
+    fn test_cost_for_plan() {
+        let pricing = with_plans();
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+        let cost = |plan| pricing.cost_for_plan(plan, "openai", "gpt-4o", &usage);
+
+        assert!((cost("free").unwrap().customer_price - 2.5 * 1.3).abs() < 1e-12);
+        assert!((cost("pro").unwrap().customer_price - 2.5 * 1.2).abs() < 1e-12);
+        // The discount is larger than the markup, so the price is floored at cost.
+        assert_eq!(cost("enterprise").unwrap().customer_price, 2.5);
+        assert!(cost("team").is_err());
+        assert!(pricing
+            .cost_for_plan("pro", "openai", "gpt-5", &usage)
+            .is_err());
+    }
+
+    #[test]
+    fn test_overlay_round_trips() {
+        let json = serde_json::to_value(with_plans()).unwrap();
+        assert_eq!(json["markupOverlay"]["enterprise"]["textAdjustment"], -30.0);
+        assert_eq!(json["markupOverlay"]["pro"]["textAdjustment"], 0.0);
+
+        let json = serde_json::to_value(pricing()).unwrap();
+        assert!(json.get("markupOverlay").is_none());
+    }
+
+    #[test]
+    fn test_cost_for_plan_applies_promotions() {
+        let mut pricing = with_plans();
+        pricing.promotions = serde_json::from_value(json!([{
+            "id": "half-off",
+            "discount": { "type": "percentage", "value": 50.0 },
+            "starts": "2000-01-01"
+        }]))
+        .expect("Failed to parse");
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+
+        let cost = pricing
+            .cost_for_plan("pro", "openai", "gpt-4o", &usage)
+            .unwrap();
+        assert_eq!(cost.provider_cost, 2.5);
+        assert!((cost.customer_price - 1.5).abs() < 1e-12);
+    }
+}
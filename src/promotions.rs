@@ -0,0 +1,281 @@
+use crate::{AiPricingJson, CostBreakdown, Discount, Model, Promotion};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+// ------------------
+// Promotions
+// ------------------
+
+impl Discount {
+    /// `price` with the discount taken off, floored at zero.
+    pub fn apply(&self, price: f64) -> f64 {
+        let discounted = match *self {
+            Discount::Percentage(percentage) => price * (1.0 - percentage / 100.0),
+            Discount::Fixed(amount) => price - amount,
+        };
+        discounted.max(0.0)
+    }
+}
+
+impl Promotion {
+    /// Whether the promotion covers `model`, by key or alias.
+    pub fn applies_to(&self, model: &Model) -> bool {
+        self.model_keys.is_empty() || self.model_keys.iter().any(|key| model.answers_to(key))
+    }
+
+    /// Whether the promotion is running at `at`, e.g. a `SystemTime` or, with
+    /// the `chrono` feature, a `DateTime<Utc>`. A promotion with a date
+    /// [`promotion_time`] doesn't understand is never running.
+    pub fn is_active_at(&self, at: impl Into<SystemTime>) -> bool {
+        let at = at.into();
+        let started = match &self.starts {
+            Some(starts) => promotion_time(starts).is_some_and(|starts| starts <= at),
+            None => true,
+        };
+        let ended = match &self.ends {
+            Some(ends) => promotion_time(ends).is_none_or(|ends| ends <= at),
+            None => false,
+        };
+        started && !ended
+    }
+}
+
+impl AiPricingJson {
+    /// Promotions running now, in file order.
+    pub fn active_promotions(&self) -> Vec<&Promotion> {
+        self.active_promotions_at(SystemTime::now())
+    }
+
+    /// Promotions running at `at`, in file order.
+    pub fn active_promotions_at(&self, at: impl Into<SystemTime>) -> Vec<&Promotion> {
+        let at = at.into();
+        self.promotions
+            .iter()
+            .filter(|promotion| promotion.is_active_at(at))
+            .collect()
+    }
+
+    /// `breakdown`, a charge for `model`, with the customer price reduced by
+    /// every promotion covering the model at `at`, in file order.
+    pub fn apply_promotions(
+        &self,
+        model: &Model,
+        breakdown: CostBreakdown,
+        at: impl Into<SystemTime>,
+    ) -> CostBreakdown {
+        let customer_price = self
+            .active_promotions_at(at)
+            .into_iter()
+            .filter(|promotion| promotion.applies_to(model))
+            .fold(breakdown.customer_price, |price, promotion| {
+                promotion.discount.apply(price)
+            });
+        CostBreakdown::new(breakdown.provider_cost, customer_price)
+    }
+}
+
+// ------------------
+// Promotion dates
+// ------------------
+
+/// Parse a promotion date in the formats a model's `added` takes: date-only
+/// (`2024-12-01`, taken as midnight UTC) or RFC 3339
+/// (`2024-12-01T09:30:00Z`). Needs no `chrono`, so promotions apply in every
+/// build.
+pub(crate) fn promotion_time(date: &str) -> Option<SystemTime> {
+    let date = date.trim();
+    let (day, time) = match date.split_once(['T', 't', ' ']) {
+        Some((day, time)) => (day, Some(time)),
+        None => (date, None),
+    };
+    let days = days_since_epoch(day)?;
+    let (seconds, nanos) = match time {
+        Some(time) => time_of_day(time)?,
+        None => (0, 0),
+    };
+
+    let seconds = days * SECONDS_PER_DAY + seconds;
+    let whole = Duration::from_secs(seconds.unsigned_abs());
+    let time = match seconds < 0 {
+        true => UNIX_EPOCH.checked_sub(whole)?,
+        false => UNIX_EPOCH.checked_add(whole)?,
+    };
+    time.checked_add(Duration::from_nanos(nanos))
+}
+
+/// Days from 1970-01-01 to a `YYYY-MM-DD` date, which must exist.
+fn days_since_epoch(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = digits(parts.next()?, 4)?;
+    let month: i64 = digits(parts.next()?, 2)?;
+    let day: i64 = digits(parts.next()?, 2)?;
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+
+    // Howard Hinnant's days_from_civil, counting years from March so the
+    // leap day falls at the end.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+/// Seconds from midnight UTC and nanoseconds of an RFC 3339 time with its
+/// offset, e.g. `09:30:00.5+02:00`. Negative for times before midnight UTC.
+fn time_of_day(time: &str) -> Option<(i64, u64)> {
+    let (clock, offset) = match time.strip_suffix(['Z', 'z']) {
+        Some(clock) => (clock, 0),
+        None => {
+            let sign_at = time.rfind(['+', '-'])?;
+            let (clock, offset) = time.split_at(sign_at);
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            let seconds = digits::<i64>(hours, 2)? * 3_600 + digits::<i64>(minutes, 2)? * 60;
+            match offset.starts_with('-') {
+                true => (clock, -seconds),
+                false => (clock, seconds),
+            }
+        }
+    };
+    let (clock, fraction) = match clock.split_once('.') {
+        Some((clock, fraction)) => (clock, Some(fraction)),
+        None => (clock, None),
+    };
+    let mut fields = clock.splitn(3, ':');
+    let hours: i64 = digits(fields.next()?, 2)?;
+    let minutes: i64 = digits(fields.next()?, 2)?;
+    let seconds: i64 = digits(fields.next()?, 2)?;
+    if hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    let nanos = match fraction {
+        Some(fraction) if !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit()) => {
+            let padded = format!("{:0<9}", &fraction[..fraction.len().min(9)]);
+            padded.parse().ok()?
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+    Some((hours * 3_600 + minutes * 60 + seconds - offset, nanos))
+}
+
+/// `field` as a number, if it is exactly `len` ASCII digits.
+fn digits<T: std::str::FromStr>(field: &str, len: usize) -> Option<T> {
+    if field.len() != len || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use serde_json::json;
+
+    #[test]
+    fn test_discounts() {
+        assert_eq!(Discount::Percentage(25.0).apply(2.0), 1.5);
+        assert_eq!(Discount::Fixed(0.5).apply(2.0), 1.5);
+        assert_eq!(Discount::Fixed(5.0).apply(2.0), 0.0);
+
+        let promotion: Promotion = serde_json::from_value(json!({
+            "id": "launch",
+            "discount": { "type": "percentage", "value": 20.0 },
+            "modelKeys": ["gpt-4o"]
+        }))
+        .expect("Failed to parse");
+        let pricing = pricing();
+        assert!(promotion.applies_to(&pricing.providers[0].models[0]));
+        assert!(!promotion.applies_to(&pricing.providers[0].models[1]));
+    }
+
+    #[test]
+    fn test_promotion_time() {
+        let at = |seconds: u64| UNIX_EPOCH + Duration::from_secs(seconds);
+        assert_eq!(promotion_time("1970-01-01"), Some(UNIX_EPOCH));
+        assert_eq!(promotion_time("2024-05-13"), Some(at(1_715_558_400)));
+        assert_eq!(
+            promotion_time("2024-05-13T11:30:00+02:00"),
+            Some(at(1_715_592_600))
+        );
+        assert_eq!(
+            promotion_time("2024-05-13T00:00:00.5Z"),
+            Some(at(1_715_558_400) + Duration::from_millis(500))
+        );
+        assert_eq!(
+            promotion_time("1969-12-31"),
+            UNIX_EPOCH.checked_sub(Duration::from_secs(86_400))
+        );
+        assert!(promotion_time("2024-02-29").is_some());
+        assert_eq!(promotion_time("2023-02-29"), None);
+        assert_eq!(promotion_time("2024-05-13T24:00:00Z"), None);
+        assert_eq!(promotion_time("May 13, 2024"), None);
+
+        let promotion: Promotion = serde_json::from_value(json!({
+            "id": "december",
+            "discount": { "type": "fixed", "value": 0.5 },
+            "starts": "2024-12-01",
+            "ends": "2025-01-01"
+        }))
+        .expect("Failed to parse");
+        assert!(promotion.is_active_at(promotion_time("2024-12-31T23:59:59Z").unwrap()));
+        assert!(!promotion.is_active_at(promotion_time("2025-01-01").unwrap()));
+    }
+
+    #[test]
+    fn test_active_promotions() {
+        let mut pricing = pricing();
+        pricing.promotions = serde_json::from_value(json!([
+            {
+                "id": "december",
+                "discount": { "type": "fixed", "value": 0.5 },
+                "starts": "2024-12-01",
+                "ends": "2025-01-01"
+            },
+            {
+                "id": "launch",
+                "discount": { "type": "percentage", "value": 50.0 },
+                "modelKeys": ["claude-3-5-sonnet"]
+            }
+        ]))
+        .expect("Failed to parse");
+
+        let christmas = promotion_time("2024-12-25T12:00:00Z").unwrap();
+        let new_year = promotion_time("2025-01-01").unwrap();
+        let ids = |at| -> Vec<&str> {
+            pricing
+                .active_promotions_at(at)
+                .iter()
+                .map(|promotion| promotion.id.as_str())
+                .collect()
+        };
+        assert_eq!(ids(christmas), ["december", "launch"]);
+        assert_eq!(ids(new_year), ["launch"]);
+
+        let bedrock = &pricing.providers[1];
+        let sonnet = &bedrock.models[0];
+        let breakdown = bedrock.apply_model_markup(sonnet, 4.0, crate::Modality::Text);
+        let discounted = pricing.apply_promotions(sonnet, breakdown, christmas);
+        assert_eq!(discounted.provider_cost, 4.0);
+        assert_eq!(discounted.customer_price, 2.25);
+        let gpt = &pricing.providers[0].models[0];
+        assert_eq!(
+            pricing
+                .apply_promotions(gpt, breakdown, new_year)
+                .customer_price,
+            5.0
+        );
+    }
+}
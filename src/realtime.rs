@@ -0,0 +1,148 @@
+use crate::{Cost, Model, Pricing, PricingError, RealtimePricing, TokenUsage};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const SECONDS_PER_MINUTE: f64 = 60.0;
+
+// ------------------
+// Realtime sessions
+// ------------------
+
+/// What a realtime session used: audio streamed each way and any text tokens.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase", default)]
+pub struct RealtimeSession {
+    #[cfg_attr(feature = "ts-rs", ts(type = "{ secs: number, nanos: number }"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub audio_input: Duration,
+    #[cfg_attr(feature = "ts-rs", ts(type = "{ secs: number, nanos: number }"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub audio_output: Duration,
+    pub text: TokenUsage,
+}
+
+/// The provider cost of a realtime session.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RealtimeCost {
+    pub audio_input_cost: f64,
+    pub audio_output_cost: f64,
+    pub text_cost: Cost,
+    pub total: f64,
+}
+
+impl RealtimePricing {
+    /// Provider cost of `session`, with audio billed to the fraction of a
+    /// minute and text as for
+    /// [`TextPricing::cost_for_usage`](crate::TextPricing::cost_for_usage).
+    pub fn session_cost(&self, session: &RealtimeSession) -> RealtimeCost {
+        let per_minute =
+            |duration: Duration, rate: f64| duration.as_secs_f64() / SECONDS_PER_MINUTE * rate;
+        let audio_input_cost = per_minute(session.audio_input, self.audio_input_per_minute);
+        let audio_output_cost = per_minute(session.audio_output, self.audio_output_per_minute);
+        let text_cost = self.text.cost_for_usage(&session.text);
+
+        RealtimeCost {
+            audio_input_cost,
+            audio_output_cost,
+            text_cost,
+            total: audio_input_cost + audio_output_cost + text_cost.total,
+        }
+    }
+}
+
+impl Model {
+    /// Provider cost of a realtime session with this model.
+    pub fn realtime_session_cost(
+        &self,
+        session: &RealtimeSession,
+    ) -> Result<RealtimeCost, PricingError> {
+        match &self.pricing {
+            Some(Pricing::RealtimePricing(realtime)) => Ok(realtime.session_cost(session)),
+            _ => Err(PricingError::Validation(format!(
+                "model {} has no realtime pricing",
+                self.key
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ModelType, ResolvedModel};
+    use serde_json::json;
+
+    fn realtime() -> Model {
+        serde_json::from_value(json!({
+            "added": "2024-10-01",
+            "created": "2024-10-01",
+            "key": "gpt-4o-realtime-preview",
+            "type": "realtime",
+            "pricing": {
+                "audioInputPerMinute": 0.06,
+                "audioOutputPerMinute": "0.24",
+                "inputPer1M": 5.0,
+                "outputPer1M": 20.0
+            }
+        }))
+        .expect("Failed to parse")
+    }
+
+    #[test]
+    fn test_realtime_pricing_deserializes() {
+        let model = realtime();
+        assert_eq!(model.model_type, ModelType::Realtime);
+        let Ok(ResolvedModel::Realtime(resolved)) = ResolvedModel::try_from(&model) else {
+            panic!("expected a realtime model");
+        };
+        assert_eq!(resolved.pricing.audio_output_per_minute, 0.24);
+        assert_eq!(resolved.pricing.text.input_per1_k, 0.005);
+        assert!(resolved.pricing.text.extra.is_empty());
+
+        let json = serde_json::to_value(&model).unwrap();
+        assert_eq!(json["pricing"]["audioInputPerMinute"], 0.06);
+        assert_eq!(json["pricing"]["outputPer1M"], 20.0);
+        let round_trip: Model = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            round_trip.pricing,
+            Some(Pricing::RealtimePricing(_))
+        ));
+    }
+
+    #[test]
+    fn test_session_cost() {
+        let session = RealtimeSession {
+            audio_input: Duration::from_secs(120),
+            audio_output: Duration::from_secs(30),
+            text: TokenUsage {
+                input_tokens: 1_000,
+                ..TokenUsage::default()
+            },
+        };
+        let cost = realtime().realtime_session_cost(&session).unwrap();
+        assert!((cost.audio_input_cost - 0.12).abs() < 1e-12);
+        assert!((cost.audio_output_cost - 0.12).abs() < 1e-12);
+        assert_eq!(cost.text_cost.input_cost, 0.005);
+        assert!((cost.total - 0.245).abs() < 1e-12);
+
+        let missing: Result<Model, _> = serde_json::from_value(json!({
+            "added": "2024-10-01",
+            "created": "2024-10-01",
+            "key": "broken",
+            "type": "realtime",
+            "pricing": { "audioInputPerMinute": 0.06, "inputPer1M": 5.0, "outputPer1M": 20.0 }
+        }));
+        assert!(missing
+            .unwrap_err()
+            .to_string()
+            .contains("audioOutputPerMinute"));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    Cost, CostBreakdown, Modality, Model, Pricing, PricingError, Provider, TextPricing, TokenUsage,
+};
+
+// ------------------
+// Regional pricing
+// ------------------
+
+impl Model {
+    /// Text prices in `region`, falling back to the model's default pricing
+    /// for regions without their own. `None` for models without text pricing.
+    pub fn pricing_for_region(&self, region: &str) -> Option<&TextPricing> {
+        self.regions.get(region).or(match &self.pricing {
+            Some(Pricing::TextPricing(pricing)) => Some(pricing),
+            _ => None,
+        })
+    }
+
+    /// [`Model::cost_for_usage`] at the prices in `region`.
+    pub fn cost_for_usage_in_region(
+        &self,
+        usage: &TokenUsage,
+        region: &str,
+    ) -> Result<Cost, PricingError> {
+        let pricing = self.pricing_for_region(region).ok_or_else(|| {
+            PricingError::Validation(format!("model {} has no text pricing", self.key))
+        })?;
+        self.cost_at(pricing, usage)
+    }
+}
+
+impl Provider {
+    /// Provider cost and marked-up customer price of a text request to one of
+    /// this provider's models in `region`.
+    #[deprecated(note = "ignores promotions; use `AiPricingJson::text_cost_in_region`")]
+    pub fn text_cost_in_region(
+        &self,
+        model_key: &str,
+        region: &str,
+        usage: &TokenUsage,
+    ) -> Result<CostBreakdown, PricingError> {
+        let model = self.find_model(model_key).ok_or_else(|| {
+            PricingError::Validation(format!("provider {} has no model {}", self.key, model_key))
+        })?;
+        let cost = model.cost_for_usage_in_region(usage, region)?;
+        Ok(self.apply_model_markup(model, cost.total, Modality::Text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use serde_json::json;
+
+    #[test]
+    fn test_pricing_for_region() {
+        let mut pricing = pricing();
+        pricing.providers[1].models[0].regions = serde_json::from_value(json!({
+            "eu-central-1": { "inputPer1M": 3.3, "outputPer1M": "16.5" }
+        }))
+        .expect("Failed to parse");
+        let bedrock = &pricing.providers[1];
+        let sonnet = &bedrock.models[0];
+
+        assert_eq!(
+            sonnet
+                .pricing_for_region("eu-central-1")
+                .unwrap()
+                .input_per1_m,
+            3.3
+        );
+        assert_eq!(
+            sonnet.pricing_for_region("us-east-1").unwrap().input_per1_m,
+            3.0
+        );
+        assert!(pricing.providers[0].models[1]
+            .pricing_for_region("us-east-1")
+            .is_none());
+
+        let usage = TokenUsage {
+            output_tokens: 1_000_000,
+            ..TokenUsage::default()
+        };
+        let cost = pricing
+            .text_cost_in_region("bedrock", "claude-3-5-sonnet", "eu-central-1", &usage)
+            .unwrap();
+        assert!((cost.provider_cost - 16.5).abs() < 1e-12);
+        let cost = pricing
+            .text_cost_in_region("bedrock", "claude-3-5-sonnet", "us-east-1", &usage)
+            .unwrap();
+        assert_eq!(cost.provider_cost, 15.0);
+
+        let json = serde_json::to_value(&bedrock.models[0]).unwrap();
+        assert_eq!(json["regions"]["eu-central-1"]["outputPer1K"], 0.0165);
+        let json = serde_json::to_value(&bedrock.models[1]).unwrap();
+        assert!(json.get("regions").is_none());
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    AudioPricing, Encoder, ImagePricing, Model, ModelType, Pricing, PricingError, RealtimePricing,
+    TextPricing, VideoPricing,
+};
+
+// ------------------
+// Resolved model views
+// ------------------
+
+/// A [`Model`] whose type and pricing shape have been checked against each
+/// other, so callers don't have to unwrap `Option`s or match on `Pricing`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResolvedModel<'a> {
+    Text(TextModel<'a>),
+    Image(ImageModel<'a>),
+    Audio(AudioModel<'a>),
+    Video(VideoModel<'a>),
+    Realtime(RealtimeModel<'a>),
+}
+
+/// A text model together with its per-token pricing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextModel<'a> {
+    pub model: &'a Model,
+    pub pricing: &'a TextPricing,
+}
+
+impl TextModel<'_> {
+    pub fn key(&self) -> &str {
+        &self.model.key
+    }
+
+    pub fn streaming(&self) -> bool {
+        self.model.streaming.unwrap_or(false)
+    }
+
+    pub fn encoder(&self) -> Option<&Encoder> {
+        self.model.encoder.as_ref()
+    }
+}
+
+/// An audio model together with its transcription and speech pricing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioModel<'a> {
+    pub model: &'a Model,
+    pub pricing: &'a AudioPricing,
+}
+
+impl AudioModel<'_> {
+    pub fn key(&self) -> &str {
+        &self.model.key
+    }
+}
+
+/// A video model together with its per-resolution pricing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoModel<'a> {
+    pub model: &'a Model,
+    pub pricing: &'a VideoPricing,
+}
+
+impl VideoModel<'_> {
+    pub fn key(&self) -> &str {
+        &self.model.key
+    }
+}
+
+/// A realtime model together with its audio and text pricing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RealtimeModel<'a> {
+    pub model: &'a Model,
+    pub pricing: &'a RealtimePricing,
+}
+
+impl RealtimeModel<'_> {
+    pub fn key(&self) -> &str {
+        &self.model.key
+    }
+}
+
+/// An image model together with its per-size pricing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageModel<'a> {
+    pub model: &'a Model,
+    pub pricing: &'a [ImagePricing],
+}
+
+impl ImageModel<'_> {
+    pub fn key(&self) -> &str {
+        &self.model.key
+    }
+}
+
+impl<'a> ResolvedModel<'a> {
+    /// The underlying model, whatever its type.
+    pub fn model(&self) -> &'a Model {
+        match self {
+            ResolvedModel::Text(text) => text.model,
+            ResolvedModel::Image(image) => image.model,
+            ResolvedModel::Audio(audio) => audio.model,
+            ResolvedModel::Video(video) => video.model,
+            ResolvedModel::Realtime(realtime) => realtime.model,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Model> for ResolvedModel<'a> {
+    type Error = PricingError;
+
+    fn try_from(model: &'a Model) -> Result<Self, Self::Error> {
+        match (&model.model_type, &model.pricing) {
+            (ModelType::Text, Some(Pricing::TextPricing(pricing))) => {
+                Ok(ResolvedModel::Text(TextModel { model, pricing }))
+            }
+            (ModelType::Image, Some(Pricing::ImagePricingVec(pricing))) => {
+                Ok(ResolvedModel::Image(ImageModel { model, pricing }))
+            }
+            (ModelType::Audio, Some(Pricing::AudioPricing(pricing))) => {
+                Ok(ResolvedModel::Audio(AudioModel { model, pricing }))
+            }
+            (ModelType::Video, Some(Pricing::VideoPricing(pricing))) => {
+                Ok(ResolvedModel::Video(VideoModel { model, pricing }))
+            }
+            (ModelType::Realtime, Some(Pricing::RealtimePricing(pricing))) => {
+                Ok(ResolvedModel::Realtime(RealtimeModel { model, pricing }))
+            }
+            (
+                ModelType::Text
+                | ModelType::Image
+                | ModelType::Audio
+                | ModelType::Video
+                | ModelType::Realtime,
+                Some(_),
+            ) => Err(PricingError::Validation(format!(
+                "model {} is of type {} but its pricing has a different shape",
+                model.key, model.model_type
+            ))),
+            (
+                ModelType::Text
+                | ModelType::Image
+                | ModelType::Audio
+                | ModelType::Video
+                | ModelType::Realtime,
+                None,
+            ) => Err(PricingError::Validation(format!(
+                "model {} has no pricing",
+                model.key
+            ))),
+            (other, _) => Err(PricingError::Validation(format!(
+                "model {} has unsupported type {}",
+                model.key, other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_resolve_models() {
+        let pricing = pricing();
+
+        let gpt = pricing.find_model("openai", "gpt-4o").unwrap();
+        match ResolvedModel::try_from(gpt).unwrap() {
+            ResolvedModel::Text(text) => {
+                assert!(text.streaming());
+                assert_eq!(text.pricing.input_per1_m, 2.5);
+            }
+            other => panic!("expected a text model, got {:?}", other),
+        }
+
+        let dalle = pricing.find_model("openai", "dall-e-3").unwrap();
+        match ResolvedModel::try_from(dalle).unwrap() {
+            ResolvedModel::Image(image) => assert_eq!(image.pricing.len(), 2),
+            other => panic!("expected an image model, got {:?}", other),
+        }
+
+        let mut mismatched = gpt.clone();
+        mismatched.model_type = ModelType::Image;
+        assert!(matches!(
+            ResolvedModel::try_from(&mismatched),
+            Err(PricingError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::PricingError;
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+// ------------------
+// Retry policy
+// ------------------
+
+/// How to retry a failed pricing fetch.
+///
+/// Only transient failures are retried: connection errors, timeouts, 429s and
+/// 5xx responses. Each retry waits roughly twice as long as the previous one,
+/// capped at `max_backoff`, with random jitter so a fleet of services doesn't
+/// retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; `0` disables retrying.
+    pub max_retries: u32,
+    /// Backoff before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound on the backoff between retries.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_retries: 0,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+    };
+
+    /// Retry up to `max_retries` times, starting at 200ms and capped at 5s.
+    pub fn exponential(max_retries: u32) -> Self {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+
+    /// The backoff before retry number `retry` (starting at 0), before jitter.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Run `attempt` until it succeeds, fails permanently, or retries run out.
+    pub(crate) async fn run<T, F, Fut>(&self, mut attempt: F) -> Result<T, PricingError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, PricingError>>,
+    {
+        let mut retry = 0;
+        loop {
+            match attempt().await {
+                Err(err) if err.is_transient() && retry < self.max_retries => {
+                    tokio::time::sleep(jitter(self.backoff(retry))).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::NONE
+    }
+}
+
+/// Pick a random duration between half of `backoff` and all of it.
+fn jitter(backoff: Duration) -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    let fraction = 0.5 + (random as f64 / u64::MAX as f64) / 2.0;
+    backoff.mul_f64(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::exponential(10);
+        assert_eq!(policy.backoff(0), Duration::from_millis(200));
+        assert_eq!(policy.backoff(1), Duration::from_millis(400));
+        assert_eq!(policy.backoff(2), Duration::from_millis(800));
+        assert_eq!(policy.backoff(9), Duration::from_secs(5));
+
+        let jittered = jitter(Duration::from_millis(200));
+        assert!(jittered >= Duration::from_millis(100));
+        assert!(jittered <= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors_only() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        };
+        let http_error = |status| PricingError::Http {
+            status,
+            url: "http://localhost/ai-pricing.json".to_string(),
+        };
+
+        let mut attempts = 0;
+        let result = policy
+            .run(|| {
+                attempts += 1;
+                let result = if attempts < 3 {
+                    Err(http_error(StatusCode::BAD_GATEWAY))
+                } else {
+                    Ok(attempts)
+                };
+                async move { result }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 3);
+
+        let mut attempts = 0;
+        let result: Result<(), _> = policy
+            .run(|| {
+                attempts += 1;
+                let err = http_error(StatusCode::NOT_FOUND);
+                async move { Err(err) }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
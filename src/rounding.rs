@@ -0,0 +1,189 @@
+#[cfg(feature = "decimal")]
+use crate::{to_decimal, DecimalCost};
+use crate::{Cost, CostBreakdown};
+#[cfg(feature = "decimal")]
+use rust_decimal::{prelude::ToPrimitive, Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+// ------------------
+// Rounding policy
+// ------------------
+
+/// How computed amounts are rounded, so charges match the invoicing system
+/// rather than each consumer rounding its own way.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub enum RoundingPolicy {
+    /// Leave amounts unrounded.
+    #[default]
+    Exact,
+    /// To the nearest cent, ties to even ("banker's rounding").
+    HalfEven,
+    /// Up to the next whole cent.
+    CeilToCent,
+    /// Down to the whole cent.
+    FloorToCent,
+    /// To six decimal places, i.e. the nearest micro-dollar (a ten-thousandth
+    /// of a cent), ties to even.
+    MicroCents,
+}
+
+impl RoundingPolicy {
+    pub fn is_exact(&self) -> bool {
+        *self == RoundingPolicy::Exact
+    }
+
+    /// Round a dollar amount.
+    pub fn round(self, amount: f64) -> f64 {
+        #[cfg(feature = "decimal")]
+        {
+            self.round_decimal(to_decimal(amount))
+                .to_f64()
+                .unwrap_or(amount)
+        }
+        #[cfg(not(feature = "decimal"))]
+        {
+            let (places, round): (i32, fn(f64) -> f64) = match self {
+                RoundingPolicy::Exact => return amount,
+                RoundingPolicy::HalfEven => (2, f64::round_ties_even),
+                RoundingPolicy::CeilToCent => (2, f64::ceil),
+                RoundingPolicy::FloorToCent => (2, f64::floor),
+                RoundingPolicy::MicroCents => (6, f64::round_ties_even),
+            };
+            let scale = 10f64.powi(places);
+            // Clear float noise below the last place first, so 0.07 dollars
+            // ceils to 7 cents rather than 8.
+            let scaled = (amount * scale * 1e6).round() / 1e6;
+            round(scaled) / scale
+        }
+    }
+
+    /// Round a decimal dollar amount.
+    #[cfg(feature = "decimal")]
+    pub fn round_decimal(self, amount: Decimal) -> Decimal {
+        let (places, strategy) = match self {
+            RoundingPolicy::Exact => return amount,
+            RoundingPolicy::HalfEven => (2, RoundingStrategy::MidpointNearestEven),
+            RoundingPolicy::CeilToCent => (2, RoundingStrategy::ToPositiveInfinity),
+            RoundingPolicy::FloorToCent => (2, RoundingStrategy::ToNegativeInfinity),
+            RoundingPolicy::MicroCents => (6, RoundingStrategy::MidpointNearestEven),
+        };
+        amount.round_dp_with_strategy(places, strategy)
+    }
+}
+
+impl Cost {
+    /// Round each component with `policy`. The total is the sum of the
+    /// rounded components, so it always reconciles with them.
+    pub fn rounded(&self, policy: RoundingPolicy) -> Cost {
+        let input_cost = policy.round(self.input_cost);
+        let cached_input_cost = policy.round(self.cached_input_cost);
+        let output_cost = policy.round(self.output_cost);
+        let reasoning_output_cost = policy.round(self.reasoning_output_cost);
+        let cache_write_cost = policy.round(self.cache_write_cost);
+        let request_cost = policy.round(self.request_cost);
+        let tool_cost = policy.round(self.tool_cost);
+
+        Cost {
+            input_cost,
+            cached_input_cost,
+            output_cost,
+            reasoning_output_cost,
+            cache_write_cost,
+            request_cost,
+            tool_cost,
+            total: policy.round(
+                input_cost
+                    + cached_input_cost
+                    + output_cost
+                    + reasoning_output_cost
+                    + cache_write_cost
+                    + request_cost
+                    + tool_cost,
+            ),
+        }
+    }
+}
+
+impl CostBreakdown {
+    /// Round the provider cost and customer price with `policy`, and derive
+    /// the markup and margin from the rounded amounts.
+    pub fn rounded(&self, policy: RoundingPolicy) -> CostBreakdown {
+        CostBreakdown::new(
+            policy.round(self.provider_cost),
+            policy.round(self.customer_price),
+        )
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl DecimalCost {
+    /// [`Cost::rounded`], in decimal.
+    pub fn rounded(&self, policy: RoundingPolicy) -> DecimalCost {
+        let input_cost = policy.round_decimal(self.input_cost);
+        let cached_input_cost = policy.round_decimal(self.cached_input_cost);
+        let output_cost = policy.round_decimal(self.output_cost);
+        let reasoning_output_cost = policy.round_decimal(self.reasoning_output_cost);
+        let cache_write_cost = policy.round_decimal(self.cache_write_cost);
+        let request_cost = policy.round_decimal(self.request_cost);
+        let tool_cost = policy.round_decimal(self.tool_cost);
+
+        DecimalCost {
+            input_cost,
+            cached_input_cost,
+            output_cost,
+            reasoning_output_cost,
+            cache_write_cost,
+            request_cost,
+            tool_cost,
+            total: input_cost
+                + cached_input_cost
+                + output_cost
+                + reasoning_output_cost
+                + cache_write_cost
+                + request_cost
+                + tool_cost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round() {
+        assert_eq!(RoundingPolicy::Exact.round(0.123456789), 0.123456789);
+        assert_eq!(RoundingPolicy::HalfEven.round(0.125), 0.12);
+        assert_eq!(RoundingPolicy::HalfEven.round(0.135), 0.14);
+        assert_eq!(RoundingPolicy::CeilToCent.round(0.07), 0.07);
+        assert_eq!(RoundingPolicy::CeilToCent.round(0.0701), 0.08);
+        assert_eq!(RoundingPolicy::FloorToCent.round(0.0799), 0.07);
+        assert_eq!(RoundingPolicy::MicroCents.round(0.0000125), 0.000012);
+    }
+
+    #[test]
+    fn test_rounded_cost_total_reconciles() {
+        let cost = Cost {
+            input_cost: 0.004,
+            cached_input_cost: 0.0,
+            output_cost: 0.004,
+            reasoning_output_cost: 0.0,
+            total: 0.008,
+            ..Cost::default()
+        };
+        let rounded = cost.rounded(RoundingPolicy::CeilToCent);
+        assert_eq!(rounded.input_cost, 0.01);
+        assert_eq!(rounded.output_cost, 0.01);
+        assert_eq!(rounded.total, 0.02);
+
+        let breakdown = CostBreakdown::new(0.004, 0.0052).rounded(RoundingPolicy::HalfEven);
+        assert_eq!(breakdown.provider_cost, 0.0);
+        assert_eq!(breakdown.customer_price, 0.01);
+        assert_eq!(breakdown.markup_amount, 0.01);
+    }
+}
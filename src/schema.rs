@@ -0,0 +1,100 @@
+use crate::{AiPricingJson, PricingError};
+use serde_json::Value;
+
+// ------------------
+// Schema versions
+// ------------------
+
+/// The newest pricing file format this crate reads and writes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades from each older version, indexed from version 1: entry `i`
+/// rewrites a version `i + 1` file into version `i + 2`. A format change
+/// bumps [`SCHEMA_VERSION`] and appends its migration here.
+const MIGRATIONS: [fn(&mut Value); SCHEMA_VERSION as usize - 1] = [];
+
+pub(crate) fn default_schema_version() -> u32 {
+    1
+}
+
+impl AiPricingJson {
+    /// Deserialize pricing JSON of any supported schema version, migrating
+    /// older files to the current shape. Files without a `schemaVersion` are
+    /// version 1.
+    pub fn from_versioned_value(mut value: Value) -> Result<AiPricingJson, PricingError> {
+        let version = value
+            .get("schemaVersion")
+            .map_or(Some(default_schema_version()), |v| {
+                v.as_u64().and_then(|v| u32::try_from(v).ok())
+            })
+            .filter(|version| (1..=SCHEMA_VERSION).contains(version))
+            .ok_or_else(|| PricingError::UnsupportedSchemaVersion {
+                version: value["schemaVersion"].clone(),
+                supported: SCHEMA_VERSION,
+            })?;
+
+        for migration in &MIGRATIONS[version as usize - 1..] {
+            migration(&mut value);
+        }
+        if let Value::Object(object) = &mut value {
+            object.insert("schemaVersion".to_string(), SCHEMA_VERSION.into());
+        }
+        crate::parse::from_value(value).map_err(|source| PricingError::Deserialize {
+            source,
+            url: "versioned pricing".to_string(),
+        })
+    }
+
+    /// JSON Schema for pricing files, for validating hand-edited files and for
+    /// editor autocompletion. It describes the format
+    /// [`from_slice_strict`](Self::from_slice_strict) accepts, so numbers must
+    /// be JSON numbers.
+    #[cfg(feature = "schemars")]
+    pub fn json_schema() -> schemars::Schema {
+        schemars::schema_for!(AiPricingJson)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::PRICING_JSON;
+    use serde_json::json;
+
+    #[test]
+    fn test_unversioned_files_are_v1() {
+        let value: Value = serde_json::from_str(PRICING_JSON).unwrap();
+        assert!(value.get("schemaVersion").is_none());
+
+        let pricing = AiPricingJson::from_versioned_value(value).expect("Failed to read v1");
+        assert_eq!(pricing.schema_version, SCHEMA_VERSION);
+        let json = serde_json::to_value(&pricing).unwrap();
+        assert_eq!(json["schemaVersion"], SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_rejects_unknown_versions() {
+        for version in [json!(SCHEMA_VERSION + 1), json!(0), json!("2")] {
+            let mut value: Value = serde_json::from_str(PRICING_JSON).unwrap();
+            value["schemaVersion"] = version.clone();
+            let err = AiPricingJson::from_versioned_value(value).unwrap_err();
+            assert!(matches!(
+                err,
+                PricingError::UnsupportedSchemaVersion { version: v, .. } if v == version
+            ));
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(AiPricingJson::json_schema()).unwrap();
+        assert_eq!(schema["required"], json!(["meteredPriceId", "providers"]));
+        assert_eq!(
+            schema["properties"]["providers"]["items"]["$ref"],
+            "#/$defs/Provider"
+        );
+        assert!(schema["$defs"]["TextPricing"]["properties"]["inputPer1M"].is_object());
+        assert_eq!(schema["$defs"]["ModelType"]["type"], "string");
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{AiPricingJson, Model, ModelFeature, ModelType, Pricing, Provider, TextPricing};
+
+/// Input tokens assumed per output token when blending prices, unless the
+/// caller says otherwise.
+pub const DEFAULT_INPUT_OUTPUT_RATIO: f64 = 3.0;
+
+impl TextPricing {
+    /// Price per 1M tokens for a mix of `ratio` input tokens per output token,
+    /// e.g. `3.0` for a 3:1 mix.
+    pub fn blended_per1_m(&self, ratio: f64) -> f64 {
+        (self.input_per1_m * ratio + self.output_per1_m) / (ratio + 1.0)
+    }
+}
+
+// ------------------
+// Model limits
+// ------------------
+
+impl Model {
+    /// Whether a request with `input_tokens` of prompt, asking for up to
+    /// `output_tokens`, fits this model's context window and output limit.
+    /// Limits the model doesn't state are assumed to fit.
+    pub fn fits(&self, input_tokens: u64, output_tokens: u64) -> bool {
+        self.context_window
+            .is_none_or(|window| input_tokens.saturating_add(output_tokens) <= window)
+            && self
+                .max_output_tokens
+                .is_none_or(|max| output_tokens <= max)
+    }
+}
+
+// ------------------
+// Model selection
+// ------------------
+
+/// What a text model must support to be picked by
+/// [`AiPricingJson::cheapest_text_model`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelRequirements {
+    /// Input tokens per output token to blend prices at.
+    pub input_output_ratio: f64,
+    /// Features the model must all list, e.g. [`ModelFeature::Vision`].
+    pub features: Vec<ModelFeature>,
+    /// Whether the model must support streaming.
+    pub streaming: bool,
+}
+
+impl Default for ModelRequirements {
+    fn default() -> Self {
+        ModelRequirements {
+            input_output_ratio: DEFAULT_INPUT_OUTPUT_RATIO,
+            features: Vec::new(),
+            streaming: false,
+        }
+    }
+}
+
+impl ModelRequirements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn input_output_ratio(mut self, ratio: f64) -> Self {
+        self.input_output_ratio = ratio;
+        self
+    }
+
+    pub fn feature(mut self, feature: impl Into<ModelFeature>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// Whether `model` is [available](Model::is_available) and meets these
+    /// requirements. Pricing isn't considered.
+    pub fn matches(&self, model: &Model) -> bool {
+        model.is_available()
+            && (!self.streaming || model.streaming.unwrap_or(false))
+            && self.features.iter().all(|f| model.features.contains(f))
+    }
+}
+
+/// A text model ranked by [`AiPricingJson::rank_text_models`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankedModel<'a> {
+    pub provider: &'a Provider,
+    pub model: &'a Model,
+    /// Blended price per 1M tokens at the requested input:output ratio.
+    pub blended_per1_m: f64,
+}
+
+impl AiPricingJson {
+    /// Every available text model meeting `requirements`, cheapest first by
+    /// blended price.
+    pub fn rank_text_models(&self, requirements: &ModelRequirements) -> Vec<RankedModel<'_>> {
+        let mut ranked: Vec<RankedModel<'_>> = self
+            .all_models()
+            .filter(|(_, model)| requirements.matches(model))
+            .filter_map(|(provider, model)| match &model.pricing {
+                Some(Pricing::TextPricing(pricing)) => Some(RankedModel {
+                    provider,
+                    model,
+                    blended_per1_m: pricing.blended_per1_m(requirements.input_output_ratio),
+                }),
+                _ => None,
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.blended_per1_m.total_cmp(&b.blended_per1_m));
+        ranked
+    }
+
+    /// The cheapest available text model meeting `requirements`, if any.
+    pub fn cheapest_text_model(&self, requirements: &ModelRequirements) -> Option<RankedModel<'_>> {
+        self.rank_text_models(requirements).into_iter().next()
+    }
+}
+
+// ------------------
+// Model queries
+// ------------------
+
+/// Filters models by capability and price, for building routing and fallback
+/// policies. Every condition set must hold; deprecated, disabled, and retired
+/// models are skipped unless [`include_unavailable`](Self::include_unavailable) is set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelQuery {
+    provider: Option<String>,
+    model_type: Option<ModelType>,
+    features: Vec<ModelFeature>,
+    streaming: Option<bool>,
+    max_input_price_per_1m: Option<f64>,
+    max_output_price_per_1m: Option<f64>,
+    min_context_window: Option<u64>,
+    include_unavailable: bool,
+}
+
+impl ModelQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only models from the provider with this key.
+    pub fn provider(mut self, provider_key: impl Into<String>) -> Self {
+        self.provider = Some(provider_key.into());
+        self
+    }
+
+    pub fn model_type(mut self, model_type: ModelType) -> Self {
+        self.model_type = Some(model_type);
+        self
+    }
+
+    /// Only models listing `feature`. May be given more than once.
+    pub fn feature(mut self, feature: impl Into<ModelFeature>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    /// Only models that do (or don't) support streaming. Models that don't
+    /// say are treated as not streaming.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = Some(streaming);
+        self
+    }
+
+    /// Only text models whose input price is at most `price` per 1M tokens.
+    pub fn max_input_price_per_1m(mut self, price: f64) -> Self {
+        self.max_input_price_per_1m = Some(price);
+        self
+    }
+
+    /// Only text models whose output price is at most `price` per 1M tokens.
+    pub fn max_output_price_per_1m(mut self, price: f64) -> Self {
+        self.max_output_price_per_1m = Some(price);
+        self
+    }
+
+    /// Only models with a context window of at least `tokens`. Models that
+    /// don't state one don't match.
+    pub fn min_context_window(mut self, tokens: u64) -> Self {
+        self.min_context_window = Some(tokens);
+        self
+    }
+
+    /// Also match deprecated, disabled, and retired models.
+    pub fn include_unavailable(mut self, include: bool) -> Self {
+        self.include_unavailable = include;
+        self
+    }
+
+    /// Whether `model`, offered by `provider`, matches the query.
+    pub fn matches(&self, provider: &Provider, model: &Model) -> bool {
+        let text_pricing = match &model.pricing {
+            Some(Pricing::TextPricing(pricing)) => Some(pricing),
+            _ => None,
+        };
+        let within = |max: Option<f64>, price: fn(&TextPricing) -> f64| match max {
+            Some(max) => text_pricing.is_some_and(|pricing| price(pricing) <= max),
+            None => true,
+        };
+
+        (self.include_unavailable || model.is_available())
+            && self
+                .provider
+                .as_ref()
+                .is_none_or(|key| *key == provider.key)
+            && self
+                .model_type
+                .as_ref()
+                .is_none_or(|t| *t == model.model_type)
+            && self.features.iter().all(|f| model.features.contains(f))
+            && self
+                .streaming
+                .is_none_or(|streaming| model.streaming.unwrap_or(false) == streaming)
+            && within(self.max_input_price_per_1m, |p| p.input_per1_m)
+            && within(self.max_output_price_per_1m, |p| p.output_per1_m)
+            && self
+                .min_context_window
+                .is_none_or(|min| model.context_window.is_some_and(|window| window >= min))
+    }
+
+    /// Every matching model in `pricing`, with its provider, in file order.
+    pub fn find<'a>(&self, pricing: &'a AiPricingJson) -> Vec<(&'a Provider, &'a Model)> {
+        pricing
+            .all_models()
+            .filter(|(provider, model)| self.matches(provider, model))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_blended_price() {
+        let pricing = pricing();
+        let Some(Pricing::TextPricing(gpt)) = &pricing.providers[0].models[0].pricing else {
+            panic!("gpt-4o should have text pricing");
+        };
+        assert_eq!(gpt.blended_per1_m(3.0), 4.375);
+        assert_eq!(gpt.blended_per1_m(1.0), 6.25);
+    }
+
+    #[test]
+    fn test_model_limits() {
+        let mut pricing = pricing();
+        let gpt = &mut pricing.providers[0].models[0];
+        assert!(gpt.fits(1_000_000, 1_000_000));
+
+        gpt.context_window = Some(128_000);
+        gpt.max_output_tokens = Some(16_384);
+        assert!(gpt.fits(100_000, 16_384));
+        assert!(!gpt.fits(120_000, 16_384));
+        assert!(!gpt.fits(1_000, 20_000));
+
+        let keys: Vec<&str> = ModelQuery::new()
+            .min_context_window(100_000)
+            .find(&pricing)
+            .iter()
+            .map(|(_, model)| model.key.as_str())
+            .collect();
+        assert_eq!(keys, ["gpt-4o"]);
+    }
+
+    #[test]
+    fn test_cheapest_text_model() {
+        let pricing = pricing();
+
+        let ranked = pricing.rank_text_models(&ModelRequirements::new());
+        let keys: Vec<&str> = ranked.iter().map(|r| r.model.key.as_str()).collect();
+        // The deprecated claude-3-sonnet and the image model are skipped.
+        assert_eq!(keys, ["gpt-4o", "claude-3-5-sonnet"]);
+
+        let cheapest = pricing
+            .cheapest_text_model(&ModelRequirements::new().streaming(true).feature("vision"))
+            .unwrap();
+        assert_eq!(cheapest.model.key, "gpt-4o");
+        assert_eq!(cheapest.provider.key, "openai");
+
+        assert!(pricing
+            .cheapest_text_model(&ModelRequirements::new().feature("audio"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_model_query() {
+        let pricing = pricing();
+        let keys = |query: ModelQuery| -> Vec<String> {
+            query
+                .find(&pricing)
+                .into_iter()
+                .map(|(_, model)| model.key.clone())
+                .collect()
+        };
+
+        assert_eq!(
+            keys(
+                ModelQuery::new()
+                    .model_type(ModelType::Text)
+                    .feature("vision")
+                    .streaming(true)
+                    .max_input_price_per_1m(5.0)
+            ),
+            ["gpt-4o"]
+        );
+        assert_eq!(
+            keys(ModelQuery::new().max_output_price_per_1m(15.0)),
+            ["gpt-4o", "claude-3-5-sonnet"]
+        );
+        assert_eq!(
+            keys(
+                ModelQuery::new()
+                    .provider("bedrock")
+                    .include_unavailable(true)
+            ),
+            ["claude-3-5-sonnet", "claude-3-sonnet"]
+        );
+        assert_eq!(
+            keys(ModelQuery::new().model_type(ModelType::Image)),
+            ["dall-e-3"]
+        );
+        assert!(keys(ModelQuery::new().max_input_price_per_1m(1.0)).is_empty());
+    }
+}
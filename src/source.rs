@@ -0,0 +1,269 @@
+use crate::parse::{parse, parse_file};
+use crate::{telemetry, AiPricingJson, PricingError};
+use once_cell::sync::Lazy;
+use reqwest::header::{self, HeaderValue};
+use reqwest::{Client, StatusCode, Url};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// ------------------
+// Source trait
+// ------------------
+
+/// Somewhere pricing data can be loaded from.
+///
+/// [`PricingClient`](crate::PricingClient) is generic over its source, so
+/// tests and alternative storage backends (S3, a database, ...) can plug in
+/// their own. Failures from custom backends can be reported as
+/// [`PricingError::Source`].
+pub trait PricingSource: Send + Sync + 'static {
+    /// Load the current pricing.
+    fn load(&self) -> impl Future<Output = Result<AiPricingJson, PricingError>> + Send;
+
+    /// Load the current pricing, or return `None` if it hasn't changed since
+    /// the last successful load from this source.
+    ///
+    /// The default always loads. Sources that can check for changes cheaply,
+    /// like [`HttpSource`] with conditional requests, override it.
+    fn load_if_modified(
+        &self,
+    ) -> impl Future<Output = Result<Option<AiPricingJson>, PricingError>> + Send {
+        async { self.load().await.map(Some) }
+    }
+}
+
+// ------------------
+// HTTP source
+// ------------------
+
+/// Loads pricing from a URL.
+///
+/// Conditional loads send back the `ETag`/`Last-Modified` validators of the
+/// previous response, so an unchanged file costs a 304 rather than a full
+/// download. `file://` URLs are read from local disk.
+#[derive(Debug)]
+pub struct HttpSource {
+    http: Client,
+    url: String,
+    timeout: Option<Duration>,
+    validators: Mutex<Validators>,
+}
+
+/// The HTTP client used unless one is injected. Sharing it lets every source
+/// reuse one connection pool.
+static SHARED_CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// HTTP validators from the last successful response.
+#[derive(Debug, Clone, Default)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl HttpSource {
+    /// Load from `url` using the crate's shared HTTP client.
+    pub fn new(url: impl Into<String>) -> Self {
+        HttpSource::with_client(SHARED_CLIENT.clone(), url)
+    }
+
+    /// Load from `url` using a pre-configured HTTP client, e.g. one set up
+    /// with a corporate proxy or custom TLS roots.
+    pub fn with_client(http: Client, url: impl Into<String>) -> Self {
+        HttpSource {
+            http,
+            url: url.into(),
+            timeout: None,
+            validators: Mutex::default(),
+        }
+    }
+
+    /// Time out each request after `timeout`, on top of any timeout the HTTP
+    /// client itself is configured with.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    async fn fetch(&self, conditional: bool) -> Result<Option<AiPricingJson>, PricingError> {
+        telemetry::instrument_fetch(&self.url, conditional, self.send(conditional)).await
+    }
+
+    async fn send(&self, conditional: bool) -> Result<Option<AiPricingJson>, PricingError> {
+        let url = &self.url;
+        if let Some(path) = file_url_path(url) {
+            return FileSource::new(path).load().await.map(Some);
+        }
+
+        let request_error = |source| PricingError::Request {
+            source,
+            url: url.to_string(),
+        };
+
+        let mut request = self.http.get(url);
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        if conditional {
+            let validators = self.validators.lock().unwrap().clone();
+            if let Some(etag) = &validators.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let resp = request.send().await.map_err(request_error)?;
+        let status = resp.status();
+        telemetry::response_status(status);
+        if status == StatusCode::NOT_MODIFIED && conditional {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(PricingError::Http {
+                status,
+                url: url.to_string(),
+            });
+        }
+
+        let header_value = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let validators = Validators {
+            etag: header_value(header::ETAG),
+            last_modified: header_value(header::LAST_MODIFIED),
+        };
+
+        let body = resp.bytes().await.map_err(request_error)?;
+        telemetry::payload_received(body.len());
+        let parse_started = Instant::now();
+        let data = parse(&body, url)?;
+        telemetry::parsed(parse_started.elapsed());
+        *self.validators.lock().unwrap() = validators;
+        Ok(Some(data))
+    }
+}
+
+impl PricingSource for HttpSource {
+    async fn load(&self) -> Result<AiPricingJson, PricingError> {
+        let data = self.fetch(false).await?;
+        Ok(data.expect("unconditional requests always return data"))
+    }
+
+    async fn load_if_modified(&self) -> Result<Option<AiPricingJson>, PricingError> {
+        self.fetch(true).await
+    }
+}
+
+// ------------------
+// File source
+// ------------------
+
+/// Loads pricing from a local file on every load.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileSource { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl PricingSource for FileSource {
+    async fn load(&self) -> Result<AiPricingJson, PricingError> {
+        let body = tokio::fs::read(&self.path)
+            .await
+            .map_err(|source| PricingError::Io {
+                source,
+                path: self.path.clone(),
+            })?;
+        parse_file(&body, &self.path)
+    }
+}
+
+// ------------------
+// Static source
+// ------------------
+
+/// Always loads the same pricing, e.g. in tests.
+#[derive(Debug, Clone)]
+pub struct StaticSource {
+    pricing: Arc<AiPricingJson>,
+}
+
+impl StaticSource {
+    pub fn new(pricing: impl Into<Arc<AiPricingJson>>) -> Self {
+        StaticSource {
+            pricing: pricing.into(),
+        }
+    }
+}
+
+impl PricingSource for StaticSource {
+    async fn load(&self) -> Result<AiPricingJson, PricingError> {
+        Ok(AiPricingJson::clone(&self.pricing))
+    }
+}
+
+/// The local path a `file://` URL points at, or `None` for any other URL.
+pub(crate) fn file_url_path(url: &str) -> Option<PathBuf> {
+    Url::parse(url)
+        .ok()
+        .filter(|url| url.scheme() == "file")
+        .and_then(|url| url.to_file_path().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::PRICING_JSON;
+
+    #[test]
+    fn test_file_url_path() {
+        assert_eq!(
+            file_url_path("file:///tmp/ai-pricing.json"),
+            Some(PathBuf::from("/tmp/ai-pricing.json"))
+        );
+        assert_eq!(
+            file_url_path("https://images.bookcicle.com/ai/ai-pricing.json"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_and_static_sources() {
+        let path = std::env::temp_dir().join(format!(
+            "ai-pricing-file-source-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, PRICING_JSON).unwrap();
+
+        let source = FileSource::new(&path);
+        assert_eq!(source.load().await.unwrap().providers.len(), 2);
+        assert!(source.load_if_modified().await.unwrap().is_some());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(source.load().await, Err(PricingError::Io { .. })));
+
+        let source = StaticSource::new(crate::test_fixtures::pricing());
+        assert_eq!(
+            source.load().await.unwrap().metered_price_id,
+            "price_metered"
+        );
+    }
+}
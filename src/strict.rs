@@ -0,0 +1,353 @@
+use crate::{
+    AiPricingJson, AuthScheme, Model, ModelType, Pricing, PricingError, Provider, TextPricing,
+    ValidationReport,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::path::Path;
+
+/// Per-1K/per-1M pairs a text pricing object must publish in full.
+const REQUIRED_PAIRS: [&str; 2] = ["input", "output"];
+const OPTIONAL_PAIRS: [&str; 6] = [
+    "cachedInput",
+    "reasoningOutput",
+    "cacheWrite",
+    "batchInput",
+    "batchOutput",
+    "imageInput",
+];
+
+// ------------------
+// Strict parsing
+// ------------------
+
+impl AiPricingJson {
+    /// Deserialize pricing JSON for authoring or publishing, rejecting what
+    /// the lenient parse would quietly accept:
+    ///
+    /// - fields this crate doesn't know, such as `inputPer1k` for `inputPer1K`
+    /// - text prices given only per 1K or only per 1M
+    /// - models without a `key`, or with an unknown `type`
+    /// - providers with an unknown auth `scheme`
+    /// - numbers given as strings, such as `"0.0025"`
+    ///
+    /// Problems are listed in a [`PricingError::Validation`].
+    pub fn from_slice_strict(body: &[u8]) -> Result<AiPricingJson, PricingError> {
+        parse_strict(body, "strict pricing")
+    }
+
+    /// [`from_slice_strict`](Self::from_slice_strict), reading from a file.
+    pub fn from_path_strict(path: impl AsRef<Path>) -> Result<AiPricingJson, PricingError> {
+        let path = path.as_ref();
+        let body = std::fs::read(path).map_err(|source| PricingError::Io {
+            source,
+            path: path.to_path_buf(),
+        })?;
+        parse_strict(&body, &path.display().to_string())
+    }
+}
+
+// ------------------
+// Document checks
+// ------------------
+
+impl AiPricingJson {
+    /// Check an arbitrary JSON document as pricing data, reporting every
+    /// problem at once rather than stopping at the first.
+    ///
+    /// A document that doesn't deserialize gets one error per top-level
+    /// field, provider, and model that is the wrong shape. One that does gets
+    /// the [`from_slice_strict`](Self::from_slice_strict) checks and
+    /// [`validate`](Self::validate).
+    pub fn check(value: &Value) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        match crate::parse::from_value::<AiPricingJson>(value.clone()) {
+            Ok(pricing) => {
+                if let Err(err) = strict_checks(value, &pricing, &mut report) {
+                    report.error("", err.to_string());
+                }
+                report.issues.extend(pricing.validate().issues);
+            }
+            Err(_) => check_shape(value, &mut report),
+        }
+        report
+    }
+}
+
+/// Deserialize the document piece by piece, so one bad model doesn't hide
+/// problems elsewhere.
+fn check_shape(raw: &Value, report: &mut ValidationReport) {
+    let mut top = raw.clone();
+    let providers = take_array(&mut top, "providers");
+    shape_error::<AiPricingJson>(top, "", report);
+
+    for (p, provider) in providers.into_iter().enumerate() {
+        let path = format!("providers[{}]", label(&provider, p));
+        let mut shell = provider;
+        let models = take_array(&mut shell, "models");
+        shape_error::<Provider>(shell, &path, report);
+
+        for (m, model) in models.into_iter().enumerate() {
+            let model_path = format!("{}.models[{}]", path, label(&model, m));
+            shape_error::<Model>(model, &model_path, report);
+        }
+    }
+}
+
+/// Move the array in `field` out of `object`, leaving an empty one behind.
+/// Anything other than an array is left for deserialization to report.
+fn take_array(object: &mut Value, field: &str) -> Vec<Value> {
+    match object.get_mut(field) {
+        Some(Value::Array(items)) => std::mem::take(items),
+        _ => Vec::new(),
+    }
+}
+
+fn shape_error<T: DeserializeOwned>(value: Value, path: &str, report: &mut ValidationReport) {
+    let Err(err) = serde_path_to_error::deserialize::<_, T>(value) else {
+        return;
+    };
+    let inner = err.path().to_string();
+    let path = match (path, inner.as_str()) {
+        (path, ".") => path.to_string(),
+        ("", inner) => inner.to_string(),
+        (path, inner) => format!("{}.{}", path, inner),
+    };
+    report.error(path, err.into_inner().to_string());
+}
+
+fn parse_strict(body: &[u8], origin: &str) -> Result<AiPricingJson, PricingError> {
+    let deserialize_error = |source| PricingError::Deserialize {
+        source,
+        url: origin.to_string(),
+    };
+    let raw: Value = serde_json::from_slice(body).map_err(deserialize_error)?;
+    let pricing: AiPricingJson =
+        crate::parse::from_value(raw.clone()).map_err(deserialize_error)?;
+
+    let mut report = ValidationReport::default();
+    strict_checks(&raw, &pricing, &mut report).map_err(deserialize_error)?;
+
+    report.into_result()?;
+    Ok(pricing)
+}
+
+fn strict_checks(
+    raw: &Value,
+    pricing: &AiPricingJson,
+    report: &mut ValidationReport,
+) -> Result<(), serde_json::Error> {
+    check_expected(raw, pricing, report);
+
+    // Whatever the typed structs don't carry once unknown fields are cleared
+    // out was either unknown or dropped.
+    let mut known = pricing.clone();
+    clear_extras(&mut known);
+    let known = serde_json::to_value(&known)?;
+    check_unknown(raw, &known, "", report);
+    Ok(())
+}
+
+fn check_expected(raw: &Value, pricing: &AiPricingJson, report: &mut ValidationReport) {
+    for (p, provider) in pricing.providers.iter().enumerate() {
+        if let Some(AuthScheme::Other(other)) = provider.auth.as_ref().map(|auth| &auth.scheme) {
+            report.error(
+                format!("providers[{}].auth.scheme", provider.key),
+                format!("unknown auth scheme {:?}", other),
+            );
+        }
+        for (m, model) in provider.models.iter().enumerate() {
+            let raw_model = &raw["providers"][p]["models"][m];
+            let path = format!(
+                "providers[{}].models[{}]",
+                provider.key,
+                label(raw_model, m)
+            );
+
+            if raw_model.get("key").is_none() {
+                report.error(&path, "missing key");
+            }
+            if let ModelType::Other(other) = &model.model_type {
+                report.error(&path, format!("unknown model type {:?}", other));
+            }
+            if let Some(Pricing::TextPricing(_)) = &model.pricing {
+                check_pairs(&raw_model["pricing"], &format!("{}.pricing", path), report);
+            }
+            for region in model.regions.keys() {
+                check_pairs(
+                    &raw_model["regions"][region],
+                    &format!("{}.regions.{}", path, region),
+                    report,
+                );
+            }
+        }
+    }
+}
+
+fn check_pairs(raw_pricing: &Value, path: &str, report: &mut ValidationReport) {
+    let given = |field: String| !raw_pricing[field].is_null();
+    for pair in REQUIRED_PAIRS.iter().chain(OPTIONAL_PAIRS.iter()) {
+        let per1_k = given(format!("{}Per1K", pair));
+        let per1_m = given(format!("{}Per1M", pair));
+        let required = REQUIRED_PAIRS.contains(pair);
+        if per1_k != per1_m || (required && !per1_k) {
+            report.error(
+                path,
+                format!("{}Per1K and {}Per1M must both be given", pair, pair),
+            );
+        }
+    }
+}
+
+fn clear_extras(pricing: &mut AiPricingJson) {
+    pricing.extra.clear();
+    for provider in &mut pricing.providers {
+        provider.extra.clear();
+        let threshold = &mut provider.moderation_threshold;
+        threshold.categories.extra.clear();
+        threshold.category_score.extra.clear();
+        for model in &mut provider.models {
+            model.extra.clear();
+            match &mut model.pricing {
+                Some(Pricing::TextPricing(text)) => clear_text_extras(text),
+                Some(Pricing::RealtimePricing(realtime)) => clear_text_extras(&mut realtime.text),
+                Some(Pricing::ImagePricingVec(images)) => {
+                    images.iter_mut().for_each(|image| image.extra.clear())
+                }
+                Some(Pricing::AudioPricing(audio)) => audio.extra.clear(),
+                Some(Pricing::VideoPricing(video)) => video.extra.clear(),
+                None => {}
+            }
+            model.regions.values_mut().for_each(clear_text_extras);
+            if let Some(fine_tune) = &mut model.fine_tuning {
+                fine_tune.extra.clear();
+            }
+        }
+    }
+}
+
+fn clear_text_extras(text: &mut TextPricing) {
+    text.extra.clear();
+    text.tiers.iter_mut().for_each(|tier| tier.extra.clear());
+}
+
+/// Report every non-null field in `raw` that `known` doesn't have, and every
+/// number `raw` gives as a string.
+fn check_unknown(raw: &Value, known: &Value, path: &str, report: &mut ValidationReport) {
+    match (raw, known) {
+        (Value::Object(raw), Value::Object(known)) => {
+            for (field, value) in raw {
+                let field_path = match path {
+                    "" => field.clone(),
+                    _ => format!("{}.{}", path, field),
+                };
+                match known.get(field) {
+                    Some(known) => check_unknown(value, known, &field_path, report),
+                    None if !value.is_null() => report.error(field_path, "unknown field"),
+                    None => {}
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(known)) => {
+            for (i, (raw, known)) in raw.iter().zip(known).enumerate() {
+                let element_path = format!("{}[{}]", path, label(raw, i));
+                check_unknown(raw, known, &element_path, report);
+            }
+        }
+        (Value::String(_), Value::Number(_)) => report.error(path, "number given as a string"),
+        _ => {}
+    }
+}
+
+/// How a validation path names an array element: by key or image size where
+/// it has one, otherwise by index.
+fn label(element: &Value, index: usize) -> String {
+    element
+        .get("key")
+        .or_else(|| element.get("size"))
+        .and_then(Value::as_str)
+        .map_or_else(|| index.to_string(), str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::PRICING_JSON;
+
+    #[test]
+    fn test_strict_accepts_fixture() {
+        let pricing = AiPricingJson::from_slice_strict(PRICING_JSON.as_bytes())
+            .expect("fixture should parse strictly");
+        assert_eq!(pricing.providers.len(), 2);
+    }
+
+    #[test]
+    fn test_strict_rejects_typos_and_gaps() {
+        let mut json: Value = serde_json::from_str(PRICING_JSON).unwrap();
+        let gpt = &mut json["providers"][0]["models"][0];
+        let per1_k = gpt["pricing"]["inputPer1K"].take();
+        gpt["pricing"]["inputPer1k"] = per1_k;
+        gpt["pricing"]["cachedInputPer1K"] = Value::Null;
+        json["providers"][0]["markup"]["textPercentge"] = 20.0.into();
+        json["providers"][0]["moderationThreshold"]["general"] = "0.8".into();
+        json["providers"][1]["models"][1]["type"] = "Text".into();
+        json["providers"][1]["auth"] = serde_json::json!({ "scheme": "sigv4" });
+        let body = serde_json::to_vec(&json).unwrap();
+
+        // The lenient parse takes all of this.
+        assert!(crate::parse::parse(&body, "test").is_ok());
+
+        let Err(PricingError::Validation(message)) = AiPricingJson::from_slice_strict(&body) else {
+            panic!("strict parse should fail");
+        };
+        for expected in [
+            "providers[openai].models[gpt-4o].pricing.inputPer1k: unknown field",
+            "providers[openai].models[gpt-4o].pricing: inputPer1K and inputPer1M must both be given",
+            "providers[openai].models[gpt-4o].pricing: cachedInputPer1K and cachedInputPer1M",
+            "providers[openai].markup.textPercentge: unknown field",
+            "providers[openai].moderationThreshold.general: number given as a string",
+            "providers[bedrock].models[claude-3-sonnet]: unknown model type \"Text\"",
+            "providers[bedrock].auth.scheme: unknown auth scheme \"sigv4\"",
+        ] {
+            assert!(message.contains(expected), "{} not in {}", expected, message);
+        }
+    }
+
+    #[test]
+    fn test_check_reports_every_shape_error() {
+        let mut json: Value = serde_json::from_str(PRICING_JSON).unwrap();
+        json["meteredPriceId"] = 7.into();
+        json["providers"][0]["models"][0]["pricing"]["outputPer1M"] = true.into();
+        json["providers"][1]["markup"] = Value::Null;
+        json["providers"][1]["models"][1]["added"] = Value::Null;
+
+        let report = AiPricingJson::check(&json);
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "meteredPriceId",
+                "providers[openai].models[gpt-4o].pricing.outputPer1M",
+                "providers[bedrock].markup",
+                "providers[bedrock].models[claude-3-sonnet].added",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_combines_strict_and_semantic_checks() {
+        assert!(AiPricingJson::check(&serde_json::from_str(PRICING_JSON).unwrap()).is_ok());
+
+        let mut json: Value = serde_json::from_str(PRICING_JSON).unwrap();
+        json["providers"][0]["markup"]["textPercentge"] = 20.0.into();
+        json["providers"][1]["models"][1]["key"] = "claude-3-5-sonnet".into();
+
+        let report = AiPricingJson::check(&json);
+        let messages: Vec<String> = report.errors().map(ToString::to_string).collect();
+        assert!(messages
+            .contains(&"error: providers[openai].markup.textPercentge: unknown field".to_string()));
+        assert!(messages.contains(
+            &"error: providers[bedrock].models[claude-3-5-sonnet]: duplicate model key".to_string()
+        ));
+    }
+}
@@ -0,0 +1,385 @@
+use crate::{AiPricingJson, CostBreakdown, RoundingPolicy};
+#[cfg(feature = "stripe-verify")]
+use crate::{PricingError, ValidationReport};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "stripe-verify")]
+use std::collections::HashMap;
+
+const MICROS_PER_DOLLAR: f64 = 1_000_000.0;
+
+// ------------------
+// Quantity
+// ------------------
+
+/// The billable quantity for `charge`: its customer price in micro-dollars.
+/// Metered prices bill one unit per micro-dollar (a `unit_amount_decimal` of
+/// `0.0001` cents), so even the smallest requests are billed what they cost,
+/// and Stripe rounds the period's total to cents when it invoices.
+pub fn quantity_for_cost(charge: &CostBreakdown) -> u64 {
+    let dollars = RoundingPolicy::MicroCents.round(charge.customer_price);
+    (dollars * MICROS_PER_DOLLAR).round().max(0.0) as u64
+}
+
+// ------------------
+// Usage records
+// ------------------
+
+/// Whether a usage record adds to or replaces the period's usage so far.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum UsageAction {
+    #[default]
+    Increment,
+    Set,
+}
+
+/// A usage record for a metered subscription price.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UsageRecord {
+    #[serde(rename = "price")]
+    pub price_id: String,
+    pub quantity: u64,
+    /// Unix timestamp, in seconds, the usage occurred at.
+    pub timestamp: i64,
+    #[serde(default)]
+    pub action: UsageAction,
+    /// Sent as the `Idempotency-Key` header so retried requests aren't billed
+    /// twice.
+    #[serde(skip)]
+    pub idempotency_key: String,
+}
+
+impl UsageRecord {
+    /// A record incrementing usage on `price_id` by `charge`'s customer
+    /// price.
+    pub fn from_cost(
+        price_id: impl Into<String>,
+        charge: &CostBreakdown,
+        timestamp: i64,
+        idempotency_key: impl Into<String>,
+    ) -> Self {
+        UsageRecord {
+            price_id: price_id.into(),
+            quantity: quantity_for_cost(charge),
+            timestamp,
+            action: UsageAction::Increment,
+            idempotency_key: idempotency_key.into(),
+        }
+    }
+
+    /// A record incrementing usage on the pricing file's top-level
+    /// `metered_price_id` by `charge`'s customer price.
+    pub fn metered(
+        pricing: &AiPricingJson,
+        charge: &CostBreakdown,
+        timestamp: i64,
+        idempotency_key: impl Into<String>,
+    ) -> Self {
+        Self::from_cost(
+            &pricing.metered_price_id,
+            charge,
+            timestamp,
+            idempotency_key,
+        )
+    }
+}
+
+// ------------------
+// Meter events
+// ------------------
+
+/// A billing meter event, for prices backed by a Stripe meter.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct MeterEvent {
+    pub event_name: String,
+    /// Unique per event; Stripe uses it to drop duplicates.
+    pub identifier: String,
+    /// Unix timestamp, in seconds, the usage occurred at.
+    pub timestamp: i64,
+    pub payload: MeterEventPayload,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct MeterEventPayload {
+    pub stripe_customer_id: String,
+    /// The quantity, as a string like Stripe expects.
+    pub value: String,
+}
+
+impl MeterEvent {
+    /// An event reporting `charge`'s customer price for `customer_id` on the
+    /// meter named `event_name`.
+    pub fn from_cost(
+        event_name: impl Into<String>,
+        customer_id: impl Into<String>,
+        charge: &CostBreakdown,
+        timestamp: i64,
+        identifier: impl Into<String>,
+    ) -> Self {
+        MeterEvent {
+            event_name: event_name.into(),
+            identifier: identifier.into(),
+            timestamp,
+            payload: MeterEventPayload {
+                stripe_customer_id: customer_id.into(),
+                value: quantity_for_cost(charge).to_string(),
+            },
+        }
+    }
+}
+
+// ------------------
+// Price ID verification
+// ------------------
+
+/// Where the Stripe API is served.
+#[cfg(feature = "stripe-verify")]
+pub const STRIPE_API_BASE: &str = "https://api.stripe.com";
+
+/// Every Stripe price ID in `pricing`, with its path in the JSON.
+pub fn price_ids(pricing: &AiPricingJson) -> Vec<(String, &str)> {
+    let mut ids = vec![(
+        "meteredPriceId".to_string(),
+        pricing.metered_price_id.as_str(),
+    )];
+    for (provider, model) in pricing.all_models() {
+        let Some(prod) = &model.prod_price_ids else {
+            continue;
+        };
+        let path = format!(
+            "providers[{}].models[{}].prodPriceIds",
+            provider.key, model.key
+        );
+        for (field, id) in [
+            ("input", &prod.input),
+            ("output", &prod.output),
+            ("cachedInput", &prod.cached_input),
+        ] {
+            if let Some(id) = id {
+                ids.push((format!("{}.{}", path, field), id.as_str()));
+            }
+        }
+    }
+    ids.retain(|(_, id)| !id.is_empty());
+    ids
+}
+
+/// Checks that the price IDs in a pricing file exist and are active in
+/// Stripe, so typos are caught before the file is published.
+#[cfg(feature = "stripe-verify")]
+#[derive(Clone)]
+pub struct PriceVerifier {
+    http: reqwest::Client,
+    api_base: String,
+    secret_key: String,
+}
+
+// Hand-written so the secret key never ends up in logs.
+#[cfg(feature = "stripe-verify")]
+impl std::fmt::Debug for PriceVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PriceVerifier")
+            .field("http", &self.http)
+            .field("api_base", &self.api_base)
+            .field("secret_key", &"<redacted>")
+            .finish()
+    }
+}
+
+#[cfg(feature = "stripe-verify")]
+impl PriceVerifier {
+    /// A verifier authenticating with `secret_key`. A restricted key with read
+    /// access to prices is enough.
+    pub fn new(secret_key: impl Into<String>) -> Self {
+        PriceVerifier {
+            http: reqwest::Client::new(),
+            api_base: STRIPE_API_BASE.to_string(),
+            secret_key: secret_key.into(),
+        }
+    }
+
+    /// Talk to a different API host, e.g. a local Stripe mock.
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Look up every price ID in `pricing`, reporting each one Stripe doesn't
+    /// know or has archived. Each distinct ID is requested once.
+    ///
+    /// Fails only if Stripe can't be asked, e.g. the key is rejected.
+    pub async fn verify(&self, pricing: &AiPricingJson) -> Result<ValidationReport, PricingError> {
+        let mut report = ValidationReport::default();
+        let mut statuses = HashMap::new();
+
+        for (path, id) in price_ids(pricing) {
+            let active = match statuses.get(id) {
+                Some(&active) => active,
+                None => {
+                    let active = self.fetch_active(id).await?;
+                    statuses.insert(id, active);
+                    active
+                }
+            };
+            match active {
+                None => report.error(path, format!("price {} does not exist in Stripe", id)),
+                Some(false) => report.error(path, format!("price {} is archived in Stripe", id)),
+                Some(true) => {}
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Whether the price is active, or `None` if Stripe has no such price.
+    async fn fetch_active(&self, id: &str) -> Result<Option<bool>, PricingError> {
+        #[derive(Deserialize)]
+        struct Price {
+            active: bool,
+        }
+
+        let url = self.price_url(id)?;
+        let request_error = |source| PricingError::Request {
+            source,
+            url: url.clone(),
+        };
+
+        let resp = self
+            .http
+            .get(&url)
+            .bearer_auth(&self.secret_key)
+            .send()
+            .await
+            .map_err(request_error)?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(PricingError::Http { status, url });
+        }
+
+        let price: Price = resp.json().await.map_err(request_error)?;
+        Ok(Some(price.active))
+    }
+
+    /// The API URL of price `id`, which comes from the pricing file and so is
+    /// percent-encoded as a single path segment.
+    fn price_url(&self, id: &str) -> Result<String, PricingError> {
+        let mut url = reqwest::Url::parse(&self.api_base).map_err(|err| {
+            PricingError::Validation(format!(
+                "invalid Stripe API base {}: {}",
+                self.api_base, err
+            ))
+        })?;
+        url.path_segments_mut()
+            .map_err(|_| {
+                PricingError::Validation(format!("invalid Stripe API base {}", self.api_base))
+            })?
+            .pop_if_empty()
+            .extend(["v1", "prices", id]);
+        Ok(url.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    /// A charge of `customer_price` on half that in provider cost.
+    fn charge(customer_price: f64) -> CostBreakdown {
+        CostBreakdown::new(customer_price / 2.0, customer_price)
+    }
+
+    #[test]
+    fn test_quantity_is_customer_price_in_micro_dollars() {
+        assert_eq!(quantity_for_cost(&charge(0.07)), 70_000);
+        assert_eq!(quantity_for_cost(&charge(0.0001)), 100);
+        assert_eq!(quantity_for_cost(&charge(0.000_000_4)), 0);
+        assert_eq!(quantity_for_cost(&charge(0.0)), 0);
+        // 3 * 1.2 is 3.5999999999999996 in f64.
+        let marked_up = CostBreakdown::new(3.0, 3.0 * 1.2);
+        assert_eq!(quantity_for_cost(&marked_up), 3_600_000);
+    }
+
+    #[test]
+    fn test_usage_record_payload() {
+        let record = UsageRecord::metered(&pricing(), &charge(1.234), 1_700_000_000, "req-1");
+        assert_eq!(record.price_id, pricing().metered_price_id);
+        assert_eq!(record.idempotency_key, "req-1");
+        assert_eq!(
+            serde_json::to_value(&record).unwrap(),
+            serde_json::json!({
+                "price": pricing().metered_price_id,
+                "quantity": 1_234_000,
+                "timestamp": 1_700_000_000,
+                "action": "increment"
+            })
+        );
+    }
+
+    #[test]
+    fn test_meter_event_payload() {
+        let event =
+            MeterEvent::from_cost("ai_usage", "cus_123", &charge(0.5), 1_700_000_000, "req-2");
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "event_name": "ai_usage",
+                "identifier": "req-2",
+                "timestamp": 1_700_000_000,
+                "payload": {"stripe_customer_id": "cus_123", "value": "500000"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_price_ids() {
+        let pricing = pricing();
+        let ids = price_ids(&pricing);
+        assert_eq!(ids[0], ("meteredPriceId".to_string(), "price_metered"));
+        assert!(ids.contains(&(
+            "providers[openai].models[gpt-4o].prodPriceIds.input".to_string(),
+            "price_gpt4o_input"
+        )));
+    }
+
+    #[cfg(feature = "stripe-verify")]
+    #[test]
+    fn test_verifier_hides_secret_and_encodes_ids() {
+        let verifier =
+            PriceVerifier::new("sk_live_secret").with_api_base("http://localhost:12111/");
+        assert!(!format!("{:?}", verifier).contains("sk_live_secret"));
+
+        assert_eq!(
+            verifier.price_url("price_123").unwrap(),
+            "http://localhost:12111/v1/prices/price_123"
+        );
+        assert_eq!(
+            verifier.price_url("../customers?limit=1").unwrap(),
+            "http://localhost:12111/v1/prices/..%2Fcustomers%3Flimit=1"
+        );
+    }
+
+    #[cfg(all(feature = "stripe-verify", feature = "fetch"))]
+    #[tokio::test]
+    async fn test_verify_reports_connection_errors() {
+        // Nothing listens on port 1, so the first lookup fails to connect.
+        let verifier = PriceVerifier::new("sk_test").with_api_base("http://127.0.0.1:1/");
+        let result = verifier.verify(&pricing()).await;
+        assert!(matches!(result, Err(PricingError::Request { .. })));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::markdown::price;
+use crate::{Model, Pricing, Provider, TextPricing};
+use std::fmt::{self, Write};
+
+// ------------------
+// One-line summaries
+// ------------------
+
+/// Input/output prices per 1M tokens, plus the cached input price if there is
+/// one, e.g. `$2.50/$10.00 per 1M, cached $1.25`. Prices are shown in the
+/// file's currency, which the `$` stands in for.
+impl fmt::Display for TextPricing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "${}/${} per 1M",
+            price(self.input_per1_m),
+            price(self.output_per1_m)
+        )?;
+        if let Some(cached) = self.cached_input_per1_m {
+            write!(f, ", cached ${}", price(cached))?;
+        }
+        Ok(())
+    }
+}
+
+/// The model's key and its prices, e.g. `gpt-4o: $2.50/$10.00 per 1M`, marked
+/// `(deprecated)` where it is.
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.key)?;
+        match &self.pricing {
+            Some(Pricing::TextPricing(text)) => write!(f, "{}", text)?,
+            Some(Pricing::ImagePricingVec(images)) => {
+                let range = range(images.iter().map(|image| image.cost_per_image));
+                write!(f, "{} per image", range.as_deref().unwrap_or("-"))?;
+            }
+            Some(Pricing::AudioPricing(audio)) => {
+                let mut parts = vec![];
+                if let Some(per_minute) = audio.transcription_per_minute {
+                    parts.push(format!("${} per minute transcribed", price(per_minute)));
+                }
+                if let Some(per1_k) = audio.tts_per1_k_characters {
+                    parts.push(format!("${} per 1K characters spoken", price(per1_k)));
+                }
+                match parts.is_empty() {
+                    true => f.write_str("unpriced")?,
+                    false => f.write_str(&parts.join(", "))?,
+                }
+            }
+            Some(Pricing::VideoPricing(video)) => {
+                let range = range(video.cost_per_second.values().copied());
+                write!(f, "{} per second", range.as_deref().unwrap_or("-"))?;
+            }
+            Some(Pricing::RealtimePricing(realtime)) => write!(
+                f,
+                "${}/${} per audio minute, text {}",
+                price(realtime.audio_input_per_minute),
+                price(realtime.audio_output_per_minute),
+                realtime.text
+            )?,
+            None => f.write_str("unpriced")?,
+        }
+        if self.is_deprecated() {
+            f.write_str(" (deprecated)")?;
+        }
+        Ok(())
+    }
+}
+
+/// The provider's label and key, model count and markup, e.g.
+/// `OpenAI (openai): 2 models, 20% text / 50% image markup`.
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}): {} model{}, {}% text / {}% image markup",
+            self.label,
+            self.key,
+            self.models.len(),
+            if self.models.len() == 1 { "" } else { "s" },
+            self.markup.text_percentage,
+            self.markup.image_percentage
+        )
+    }
+}
+
+impl Provider {
+    /// This provider's [`Display`](fmt::Display) line followed by one line per
+    /// model, each prefixed with the provider key, e.g.
+    /// `openai/gpt-4o: $2.50/$10.00 per 1M, cached $1.25`.
+    pub fn summary(&self) -> String {
+        let mut out = self.to_string();
+        for model in &self.models {
+            let _ = write!(out, "\n{}/{}", self.key, model);
+        }
+        out
+    }
+}
+
+/// `$min-$max`, or a single price when they're equal. `None` when empty.
+fn range(prices: impl Iterator<Item = f64>) -> Option<String> {
+    let (min, max) = prices.fold(None, |acc: Option<(f64, f64)>, price| match acc {
+        Some((min, max)) => Some((min.min(price), max.max(price))),
+        None => Some((price, price)),
+    })?;
+    Some(match min == max {
+        true => format!("${}", price(min)),
+        false => format!("${}-${}", price(min), price(max)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_model_display() {
+        let pricing = pricing();
+        let openai = &pricing.providers[0];
+        assert_eq!(
+            openai.models[0].to_string(),
+            "gpt-4o: $2.50/$10.00 per 1M, cached $1.25"
+        );
+        assert!(openai.models[1].to_string().ends_with(" per image"));
+        assert_eq!(
+            pricing.providers[1].models[1].to_string(),
+            "claude-3-sonnet: $3.00/$15.00 per 1M (deprecated)"
+        );
+    }
+
+    #[test]
+    fn test_provider_summary() {
+        let pricing = pricing();
+        let summary = pricing.providers[0].summary();
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(
+            lines[0],
+            "OpenAI (openai): 2 models, 20% text / 50% image markup"
+        );
+        assert_eq!(lines[1], "openai/gpt-4o: $2.50/$10.00 per 1M, cached $1.25");
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(
+            range([0.04, 0.12, 0.08].into_iter()).unwrap(),
+            "$0.04-$0.12"
+        );
+        assert_eq!(range([0.5].into_iter()).unwrap(), "$0.50");
+        assert_eq!(range(std::iter::empty()), None);
+    }
+}
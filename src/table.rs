@@ -0,0 +1,163 @@
+use crate::{AiPricingJson, Pricing, DEFAULT_INPUT_OUTPUT_RATIO};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+// ------------------
+// Price table
+// ------------------
+
+/// One text model's prices, normalized for comparison.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PriceRow {
+    pub provider_key: String,
+    pub provider_label: String,
+    pub model_key: String,
+    pub input_per1_m: f64,
+    pub output_per1_m: f64,
+    #[serde(default)]
+    pub cached_input_per1_m: Option<f64>,
+    /// Price per 1M tokens at the table's input:output ratio.
+    pub blended_per1_m: f64,
+    /// See [`Model::is_deprecated`](crate::Model::is_deprecated).
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+/// Column to sort a [`PriceTable`] by.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum PriceColumn {
+    Provider,
+    Model,
+    Input,
+    Output,
+    Blended,
+}
+
+/// Text model prices across every provider, normalized to per-1M-token
+/// prices and blended at a fixed input:output ratio.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PriceTable {
+    /// Input tokens per output token used for `blended_per1_m`.
+    pub blend_ratio: f64,
+    pub rows: Vec<PriceRow>,
+}
+
+impl PriceTable {
+    /// Sort rows by `column`, cheapest or alphabetically first. Ties keep
+    /// their current order.
+    pub fn sort_by(&mut self, column: PriceColumn) {
+        self.rows.sort_by(|a, b| compare(a, b, column));
+    }
+
+    /// [`sort_by`](Self::sort_by), returning the table.
+    pub fn sorted_by(mut self, column: PriceColumn) -> Self {
+        self.sort_by(column);
+        self
+    }
+
+    /// Drop deprecated models.
+    pub fn without_deprecated(mut self) -> Self {
+        self.rows.retain(|row| !row.deprecated);
+        self
+    }
+}
+
+fn compare(a: &PriceRow, b: &PriceRow, column: PriceColumn) -> Ordering {
+    match column {
+        PriceColumn::Provider => a.provider_key.cmp(&b.provider_key),
+        PriceColumn::Model => a.model_key.cmp(&b.model_key),
+        PriceColumn::Input => a.input_per1_m.total_cmp(&b.input_per1_m),
+        PriceColumn::Output => a.output_per1_m.total_cmp(&b.output_per1_m),
+        PriceColumn::Blended => a.blended_per1_m.total_cmp(&b.blended_per1_m),
+    }
+}
+
+impl AiPricingJson {
+    /// A comparison table of every text model's prices, blended at
+    /// [`DEFAULT_INPUT_OUTPUT_RATIO`] input tokens per output token. Rows are
+    /// in file order; system-disabled models are left out.
+    pub fn price_table(&self) -> PriceTable {
+        self.price_table_with_ratio(DEFAULT_INPUT_OUTPUT_RATIO)
+    }
+
+    /// [`price_table`](Self::price_table), blended at `ratio` input tokens per
+    /// output token.
+    pub fn price_table_with_ratio(&self, ratio: f64) -> PriceTable {
+        let rows = self
+            .all_models()
+            .filter(|(_, model)| !model.system_disabled.unwrap_or(false))
+            .filter_map(|(provider, model)| {
+                let Some(Pricing::TextPricing(pricing)) = &model.pricing else {
+                    return None;
+                };
+                Some(PriceRow {
+                    provider_key: provider.key.clone(),
+                    provider_label: provider.label.clone(),
+                    model_key: model.key.clone(),
+                    input_per1_m: pricing.input_per1_m,
+                    output_per1_m: pricing.output_per1_m,
+                    cached_input_per1_m: pricing.cached_input_per1_m,
+                    blended_per1_m: pricing.blended_per1_m(ratio),
+                    deprecated: model.is_deprecated(),
+                })
+            })
+            .collect();
+
+        PriceTable {
+            blend_ratio: ratio,
+            rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::ModelStatus;
+
+    #[test]
+    fn test_price_table() {
+        let table = pricing().price_table();
+        assert_eq!(table.blend_ratio, 3.0);
+        let keys: Vec<&str> = table.rows.iter().map(|r| r.model_key.as_str()).collect();
+        assert_eq!(keys, ["gpt-4o", "claude-3-5-sonnet", "claude-3-sonnet"]);
+        assert_eq!(table.rows[0].blended_per1_m, 4.375);
+        assert_eq!(table.rows[0].cached_input_per1_m, Some(1.25));
+
+        let table = table.without_deprecated().sorted_by(PriceColumn::Provider);
+        let keys: Vec<&str> = table.rows.iter().map(|r| r.model_key.as_str()).collect();
+        assert_eq!(keys, ["claude-3-5-sonnet", "gpt-4o"]);
+
+        let json = serde_json::to_value(&table).unwrap();
+        assert_eq!(json["rows"][0]["providerLabel"], "Bedrock");
+        assert_eq!(json["rows"][1]["blendedPer1M"], 4.375);
+    }
+
+    #[test]
+    fn test_legacy_models_are_deprecated() {
+        let mut pricing = pricing();
+        pricing.providers[0].models[0].status = Some(ModelStatus::Legacy);
+
+        let table = pricing.price_table();
+        assert!(table.rows[0].deprecated);
+        let table = table.without_deprecated();
+        let keys: Vec<&str> = table.rows.iter().map(|r| r.model_key.as_str()).collect();
+        assert_eq!(keys, ["claude-3-5-sonnet"]);
+        assert!(pricing
+            .render_markdown()
+            .contains("| `gpt-4o` (deprecated) |"));
+    }
+}
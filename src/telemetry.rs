@@ -0,0 +1,285 @@
+// Each hook reports to whichever of `tracing` and `metrics` are enabled, and
+// is a no-op with neither, so not every argument is used in every build.
+#![allow(unused_variables)]
+
+use crate::{AiPricingJson, PricingError};
+use reqwest::StatusCode;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+// ------------------
+// Fetches
+// ------------------
+
+/// Run one fetch of `url`, in a `pricing_fetch` span with the `tracing`
+/// feature, reporting when it starts and how it finished.
+///
+/// With the `metrics` feature, each fetch's latency is recorded in the
+/// `ai_pricing_fetch_duration_seconds` histogram, labelled with its `outcome`:
+/// `ok`, `not_modified`, or `error`.
+pub(crate) async fn instrument_fetch<F>(url: &str, conditional: bool, fetch: F) -> F::Output
+where
+    F: Future<Output = Result<Option<AiPricingJson>, PricingError>>,
+{
+    let fetch = async {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("fetching pricing");
+        let started = Instant::now();
+        let result = fetch.await;
+        fetch_finished(&result, started.elapsed());
+        result
+    };
+    #[cfg(feature = "tracing")]
+    let fetch = tracing::Instrument::instrument(
+        fetch,
+        tracing::info_span!("pricing_fetch", url, conditional),
+    );
+    fetch.await
+}
+
+fn fetch_finished(result: &Result<Option<AiPricingJson>, PricingError>, elapsed: Duration) {
+    #[cfg(feature = "tracing")]
+    match result {
+        Ok(Some(_)) => tracing::debug!(?elapsed, "fetched pricing"),
+        Ok(None) => tracing::debug!(?elapsed, "pricing not modified"),
+        Err(err) => tracing::warn!(?elapsed, error = %err, "pricing fetch failed"),
+    }
+    #[cfg(feature = "metrics")]
+    {
+        let outcome = match result {
+            Ok(Some(_)) => "ok",
+            Ok(None) => "not_modified",
+            Err(_) => "error",
+        };
+        metrics::histogram!("ai_pricing_fetch_duration_seconds", "outcome" => outcome)
+            .record(elapsed.as_secs_f64());
+    }
+}
+
+pub(crate) fn response_status(status: StatusCode) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(status = status.as_u16(), "pricing response");
+}
+
+pub(crate) fn payload_received(bytes: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(bytes, "pricing payload received");
+}
+
+pub(crate) fn parsed(elapsed: Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(?elapsed, "pricing parsed");
+}
+
+// ------------------
+// Cache and refreshes
+// ------------------
+
+/// A cache lookup served without waiting on the source. `stale` hits are
+/// revalidated in the background.
+///
+/// Counted in `ai_pricing_cache_hits_total`, labelled `stale`.
+pub(crate) fn cache_hit(stale: bool) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(stale, "pricing cache hit");
+    #[cfg(feature = "metrics")]
+    metrics::counter!("ai_pricing_cache_hits_total", "stale" => label(stale)).increment(1);
+}
+
+/// Counted in `ai_pricing_cache_misses_total`. Forced refreshes aren't misses.
+pub(crate) fn cache_miss() {
+    #[cfg(feature = "tracing")]
+    tracing::debug!("pricing cache miss");
+    #[cfg(feature = "metrics")]
+    metrics::counter!("ai_pricing_cache_misses_total").increment(1);
+}
+
+/// A refresh of the cache from the source finished. `changed` is false when
+/// the source reported the cached snapshot unchanged.
+///
+/// Counted in `ai_pricing_refresh_successes_total`, labelled `changed`.
+pub(crate) fn refreshed(changed: bool, elapsed: Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(changed, ?elapsed, "pricing refreshed");
+    #[cfg(feature = "metrics")]
+    metrics::counter!("ai_pricing_refresh_successes_total", "changed" => label(changed))
+        .increment(1);
+}
+
+/// Counted in `ai_pricing_refresh_failures_total`, including failures that
+/// were papered over by serving stale or persisted pricing.
+pub(crate) fn refresh_failed(err: &PricingError, elapsed: Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(?elapsed, error = %err, "pricing refresh failed");
+    #[cfg(feature = "metrics")]
+    metrics::counter!("ai_pricing_refresh_failures_total").increment(1);
+}
+
+#[cfg(feature = "metrics")]
+fn label(value: bool) -> &'static str {
+    match value {
+        true => "true",
+        false => "false",
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_tests {
+    use crate::test_fixtures::pricing;
+    use crate::testing::MockPricingServer;
+    use crate::Env;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records the message of every event.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Recorder {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                self.0.lock().unwrap().push(format!("{:?}", value));
+            }
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[tokio::test]
+    async fn test_fetch_and_cache_events() {
+        let server = MockPricingServer::start(&pricing()).await;
+        let client = server.client(Env::Dev).unwrap();
+        let recorder = Recorder::default();
+        let _default = tracing::subscriber::set_default(recorder.clone());
+
+        client.get().await.unwrap();
+        client.get().await.unwrap();
+        server.fail_with(500).await;
+        assert!(client.refresh().await.is_err());
+
+        let messages = recorder.0.lock().unwrap().clone();
+        for expected in [
+            "pricing cache miss",
+            "fetching pricing",
+            "pricing response",
+            "pricing payload received",
+            "pricing parsed",
+            "fetched pricing",
+            "pricing refreshed",
+            "pricing cache hit",
+            "pricing fetch failed",
+            "pricing refresh failed",
+        ] {
+            assert!(
+                messages.iter().any(|message| message == expected),
+                "missing {:?} in {:?}",
+                expected,
+                messages
+            );
+        }
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod metrics_tests {
+    use crate::test_fixtures::pricing;
+    use crate::testing::MockPricingServer;
+    use crate::Env;
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+        SharedString, Unit,
+    };
+    use std::sync::{Arc, Mutex};
+
+    /// Records every counter increment and histogram sample as
+    /// `name{labels} value`.
+    #[derive(Clone, Default)]
+    struct Samples(Arc<Mutex<Vec<String>>>);
+
+    struct Handle(Key, Samples);
+
+    impl Handle {
+        fn push(&self, value: impl std::fmt::Display) {
+            let labels: Vec<String> = self
+                .0
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+            let sample = format!("{}{{{}}} {}", self.0.name(), labels.join(","), value);
+            self.1 .0.lock().unwrap().push(sample);
+        }
+    }
+
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            self.push(value);
+        }
+        fn absolute(&self, value: u64) {
+            self.push(value);
+        }
+    }
+
+    impl HistogramFn for Handle {
+        fn record(&self, _: f64) {
+            self.push("sample");
+        }
+    }
+
+    impl Recorder for Samples {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(Arc::new(Handle(key.clone(), self.clone())))
+        }
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(Arc::new(Handle(key.clone(), self.clone())))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_and_cache_metrics() {
+        let server = MockPricingServer::start(&pricing()).await;
+        let client = server.client(Env::Dev).unwrap();
+        let recorder = Samples::default();
+        let _default = metrics::set_default_local_recorder(&recorder);
+
+        client.get().await.unwrap();
+        client.get().await.unwrap();
+        server.fail_with(500).await;
+        assert!(client.refresh().await.is_err());
+
+        let samples = recorder.0.lock().unwrap().clone();
+        assert_eq!(
+            samples,
+            [
+                "ai_pricing_cache_misses_total{} 1",
+                "ai_pricing_fetch_duration_seconds{outcome=ok} sample",
+                "ai_pricing_refresh_successes_total{changed=true} 1",
+                "ai_pricing_cache_hits_total{stale=false} 1",
+                "ai_pricing_fetch_duration_seconds{outcome=error} sample",
+                "ai_pricing_refresh_failures_total{} 1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use crate::AiPricingJson;
+
+/// A small two-provider pricing file shared by the unit tests.
+pub(crate) const PRICING_JSON: &str = r#"{
+    "meteredPriceId": "price_metered",
+    "providers": [
+        {
+            "description": "OpenAI",
+            "key": "openai",
+            "label": "OpenAI",
+            "markup": { "imagePercentage": 50.0, "textPercentage": 20.0 },
+            "models": [
+                {
+                    "added": "2024-05-13",
+                    "created": "2024-05-13",
+                    "features": ["vision"],
+                    "key": "gpt-4o",
+                    "type": "text",
+                    "streaming": true,
+                    "encoder": "o200k_base",
+                    "pricing": {
+                        "cachedInputPer1K": 0.00125,
+                        "cachedInputPer1M": 1.25,
+                        "inputPer1K": 0.0025,
+                        "inputPer1M": 2.5,
+                        "outputPer1K": 0.01,
+                        "outputPer1M": 10.0
+                    },
+                    "prodPriceIds": {
+                        "cachedInput": "price_gpt4o_cached",
+                        "input": "price_gpt4o_input",
+                        "output": "price_gpt4o_output"
+                    }
+                },
+                {
+                    "added": "2023-11-06",
+                    "created": "2023-11-06",
+                    "key": "dall-e-3",
+                    "type": "image",
+                    "pricing": [
+                        { "costPerImage": 0.04, "description": "Square", "size": "1024x1024" },
+                        { "costPerImage": 0.08, "description": "Wide", "size": "1792x1024" }
+                    ]
+                }
+            ],
+            "moderationThreshold": {
+                "categories": {
+                    "hate": true,
+                    "hate/threatening": true,
+                    "self-harm": true,
+                    "self-harm/instructions": true,
+                    "self-harm/intent": true,
+                    "sexual/minors": true
+                },
+                "categoryScore": {
+                    "harassment/threatening": 0.5,
+                    "illicit": 0.5,
+                    "illicit/violent": 0.4,
+                    "violence/graphic": 0.6
+                },
+                "general": 0.8
+            },
+            "providerHost": "api.openai.com",
+            "website": "https://openai.com"
+        },
+        {
+            "description": "Anthropic via Bedrock",
+            "key": "bedrock",
+            "label": "Bedrock",
+            "markup": { "imagePercentage": 0.0, "textPercentage": 25.0 },
+            "models": [
+                {
+                    "added": "2024-10-22",
+                    "created": "2024-10-22",
+                    "key": "claude-3-5-sonnet",
+                    "modelId": "anthropic.claude-3-5-sonnet-20241022-v2:0",
+                    "inferenceProfileId": "us.anthropic.claude-3-5-sonnet-20241022-v2:0",
+                    "type": "text",
+                    "streaming": true,
+                    "pricing": {
+                        "inputPer1K": 0.003,
+                        "inputPer1M": 3.0,
+                        "outputPer1K": 0.015,
+                        "outputPer1M": 15.0
+                    }
+                },
+                {
+                    "added": "2024-03-04",
+                    "created": "2024-03-04",
+                    "key": "claude-3-sonnet",
+                    "modelId": "anthropic.claude-3-sonnet-20240229-v1:0",
+                    "type": "text",
+                    "deprecated": true,
+                    "pricing": {
+                        "inputPer1K": 0.003,
+                        "inputPer1M": 3.0,
+                        "outputPer1K": 0.015,
+                        "outputPer1M": 15.0
+                    }
+                }
+            ],
+            "moderationThreshold": {
+                "categories": {
+                    "hate": true,
+                    "hate/threatening": true,
+                    "self-harm": true,
+                    "self-harm/instructions": true,
+                    "self-harm/intent": true,
+                    "sexual/minors": true
+                },
+                "categoryScore": {
+                    "harassment/threatening": 0.5,
+                    "illicit": 0.5,
+                    "illicit/violent": 0.5,
+                    "violence/graphic": 0.5
+                },
+                "general": 0.8
+            },
+            "providerHost": "bedrock-runtime.us-east-1.amazonaws.com",
+            "website": "https://aws.amazon.com/bedrock"
+        }
+    ]
+}"#;
+
+pub(crate) fn pricing() -> AiPricingJson {
+    serde_json::from_str(PRICING_JSON).expect("Failed to parse test pricing")
+}
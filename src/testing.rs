@@ -0,0 +1,103 @@
+use crate::{AiPricingJson, Env, PricingClient, PricingError};
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// ------------------
+// Mock pricing server
+// ------------------
+
+/// A local HTTP server that serves pricing, for testing the fetch and caching
+/// paths without reaching the real pricing host.
+///
+/// Every environment's pricing file is served with the same data. Point a
+/// client at it with [`base_url`](Self::base_url) or [`client`](Self::client).
+/// The server shuts down when dropped.
+pub struct MockPricingServer {
+    server: MockServer,
+}
+
+impl MockPricingServer {
+    /// Start a server serving `pricing`. Must be called within a tokio
+    /// runtime.
+    pub async fn start(pricing: &AiPricingJson) -> Self {
+        let server = MockPricingServer {
+            server: MockServer::start().await,
+        };
+        server.set_pricing(pricing).await;
+        server
+    }
+
+    /// Serve `pricing` from now on, e.g. to test that a refresh picks up a
+    /// price change. Also resets the request count.
+    pub async fn set_pricing(&self, pricing: &AiPricingJson) {
+        self.server.reset().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pricing))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Answer every request with `status` from now on, e.g. to test error
+    /// handling. Also resets the request count.
+    pub async fn fail_with(&self, status: u16) {
+        self.server.reset().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(status))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Base URL to pass to
+    /// [`PricingClientBuilder::base_url`](crate::PricingClientBuilder::base_url).
+    pub fn base_url(&self) -> String {
+        self.server.uri()
+    }
+
+    /// The URL of `env`'s pricing file on this server.
+    pub fn url(&self, env: &Env) -> String {
+        env.pricing_url(&self.base_url())
+    }
+
+    /// A client fetching `env`'s pricing from this server.
+    pub fn client(&self, env: Env) -> Result<PricingClient, PricingError> {
+        PricingClient::builder().url(self.url(&env)).build()
+    }
+
+    /// How many requests the server has answered since it started or was last
+    /// reset.
+    pub async fn request_count(&self) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .map_or(0, |requests| requests.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[tokio::test]
+    async fn test_mock_pricing_server() {
+        let server = MockPricingServer::start(&pricing()).await;
+        let client = server.client(Env::Staging).unwrap();
+        assert!(client.url().ends_with("/ai-pricing-staging.json"));
+
+        let fetched = client.get().await.unwrap();
+        assert_eq!(fetched.metered_price_id, "price_metered");
+        assert_eq!(server.request_count().await, 1);
+
+        let mut changed = pricing();
+        changed.metered_price_id = "price_changed".to_string();
+        server.set_pricing(&changed).await;
+        assert_eq!(
+            client.refresh().await.unwrap().metered_price_id,
+            "price_changed"
+        );
+
+        server.fail_with(503).await;
+        let err = client.refresh().await.unwrap_err();
+        assert!(err.is_transient(), "{}", err);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Cost, Encoder, Model, PricingError, ResolvedModel};
+use tiktoken_rs::CoreBPE;
+
+// ------------------
+// Token counting
+// ------------------
+
+impl Encoder {
+    fn bpe(&self) -> Option<&'static CoreBPE> {
+        match self {
+            Encoder::O200kBase => Some(tiktoken_rs::o200k_base_singleton()),
+            Encoder::Cl100kBase => Some(tiktoken_rs::cl100k_base_singleton()),
+            Encoder::P50kBase => Some(tiktoken_rs::p50k_base_singleton()),
+            Encoder::P50kEdit => Some(tiktoken_rs::p50k_edit_singleton()),
+            Encoder::R50kBase => Some(tiktoken_rs::r50k_base_singleton()),
+            Encoder::Other(_) => None,
+        }
+    }
+
+    /// Count the tokens in `text`, treating special-token markup as plain
+    /// text. `None` for encoders without a tiktoken encoding.
+    pub fn count_tokens(&self, text: &str) -> Option<u64> {
+        let bpe = self.bpe()?;
+        Some(bpe.encode_ordinary(text).len() as u64)
+    }
+}
+
+impl Model {
+    /// Provider cost of sending `input` to this model and getting back about
+    /// `expected_output_tokens`, counting the input with the model's encoder.
+    ///
+    /// Fails for image models and for models without a known encoder.
+    pub fn estimate_cost_for_text(
+        &self,
+        input: &str,
+        expected_output_tokens: u64,
+    ) -> Result<Cost, PricingError> {
+        let ResolvedModel::Text(text) = ResolvedModel::try_from(self)? else {
+            return Err(PricingError::Validation(format!(
+                "model {} is not a text model",
+                self.key
+            )));
+        };
+        let encoder = text.encoder().ok_or_else(|| {
+            PricingError::Validation(format!("model {} has no encoder", self.key))
+        })?;
+        let input_tokens = encoder.count_tokens(input).ok_or_else(|| {
+            PricingError::Validation(format!("no tokenizer for encoder {}", encoder))
+        })?;
+        Ok(text.pricing.cost(input_tokens, expected_output_tokens, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_count_tokens() {
+        assert_eq!(Encoder::O200kBase.count_tokens("hello world"), Some(2));
+        assert_eq!(Encoder::Cl100kBase.count_tokens(""), Some(0));
+        assert_eq!(
+            Encoder::Other("claude".to_string()).count_tokens("hi"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_estimate_cost_for_text() {
+        let pricing = pricing();
+        let gpt = pricing.find_model("openai", "gpt-4o").unwrap();
+        let cost = gpt
+            .estimate_cost_for_text("hello world", 1_000)
+            .expect("Failed to estimate");
+        assert_eq!(cost.input_cost, 2.0 * 2.5 / 1_000_000.0);
+        assert_eq!(cost.output_cost, 0.01);
+
+        let claude = pricing.find_model("bedrock", "claude-3-5-sonnet").unwrap();
+        assert!(claude.estimate_cost_for_text("hello", 10).is_err());
+        let dalle = pricing.find_model("openai", "dall-e-3").unwrap();
+        assert!(dalle.estimate_cost_for_text("hello", 10).is_err());
+    }
+}
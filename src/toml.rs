@@ -0,0 +1,90 @@
+use crate::parse::parse_value;
+use crate::{AiPricingJson, PricingError};
+use serde_json::{Map, Value};
+
+// ------------------
+// TOML
+// ------------------
+
+impl AiPricingJson {
+    /// Deserialize pricing TOML of any supported schema version, e.g. an
+    /// override table kept in a service's own config file.
+    pub fn from_toml_str(toml: &str) -> Result<AiPricingJson, PricingError> {
+        parse_toml(toml.as_bytes(), "TOML string")
+    }
+
+    /// Serialize to TOML.
+    pub fn to_toml_string(&self) -> Result<String, ::toml::ser::Error> {
+        ::toml::to_string(self)
+    }
+}
+
+/// Deserialize pricing TOML, attributing failures to `origin`. Like YAML,
+/// the document goes through JSON values first.
+pub(crate) fn parse_toml(body: &[u8], origin: &str) -> Result<AiPricingJson, PricingError> {
+    let toml_error = |source| PricingError::Toml {
+        source,
+        url: origin.to_string(),
+    };
+    let body = String::from_utf8_lossy(body);
+    let table: ::toml::Table = ::toml::from_str(&body).map_err(toml_error)?;
+    parse_value(to_json(::toml::Value::Table(table)), origin)
+}
+
+/// A TOML value as JSON. Bare TOML dates such as `added = 2024-05-13` become
+/// the strings the JSON format uses.
+fn to_json(value: ::toml::Value) -> Value {
+    match value {
+        ::toml::Value::String(s) => Value::String(s),
+        ::toml::Value::Integer(i) => Value::from(i),
+        ::toml::Value::Float(f) => Value::from(f),
+        ::toml::Value::Boolean(b) => Value::Bool(b),
+        ::toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        ::toml::Value::Array(items) => Value::Array(items.into_iter().map(to_json).collect()),
+        ::toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, to_json(value)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let pricing = pricing();
+        let toml = pricing.to_toml_string().unwrap();
+        let round_trip = AiPricingJson::from_toml_str(&toml).unwrap();
+        assert_eq!(
+            serde_json::to_value(&round_trip).unwrap(),
+            serde_json::to_value(&pricing).unwrap()
+        );
+
+        let path = std::env::temp_dir().join(format!("ai-pricing-{}.toml", std::process::id()));
+        std::fs::write(&path, &toml).unwrap();
+        let loaded = AiPricingJson::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.expect("Failed to read TOML").providers.len(), 2);
+    }
+
+    #[test]
+    fn test_bare_toml_dates() {
+        let toml = pricing()
+            .to_toml_string()
+            .unwrap()
+            .replace(r#"added = "2024-05-13""#, "added = 2024-05-13");
+        assert!(toml.contains("added = 2024-05-13\n"));
+        let pricing = AiPricingJson::from_toml_str(&toml).unwrap();
+        assert_eq!(pricing.providers[0].models[0].added, "2024-05-13");
+
+        assert!(matches!(
+            AiPricingJson::from_toml_str("providers = ["),
+            Err(PricingError::Toml { .. })
+        ));
+    }
+}
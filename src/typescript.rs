@@ -0,0 +1,75 @@
+use crate::estimate::{Estimate, UsageProfile};
+use crate::stripe::{MeterEvent, UsageRecord};
+use crate::{
+    AiPricingJson, Changelog, Cost, CostBreakdown, DriftReport, ImageDetail, ImageSize, Modality,
+    ModerationDecision, ModerationScores, PriceTable, PricingDiff, RealtimeCost, RealtimeSession,
+    RoundingPolicy, TokenUsage, ValidationReport,
+};
+use std::path::Path;
+use ts_rs::{ExportError, TS};
+
+// ------------------
+// TypeScript export
+// ------------------
+
+/// Write a TypeScript definition for every public serialized type to `dir`,
+/// one `{Type}.ts` file each, importing the types it refers to.
+pub fn export_typescript(dir: impl AsRef<Path>) -> Result<(), ExportError> {
+    let dir = dir.as_ref();
+    AiPricingJson::export_all_to(dir)?;
+    Changelog::export_all_to(dir)?;
+    Cost::export_all_to(dir)?;
+    CostBreakdown::export_all_to(dir)?;
+    Modality::export_all_to(dir)?;
+    TokenUsage::export_all_to(dir)?;
+    RealtimeSession::export_all_to(dir)?;
+    RealtimeCost::export_all_to(dir)?;
+    ImageSize::export_all_to(dir)?;
+    ImageDetail::export_all_to(dir)?;
+    ModerationScores::export_all_to(dir)?;
+    ModerationDecision::export_all_to(dir)?;
+    PricingDiff::export_all_to(dir)?;
+    DriftReport::export_all_to(dir)?;
+    PriceTable::export_all_to(dir)?;
+    ValidationReport::export_all_to(dir)?;
+    RoundingPolicy::export_all_to(dir)?;
+    UsageProfile::export_all_to(dir)?;
+    Estimate::export_all_to(dir)?;
+    UsageRecord::export_all_to(dir)?;
+    MeterEvent::export_all_to(dir)?;
+    #[cfg(feature = "decimal")]
+    crate::DecimalCost::export_all_to(dir)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MarkupOverlay, ModelType, Pricing};
+
+    #[test]
+    fn test_declarations() {
+        assert_eq!(ModelType::inline(), "string");
+        assert_eq!(
+            Pricing::inline(),
+            "TextPricing | Array<ImagePricing> | AudioPricing | VideoPricing | RealtimePricing"
+        );
+        assert!(AiPricingJson::decl().contains("markupOverlay?: MarkupOverlay"));
+        assert_eq!(
+            MarkupOverlay::inline(),
+            "{ [key in string]?: { imageAdjustment: number, textAdjustment: number, } }"
+        );
+    }
+
+    #[test]
+    fn test_export_typescript() {
+        let dir = std::env::temp_dir().join(format!("ai-pricing-ts-{}", std::process::id()));
+        export_typescript(&dir).unwrap();
+
+        let model = std::fs::read_to_string(dir.join("Model.ts")).unwrap();
+        assert!(model.contains("export type Model = {"));
+        assert!(dir.join("ValidationReport.ts").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
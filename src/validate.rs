@@ -0,0 +1,845 @@
+use crate::{
+    AiPricingJson, AuthScheme, CostMode, Discount, Env, Model, ModelType, PricingError, Promotion,
+    Provider, RateLimits, ResolvedModel, TextPricing,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Markups above this percentage are reported as a warning.
+pub const MARKUP_WARNING_PERCENTAGE: f64 = 100.0;
+
+/// Relative tolerance when checking that per-1K and per-1M prices agree.
+const PER1_TOLERANCE: f64 = 1e-9;
+
+// ------------------
+// Report
+// ------------------
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// The data should not be published as-is.
+    Error,
+    /// Suspicious but not necessarily wrong.
+    Warning,
+}
+
+/// A single problem found in pricing data.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// Where the problem is, e.g. `providers[openai].models[gpt-4o].pricing`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}: {}", severity, self.path, self.message)
+    }
+}
+
+/// Every problem found by [`AiPricingJson::validate`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// True when there are no errors. Warnings don't count.
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Turn a report with errors into a [`PricingError::Validation`] listing them.
+    pub fn into_result(self) -> Result<(), PricingError> {
+        if self.is_ok() {
+            return Ok(());
+        }
+        let errors: Vec<String> = self.errors().map(ToString::to_string).collect();
+        Err(PricingError::Validation(errors.join("; ")))
+    }
+
+    pub(crate) fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, path, message);
+    }
+
+    fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, path, message);
+    }
+
+    fn push(&mut self, severity: Severity, path: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            severity,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------
+// Checks
+// ------------------
+
+impl AiPricingJson {
+    /// Check the pricing data for problems that deserialization can't catch.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        if self.metered_price_id.is_empty() {
+            report.error("meteredPriceId", "metered price ID is empty");
+        }
+        if self.providers.is_empty() {
+            report.error("providers", "no providers");
+        }
+        if let Some(currency) = &self.currency {
+            if currency.len() != 3 || !currency.bytes().all(|b| b.is_ascii_uppercase()) {
+                report.error(
+                    "currency",
+                    format!("{:?} is not an ISO 4217 currency code", currency),
+                );
+            }
+        }
+
+        for (plan, adjustment) in &self.markup_overlay.plans {
+            let path = format!("markupOverlay.{}", plan);
+            if plan.is_empty() {
+                report.error(&path, "plan ID is empty");
+            }
+            for (field, value) in [
+                ("imageAdjustment", adjustment.image_adjustment),
+                ("textAdjustment", adjustment.text_adjustment),
+            ] {
+                if !value.is_finite() {
+                    report.error(
+                        format!("{}.{}", path, field),
+                        format!("adjustment {} is not a number", value),
+                    );
+                }
+            }
+        }
+
+        let mut promotion_ids = HashSet::new();
+        for promotion in &self.promotions {
+            let path = format!("promotions[{}]", promotion.id);
+            if promotion.id.is_empty() {
+                report.error(&path, "promotion ID is empty");
+            } else if !promotion_ids.insert(promotion.id.as_str()) {
+                report.error(&path, "duplicate promotion ID");
+            }
+            match promotion.discount {
+                Discount::Percentage(percentage) if !(0.0..=100.0).contains(&percentage) => {
+                    report.error(
+                        format!("{}.discount", path),
+                        format!("percentage {} is outside 0..=100", percentage),
+                    );
+                }
+                Discount::Fixed(amount) => check_price(&mut report, &path, "discount", amount),
+                Discount::Percentage(_) => {}
+            }
+            validate_promotion_dates(promotion, &path, &mut report);
+        }
+
+        let mut provider_keys = HashSet::new();
+        for provider in &self.providers {
+            let path = format!("providers[{}]", provider.key);
+            if !provider_keys.insert(provider.key.as_str()) {
+                report.error(&path, "duplicate provider key");
+            }
+            validate_provider(provider, &path, &mut report);
+        }
+
+        report
+    }
+
+    /// [`validate`](Self::validate), plus the checks that only apply to data
+    /// published for `env`. Prod data must carry Stripe price IDs for every
+    /// billable text model.
+    pub fn validate_for_env(&self, env: &Env) -> ValidationReport {
+        let mut report = self.validate();
+
+        if *env == Env::Prod {
+            for (provider, model) in self.all_models() {
+                if model.model_type != ModelType::Text || is_unbillable(model) {
+                    continue;
+                }
+                let path = format!(
+                    "providers[{}].models[{}].prodPriceIds",
+                    provider.key, model.key
+                );
+                let ids = model.prod_price_ids.clone().unwrap_or_default();
+                if ids.input.is_none() {
+                    report.error(&path, "missing input price ID");
+                }
+                if ids.output.is_none() {
+                    report.error(&path, "missing output price ID");
+                }
+            }
+        }
+
+        report
+    }
+}
+
+fn validate_promotion_dates(promotion: &Promotion, path: &str, report: &mut ValidationReport) {
+    let mut parse = |field: &str, date: &Option<String>| {
+        let date = date.as_ref()?;
+        let parsed = crate::promotions::promotion_time(date);
+        if parsed.is_none() {
+            report.error(
+                format!("{}.{}", path, field),
+                format!("{:?} is not a date", date),
+            );
+        }
+        parsed
+    };
+    let starts = parse("starts", &promotion.starts);
+    let ends = parse("ends", &promotion.ends);
+    if let (Some(starts), Some(ends)) = (starts, ends) {
+        if ends <= starts {
+            report.error(format!("{}.ends", path), "promotion ends before it starts");
+        }
+    }
+}
+
+fn is_unbillable(model: &Model) -> bool {
+    !model.is_available()
+}
+
+fn validate_provider(provider: &Provider, path: &str, report: &mut ValidationReport) {
+    let markup = &provider.markup;
+    for (field, percentage) in [
+        ("imagePercentage", markup.image_percentage),
+        ("textPercentage", markup.text_percentage),
+    ] {
+        check_markup(report, &format!("{}.markup.{}", path, field), percentage);
+    }
+
+    let threshold = &provider.moderation_threshold;
+    let scores = threshold
+        .category_score
+        .iter()
+        .map(|(category, score)| (format!("categoryScore.{}", category), score));
+    for (field, score) in std::iter::once(("general".to_string(), threshold.general)).chain(scores)
+    {
+        if !(0.0..=1.0).contains(&score) {
+            report.error(
+                format!("{}.moderationThreshold.{}", path, field),
+                format!("threshold {} is outside 0..=1", score),
+            );
+        }
+    }
+
+    if let Some(auth) = &provider.auth {
+        let auth_path = format!("{}.auth", path);
+        for name in &auth.env_vars {
+            if !crate::auth::is_env_var_name(name) {
+                // Don't echo the value back: it may be a pasted secret.
+                report.error(
+                    format!("{}.envVars", auth_path),
+                    "entry is not an environment variable name",
+                );
+            }
+        }
+        if auth.scheme == AuthScheme::Header && auth.header.is_none() {
+            report.error(&auth_path, "header scheme without a header");
+        }
+    }
+
+    if let Some(limits) = &provider.rate_limits {
+        check_rate_limits(report, path, limits);
+    }
+
+    if provider.models.is_empty() {
+        report.warning(format!("{}.models", path), "provider has no models");
+    }
+
+    let mut model_keys = HashSet::new();
+    for model in &provider.models {
+        let model_path = format!("{}.models[{}]", path, model.key);
+        if model.key.is_empty() {
+            report.error(&model_path, "model key is empty");
+        } else if !model_keys.insert(model.key.as_str()) {
+            report.error(&model_path, "duplicate model key");
+        }
+        validate_model(model, &model_path, report);
+        if model.replacement_model_key.is_some() {
+            if let Err(err) = provider.resolve_replacement(&model.key) {
+                report.error(
+                    format!("{}.replacementModelKey", model_path),
+                    err.to_string(),
+                );
+            }
+        }
+    }
+
+    let mut deployment_names = HashSet::new();
+    for deployment in &provider.deployments {
+        let deployment_path = format!("{}.deployments[{}]", path, deployment.name);
+        if deployment.name.is_empty() {
+            report.error(&deployment_path, "deployment name is empty");
+        } else if !deployment_names.insert(deployment.name.as_str()) {
+            report.error(&deployment_path, "duplicate deployment name");
+        }
+        if provider.find_model(&deployment.model_key).is_none() {
+            report.error(
+                format!("{}.modelKey", deployment_path),
+                format!("no model {}", deployment.model_key),
+            );
+        }
+    }
+
+    let mut aliases = HashSet::new();
+    for model in &provider.models {
+        for alias in &model.aliases {
+            let alias_path = format!("{}.models[{}].aliases[{}]", path, model.key, alias);
+            if model_keys.contains(alias.as_str()) {
+                report.error(alias_path, "alias shadows a model key");
+            } else if !aliases.insert(alias.as_str()) {
+                report.error(alias_path, "duplicate model alias");
+            }
+        }
+    }
+}
+
+fn validate_model(model: &Model, path: &str, report: &mut ValidationReport) {
+    let pricing_path = format!("{}.pricing", path);
+
+    for (field, date) in [
+        ("added", Some(&model.added)),
+        ("created", Some(&model.created)),
+        ("deprecationDate", model.deprecation_date.as_ref()),
+    ]
+    .into_iter()
+    .filter_map(|(field, date)| Some((field, date?)))
+    {
+        if crate::promotions::promotion_time(date).is_none() {
+            report.error(
+                format!("{}.{}", path, field),
+                format!("{:?} is not a date", date),
+            );
+        }
+    }
+
+    match ResolvedModel::try_from(model) {
+        Ok(ResolvedModel::Text(text)) => validate_text_pricing(text.pricing, &pricing_path, report),
+        Ok(ResolvedModel::Image(image)) => {
+            if image.pricing.is_empty() {
+                report.error(&pricing_path, "image model has no sizes");
+            }
+            let mut sizes = HashSet::new();
+            for size in image.pricing {
+                let size_path = format!("{}[{}]", pricing_path, size.label());
+                if !sizes.insert((size.size.as_str(), size.quality.as_deref())) {
+                    report.error(&size_path, "duplicate image size");
+                }
+                check_price(report, &size_path, "costPerImage", size.cost_per_image);
+            }
+        }
+        Ok(ResolvedModel::Audio(audio)) => {
+            let pricing = audio.pricing;
+            if pricing.transcription_per_minute.is_none() && pricing.tts_per1_k_characters.is_none()
+            {
+                report.error(&pricing_path, "audio model has no prices");
+            }
+            if let Some(price) = pricing.transcription_per_minute {
+                check_price(report, &pricing_path, "transcriptionPerMinute", price);
+            }
+            if let Some(price) = pricing.tts_per1_k_characters {
+                check_price(report, &pricing_path, "ttsPer1KCharacters", price);
+            }
+        }
+        Ok(ResolvedModel::Video(video)) => {
+            if video.pricing.cost_per_second.is_empty() {
+                report.error(&pricing_path, "video model has no resolutions");
+            }
+            for (resolution, price) in &video.pricing.cost_per_second {
+                let field = format!("costPerSecond.{}", resolution);
+                check_price(report, &pricing_path, &field, *price);
+            }
+        }
+        Ok(ResolvedModel::Realtime(realtime)) => {
+            let pricing = realtime.pricing;
+            let audio_rates = [
+                ("audioInputPerMinute", pricing.audio_input_per_minute),
+                ("audioOutputPerMinute", pricing.audio_output_per_minute),
+            ];
+            for (field, price) in audio_rates {
+                check_price(report, &pricing_path, field, price);
+            }
+            validate_text_pricing(&pricing.text, &pricing_path, report);
+        }
+        Err(_) if matches!(model.model_type, ModelType::Other(_)) => {
+            report.warning(path, format!("unknown model type {}", model.model_type));
+        }
+        Err(err) => report.error(&pricing_path, err.to_string()),
+    }
+
+    if let (Some(window), Some(max_output)) = (model.context_window, model.max_output_tokens) {
+        if max_output > window {
+            report.error(
+                format!("{}.maxOutputTokens", path),
+                format!(
+                    "{} is larger than the context window ({})",
+                    max_output, window
+                ),
+            );
+        }
+    }
+
+    if let Some(arn) = &model.inference_profile_arn {
+        if let Err(err) = arn.parse::<crate::InferenceProfileArn>() {
+            report.error(format!("{}.inferenceProfileArn", path), err.to_string());
+        }
+    }
+
+    for (region, pricing) in &model.regions {
+        let region_path = format!("{}.regions.{}", path, region);
+        if region.is_empty() {
+            report.error(&region_path, "region is empty");
+        }
+        validate_text_pricing(pricing, &region_path, report);
+    }
+
+    if let Some(markup) = &model.markup {
+        for (field, percentage) in [
+            ("imagePercentage", markup.image_percentage),
+            ("textPercentage", markup.text_percentage),
+        ] {
+            let Some(percentage) = percentage else {
+                continue;
+            };
+            check_markup(report, &format!("{}.markup.{}", path, field), percentage);
+        }
+    }
+
+    if let Some(limits) = &model.rate_limits {
+        check_rate_limits(report, path, limits);
+    }
+
+    if let Some(price) = model.per_request {
+        check_price(report, path, "perRequest", price);
+    }
+    for (tool, price) in &model.tool_pricing {
+        check_price(report, path, &format!("toolPricing.{}", tool), *price);
+    }
+
+    if let Some(fine_tune) = &model.fine_tuning {
+        let fine_tune_path = format!("{}.fineTuning", path);
+        check_price(
+            report,
+            &fine_tune_path,
+            "trainingPer1M",
+            fine_tune.training_per1_m,
+        );
+        check_price(
+            report,
+            &fine_tune_path,
+            "hostedInputPer1M",
+            fine_tune.hosted_input_per1_m,
+        );
+        check_price(
+            report,
+            &fine_tune_path,
+            "hostedOutputPer1M",
+            fine_tune.hosted_output_per1_m,
+        );
+        if let Some(price) = fine_tune.storage_per_hour {
+            check_price(report, &fine_tune_path, "storagePerHour", price);
+        }
+    }
+}
+
+fn validate_text_pricing(pricing: &TextPricing, path: &str, report: &mut ValidationReport) {
+    check_price(report, path, "inputPer1K", pricing.input_per1_k);
+    check_price(report, path, "inputPer1M", pricing.input_per1_m);
+    check_price(report, path, "outputPer1K", pricing.output_per1_k);
+    check_price(report, path, "outputPer1M", pricing.output_per1_m);
+    let mut previous_limit = Some(0);
+    for (i, tier) in pricing.tiers.iter().enumerate() {
+        let tier_path = format!("{}.tiers[{}]", path, i);
+        check_price(report, &tier_path, "inputPer1M", tier.input_per1_m);
+        check_price(report, &tier_path, "outputPer1M", tier.output_per1_m);
+        match (previous_limit, tier.up_to_tokens) {
+            (None, _) => report.error(&tier_path, "tier follows an unbounded tier"),
+            (Some(previous), Some(limit)) if limit <= previous => {
+                report.error(&tier_path, "tiers are not in ascending order")
+            }
+            _ => {}
+        }
+        previous_limit = tier.up_to_tokens;
+    }
+    for (ttl, price) in &pricing.cache_write_per1_m_by_ttl {
+        check_price(
+            report,
+            path,
+            &format!("cacheWritePer1MByTtl.{}", ttl),
+            *price,
+        );
+    }
+
+    check_per1_pair(
+        report,
+        path,
+        "input",
+        pricing.input_per1_k,
+        pricing.input_per1_m,
+    );
+    check_per1_pair(
+        report,
+        path,
+        "output",
+        pricing.output_per1_k,
+        pricing.output_per1_m,
+    );
+    let optional_pairs = [
+        (
+            "cachedInput",
+            pricing.cached_input_per1_k,
+            pricing.cached_input_per1_m,
+        ),
+        (
+            "reasoningOutput",
+            pricing.reasoning_output_per1_k,
+            pricing.reasoning_output_per1_m,
+        ),
+        (
+            "cacheWrite",
+            pricing.cache_write_per1_k,
+            pricing.cache_write_per1_m,
+        ),
+        (
+            "batchInput",
+            pricing.batch_input_per1_k,
+            pricing.batch_input_per1_m,
+        ),
+        (
+            "batchOutput",
+            pricing.batch_output_per1_k,
+            pricing.batch_output_per1_m,
+        ),
+        (
+            "imageInput",
+            pricing.image_input_per1_k,
+            pricing.image_input_per1_m,
+        ),
+    ];
+    for (field, per1_k, per1_m) in optional_pairs {
+        if let Some(price) = per1_k {
+            check_price(report, path, &format!("{}Per1K", field), price);
+        }
+        if let Some(price) = per1_m {
+            check_price(report, path, &format!("{}Per1M", field), price);
+        }
+        if let (Some(per1_k), Some(per1_m)) = (per1_k, per1_m) {
+            check_per1_pair(report, path, field, per1_k, per1_m);
+        }
+    }
+
+    if pricing.cached_input_rate_per1_m() > pricing.input_per1_m {
+        report.warning(path, "cached input is priced above regular input");
+    }
+    if pricing.input_rate_per1_m(CostMode::Batch) > pricing.input_per1_m
+        || pricing.output_rate_per1_m(CostMode::Batch) > pricing.output_per1_m
+    {
+        report.warning(path, "batch is priced above standard");
+    }
+}
+
+fn check_price(report: &mut ValidationReport, path: &str, field: &str, price: f64) {
+    if !price.is_finite() || price < 0.0 {
+        report.error(
+            format!("{}.{}", path, field),
+            format!("price {} is negative or not a number", price),
+        );
+    }
+}
+
+fn check_markup(report: &mut ValidationReport, path: &str, percentage: f64) {
+    if !percentage.is_finite() || percentage < 0.0 {
+        report.error(path, format!("markup {} is out of bounds", percentage));
+    } else if percentage > MARKUP_WARNING_PERCENTAGE {
+        report.warning(path, format!("markup {}% is unusually high", percentage));
+    }
+}
+
+fn check_rate_limits(report: &mut ValidationReport, path: &str, limits: &RateLimits) {
+    for (field, limit) in [
+        ("tpm", limits.tpm),
+        ("rpm", limits.rpm),
+        ("concurrent", limits.concurrent),
+    ] {
+        if limit == Some(0) {
+            report.error(
+                format!("{}.rateLimits.{}", path, field),
+                "limit must be positive",
+            );
+        }
+    }
+}
+
+fn check_per1_pair(
+    report: &mut ValidationReport,
+    path: &str,
+    field: &str,
+    per1_k: f64,
+    per1_m: f64,
+) {
+    let expected = per1_k * 1_000.0;
+    if (expected - per1_m).abs() > PER1_TOLERANCE * expected.abs().max(per1_m.abs()).max(1.0) {
+        report.error(
+            format!("{}.{}Per1M", path, field),
+            format!(
+                "{}Per1K ({}) x 1000 does not match {}Per1M ({})",
+                field, per1_k, field, per1_m
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+    use crate::Pricing;
+
+    #[test]
+    fn test_fixture_is_valid() {
+        let report = pricing().validate();
+        assert!(report.is_ok(), "unexpected issues:\n{}", report);
+        assert_eq!(report.warnings().count(), 0);
+    }
+
+    #[test]
+    fn test_reports_problems() {
+        let mut pricing = pricing();
+        let openai = &mut pricing.providers[0];
+        openai.markup.text_percentage = -5.0;
+        let duplicate = openai.models[0].clone();
+        openai.models.push(duplicate);
+        openai.models[1].markup = Some(crate::MarkupOverride {
+            text_percentage: Some(f64::NAN),
+            ..Default::default()
+        });
+        if let Some(Pricing::TextPricing(text)) = &mut openai.models[0].pricing {
+            text.input_per1_m = 25.0;
+            text.output_per1_k = -0.01;
+        }
+        pricing.currency = Some("euro".to_string());
+        pricing.providers[1].models[0].inference_profile_arn = Some("claude".to_string());
+        pricing.markup_overlay.plans.insert(
+            "pro".to_string(),
+            crate::PlanMarkup {
+                text_adjustment: f64::INFINITY,
+                ..Default::default()
+            },
+        );
+
+        let report = pricing.validate();
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert!(paths.contains(&"providers[openai].markup.textPercentage"));
+        assert!(paths.contains(&"providers[openai].models[dall-e-3].markup.textPercentage"));
+        assert!(paths.contains(&"providers[openai].models[gpt-4o]"));
+        assert!(paths.contains(&"providers[openai].models[gpt-4o].pricing.inputPer1M"));
+        assert!(paths.contains(&"providers[openai].models[gpt-4o].pricing.outputPer1K"));
+        assert!(paths.contains(&"currency"));
+        assert!(paths.contains(&"providers[bedrock].models[claude-3-5-sonnet].inferenceProfileArn"));
+        assert!(paths.contains(&"markupOverlay.pro.textAdjustment"));
+        assert!(report.into_result().is_err());
+    }
+
+    #[test]
+    fn test_reports_bad_promotions() {
+        let mut pricing = pricing();
+        pricing.promotions = serde_json::from_value(serde_json::json!([
+            { "id": "launch", "discount": { "type": "percentage", "value": 120.0 } },
+            { "id": "launch", "discount": { "type": "fixed", "value": -1.0 } },
+            {
+                "id": "backwards",
+                "discount": { "type": "fixed", "value": 1.0 },
+                "starts": "2025-01-01",
+                "ends": "2024-12-01"
+            }
+        ]))
+        .expect("Failed to parse");
+
+        let report = pricing.validate();
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert!(paths.contains(&"promotions[launch].discount"));
+        assert!(paths.contains(&"promotions[launch]"));
+        assert!(paths.contains(&"promotions[backwards].ends"));
+    }
+
+    #[test]
+    fn test_reports_bad_deployments() {
+        let mut pricing = pricing();
+        pricing.providers[0].deployments = serde_json::from_value(serde_json::json!([
+            { "name": "chat", "modelKey": "gpt-4o" },
+            { "name": "chat", "modelKey": "gpt-35-turbo" }
+        ]))
+        .expect("Failed to parse");
+
+        let report = pricing.validate();
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "providers[openai].deployments[chat]",
+                "providers[openai].deployments[chat].modelKey",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_bad_auth() {
+        let mut pricing = pricing();
+        pricing.providers[0].auth = serde_json::from_value(serde_json::json!({
+            "scheme": "header",
+            "envVars": ["OPENAI_API_KEY", "sk-proj-abc123"]
+        }))
+        .expect("Failed to parse");
+
+        let report = pricing.validate();
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["providers[openai].auth.envVars", "providers[openai].auth"]
+        );
+        assert!(!report.to_string().contains("sk-proj"));
+    }
+
+    #[test]
+    fn test_reports_zero_rate_limits() {
+        let mut pricing = pricing();
+        let openai = &mut pricing.providers[0];
+        openai.rate_limits = Some(RateLimits {
+            rpm: Some(0),
+            ..RateLimits::default()
+        });
+        openai.models[0].rate_limits = Some(RateLimits {
+            tpm: Some(30_000),
+            concurrent: Some(0),
+            ..RateLimits::default()
+        });
+
+        let report = pricing.validate();
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "providers[openai].rateLimits.rpm",
+                "providers[openai].models[gpt-4o].rateLimits.concurrent",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_conflicting_aliases() {
+        let mut pricing = pricing();
+        let openai = &mut pricing.providers[0];
+        openai.models[0].aliases = vec!["gpt-4o-latest".to_string(), "dall-e-3".to_string()];
+        openai.models[1].aliases = vec!["gpt-4o-latest".to_string()];
+
+        let report = pricing.validate();
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "providers[openai].models[gpt-4o].aliases[dall-e-3]",
+                "providers[openai].models[dall-e-3].aliases[gpt-4o-latest]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_misordered_tiers() {
+        let mut pricing = pricing();
+        let Some(Pricing::TextPricing(text)) = &mut pricing.providers[0].models[0].pricing else {
+            panic!("gpt-4o should have text pricing");
+        };
+        text.tiers = serde_json::from_value(serde_json::json!([
+            { "upToTokens": 200000, "inputPer1M": 2.5, "outputPer1M": 10.0 },
+            { "upToTokens": 128000, "inputPer1M": 5.0, "outputPer1M": 20.0 },
+            { "inputPer1M": 5.0, "outputPer1M": 20.0 },
+            { "inputPer1M": 5.0, "outputPer1M": 20.0 }
+        ]))
+        .unwrap();
+
+        let report = pricing.validate();
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "providers[openai].models[gpt-4o].pricing.tiers[1]",
+                "providers[openai].models[gpt-4o].pricing.tiers[3]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_bad_dates() {
+        let mut pricing = pricing();
+        pricing.providers[1].models[0].added = "2024-13-01".to_string();
+
+        let report = pricing.validate();
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["providers[bedrock].models[claude-3-5-sonnet].added"]
+        );
+    }
+
+    #[test]
+    fn test_prod_requires_price_ids() {
+        let pricing = pricing();
+        assert!(pricing.validate_for_env(&Env::Dev).is_ok());
+
+        // claude-3-5-sonnet is billable but has no prod price IDs.
+        let report = pricing.validate_for_env(&Env::Prod);
+        let paths: Vec<&str> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "providers[bedrock].models[claude-3-5-sonnet].prodPriceIds",
+                "providers[bedrock].models[claude-3-5-sonnet].prodPriceIds",
+            ]
+        );
+    }
+}
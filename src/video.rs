@@ -0,0 +1,73 @@
+use crate::{Model, Pricing, PricingError, VideoPricing};
+use std::time::Duration;
+
+// ------------------
+// Video costs
+// ------------------
+
+impl VideoPricing {
+    /// The resolutions offered, e.g. `720p`, in sorted order.
+    pub fn resolutions(&self) -> impl Iterator<Item = &str> {
+        self.cost_per_second.keys().map(String::as_str)
+    }
+
+    /// Provider cost of generating `duration` of video at `resolution`.
+    pub fn video_cost(&self, duration: Duration, resolution: &str) -> Result<f64, PricingError> {
+        let per_second = self.cost_per_second.get(resolution).ok_or_else(|| {
+            PricingError::UnknownVideoResolution {
+                resolution: resolution.to_string(),
+                available: self.resolutions().map(str::to_string).collect(),
+            }
+        })?;
+        Ok(duration.as_secs_f64() * per_second)
+    }
+}
+
+impl Model {
+    /// Provider cost of generating `duration` of video at `resolution` with
+    /// this model.
+    pub fn video_cost(&self, duration: Duration, resolution: &str) -> Result<f64, PricingError> {
+        match &self.pricing {
+            Some(Pricing::VideoPricing(video)) => video.video_cost(duration, resolution),
+            _ => Err(PricingError::Validation(format!(
+                "model {} has no video pricing",
+                self.key
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ModelType;
+    use serde_json::json;
+
+    #[test]
+    fn test_video_cost() {
+        let model: Model = serde_json::from_value(json!({
+            "added": "2025-01-01",
+            "created": "2024-12-09",
+            "key": "sora",
+            "type": "video",
+            "pricing": { "costPerSecond": { "720p": 0.1, "1080p": "0.3" } }
+        }))
+        .expect("Failed to parse");
+        assert_eq!(model.model_type, ModelType::Video);
+
+        let cost = model
+            .video_cost(Duration::from_millis(4_500), "1080p")
+            .unwrap();
+        assert!((cost - 1.35).abs() < 1e-12);
+
+        match model.video_cost(Duration::from_secs(1), "4k") {
+            Err(PricingError::UnknownVideoResolution { available, .. }) => {
+                assert_eq!(available, ["1080p", "720p"])
+            }
+            other => panic!("expected an unknown resolution, got {:?}", other),
+        }
+
+        let json = serde_json::to_value(&model).unwrap();
+        assert_eq!(json["pricing"]["costPerSecond"]["1080p"], 0.3);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{CostBreakdown, Modality, ModelFeature, Pricing, PricingError, Provider, TextPricing};
+use serde::{Deserialize, Serialize};
+
+const TOKENS_PER_1M: f64 = 1_000_000.0;
+
+// OpenAI: images are fit within 2048x2048, scaled so the short side is at
+// most 768, then billed per 512px tile plus a base charge.
+const OPENAI_MAX_SIDE: f64 = 2_048.0;
+const OPENAI_MAX_SHORT_SIDE: f64 = 768.0;
+const OPENAI_TILE_SIZE: f64 = 512.0;
+const OPENAI_BASE_TOKENS: u64 = 85;
+const OPENAI_TILE_TOKENS: u64 = 170;
+
+// Anthropic: images are scaled so the long side is at most 1568, then billed
+// at one token per 750 pixels.
+const ANTHROPIC_MAX_SIDE: f64 = 1_568.0;
+const ANTHROPIC_PIXELS_PER_TOKEN: f64 = 750.0;
+
+// ------------------
+// Vision input
+// ------------------
+
+/// The detail level requested for an input image.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub enum ImageDetail {
+    /// A fixed, low-resolution rendering.
+    Low,
+    High,
+    /// Let the provider choose; billed as `High`.
+    #[default]
+    Auto,
+}
+
+/// Tokens billed for an input image of `width` x `height` pixels, using the
+/// formula of the provider with `provider_key`. `None` for providers whose
+/// formula isn't known.
+///
+/// `openai` and `azure` use OpenAI's tiling; `anthropic` and `bedrock` use
+/// Anthropic's pixel count, which ignores `detail`.
+pub fn vision_tokens(
+    provider_key: &str,
+    width: u32,
+    height: u32,
+    detail: ImageDetail,
+) -> Option<u64> {
+    match provider_key {
+        "openai" | "azure" => Some(openai_tokens(width, height, detail)),
+        "anthropic" | "bedrock" => Some(anthropic_tokens(width, height)),
+        _ => None,
+    }
+}
+
+fn openai_tokens(width: u32, height: u32, detail: ImageDetail) -> u64 {
+    if detail == ImageDetail::Low {
+        return OPENAI_BASE_TOKENS;
+    }
+
+    let (mut width, mut height) = (width as f64, height as f64);
+    let fit = (OPENAI_MAX_SIDE / width.max(height)).min(1.0);
+    (width, height) = (width * fit, height * fit);
+    let shrink = (OPENAI_MAX_SHORT_SIDE / width.min(height)).min(1.0);
+    (width, height) = (width * shrink, height * shrink);
+
+    let tiles = (width / OPENAI_TILE_SIZE).ceil() * (height / OPENAI_TILE_SIZE).ceil();
+    OPENAI_BASE_TOKENS + OPENAI_TILE_TOKENS * tiles as u64
+}
+
+fn anthropic_tokens(width: u32, height: u32) -> u64 {
+    let (width, height) = (width as f64, height as f64);
+    let fit = (ANTHROPIC_MAX_SIDE / width.max(height)).min(1.0);
+    (width * fit * height * fit / ANTHROPIC_PIXELS_PER_TOKEN).ceil() as u64
+}
+
+impl TextPricing {
+    /// Price per 1M image input tokens, falling back to the per-1K price and
+    /// then to the regular input price for models without an image rate.
+    pub fn image_input_rate_per1_m(&self) -> f64 {
+        self.image_input_per1_m
+            .or(self.image_input_per1_k.map(|per1_k| per1_k * 1_000.0))
+            .unwrap_or(self.input_per1_m)
+    }
+}
+
+impl Provider {
+    /// Provider cost and marked-up customer price of sending one image of
+    /// `width` x `height` pixels to one of this provider's vision models.
+    #[deprecated(note = "ignores promotions; use `AiPricingJson::vision_input_cost`")]
+    pub fn vision_input_cost(
+        &self,
+        model_key: &str,
+        width: u32,
+        height: u32,
+        detail: ImageDetail,
+    ) -> Result<CostBreakdown, PricingError> {
+        let model = self.find_model(model_key).ok_or_else(|| {
+            PricingError::Validation(format!("provider {} has no model {}", self.key, model_key))
+        })?;
+        let Some(Pricing::TextPricing(pricing)) = &model.pricing else {
+            return Err(PricingError::Validation(format!(
+                "model {} has no text pricing",
+                model.key
+            )));
+        };
+        if !model.features.contains(&ModelFeature::Vision) {
+            return Err(PricingError::Validation(format!(
+                "model {} does not accept images",
+                model.key
+            )));
+        }
+        let tokens = vision_tokens(&self.key, width, height, detail).ok_or_else(|| {
+            PricingError::Validation(format!("no image token formula for provider {}", self.key))
+        })?;
+
+        let cost = tokens as f64 * pricing.image_input_rate_per1_m() / TOKENS_PER_1M;
+        Ok(self.apply_model_markup(model, cost, Modality::Text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_vision_tokens() {
+        assert_eq!(
+            vision_tokens("openai", 4096, 4096, ImageDetail::Low),
+            Some(85)
+        );
+        assert_eq!(
+            vision_tokens("openai", 1024, 1024, ImageDetail::High),
+            Some(765)
+        );
+        assert_eq!(
+            vision_tokens("openai", 2048, 4096, ImageDetail::Auto),
+            Some(1105)
+        );
+        assert_eq!(
+            vision_tokens("openai", 512, 512, ImageDetail::High),
+            Some(255)
+        );
+        assert_eq!(
+            vision_tokens("anthropic", 1092, 1092, ImageDetail::High),
+            Some(1590)
+        );
+        assert_eq!(
+            vision_tokens("bedrock", 3136, 1568, ImageDetail::Low),
+            Some(1640)
+        );
+        assert_eq!(vision_tokens("mistral", 512, 512, ImageDetail::High), None);
+    }
+
+    #[test]
+    fn test_vision_input_cost() {
+        let pricing = pricing();
+        let cost = pricing
+            .vision_input_cost("openai", "gpt-4o", 1024, 1024, ImageDetail::High)
+            .unwrap();
+        assert!((cost.provider_cost - 765.0 * 2.5 / 1_000_000.0).abs() < 1e-15);
+
+        assert!(pricing
+            .vision_input_cost("openai", "dall-e-3", 1024, 1024, ImageDetail::High)
+            .is_err());
+        assert!(pricing
+            .vision_input_cost(
+                "bedrock",
+                "claude-3-5-sonnet",
+                1024,
+                1024,
+                ImageDetail::High
+            )
+            .is_err());
+    }
+}
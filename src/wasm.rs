@@ -0,0 +1,60 @@
+use crate::parse::parse;
+use crate::{AiPricingJson, Env, PricingError, DEFAULT_BASE_URL};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use std::sync::Arc;
+
+// ------------------
+// Browser fetch
+// ------------------
+
+// On wasm32 the fetcher runs on the browser's event loop through reqwest's
+// `fetch` backend. There is no tokio runtime or filesystem, so the
+// `PricingClient` machinery (background refresh, TTLs, disk persistence) is
+// native-only and this module provides the plain cached fetch instead.
+
+static CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// Cached pricing data, keyed by environment.
+static AI_PRICING: Lazy<DashMap<Env, Arc<AiPricingJson>>> = Lazy::new(DashMap::new);
+
+/// Returns the AI pricing data, with optional cache-busting.
+///
+/// Each environment has its own cache entry. A cache-busting call fetches
+/// fresh data and replaces that environment's entry.
+pub async fn get_ai_pricing(
+    env: Env,
+    bust_cache: bool,
+) -> Result<Arc<AiPricingJson>, PricingError> {
+    if !bust_cache {
+        if let Some(cached) = AI_PRICING.get(&env) {
+            return Ok(Arc::clone(&cached));
+        }
+    }
+
+    let url = env.pricing_url(DEFAULT_BASE_URL);
+    let data = Arc::new(fetch(&url).await?);
+    AI_PRICING.insert(env, Arc::clone(&data));
+
+    Ok(data)
+}
+
+async fn fetch(url: &str) -> Result<AiPricingJson, PricingError> {
+    let request_error = |source| PricingError::Request {
+        source,
+        url: url.to_string(),
+    };
+
+    let resp = CLIENT.get(url).send().await.map_err(request_error)?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(PricingError::Http {
+            status,
+            url: url.to_string(),
+        });
+    }
+
+    let body = resp.bytes().await.map_err(request_error)?;
+    parse(&body, url)
+}
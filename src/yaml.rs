@@ -0,0 +1,75 @@
+use crate::parse::parse_value;
+use crate::{AiPricingJson, PricingError};
+use serde_json::Value;
+
+// ------------------
+// YAML
+// ------------------
+
+impl AiPricingJson {
+    /// Deserialize pricing YAML of any supported schema version. YAML lets
+    /// pricing authors keep comments next to the prices they explain.
+    pub fn from_yaml_str(yaml: &str) -> Result<AiPricingJson, PricingError> {
+        parse_yaml(yaml.as_bytes(), "YAML string")
+    }
+
+    /// Serialize to YAML. Comments in the source file are not preserved.
+    pub fn to_yaml_string(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+}
+
+/// Deserialize pricing YAML, attributing failures to `origin`. The document
+/// goes through JSON values first, so it's read exactly as the same JSON
+/// file would be.
+pub(crate) fn parse_yaml(body: &[u8], origin: &str) -> Result<AiPricingJson, PricingError> {
+    let value: Value = serde_yaml::from_slice(body).map_err(|source| PricingError::Yaml {
+        source,
+        url: origin.to_string(),
+    })?;
+    parse_value(value, origin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::pricing;
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let pricing = pricing();
+        let yaml = pricing.to_yaml_string().unwrap();
+        let round_trip = AiPricingJson::from_yaml_str(&yaml).unwrap();
+        assert_eq!(
+            serde_json::to_value(&round_trip).unwrap(),
+            serde_json::to_value(&pricing).unwrap()
+        );
+
+        let commented = format!("# Reviewed quarterly.\n{}", yaml);
+        assert!(AiPricingJson::from_yaml_str(&commented).is_ok());
+        assert!(matches!(
+            AiPricingJson::from_yaml_str("providers: [unclosed"),
+            Err(PricingError::Yaml { .. })
+        ));
+        assert!(matches!(
+            AiPricingJson::from_yaml_str("meteredPriceId: 12\nproviders: oops"),
+            Err(PricingError::Deserialize { .. })
+        ));
+    }
+
+    #[test]
+    fn test_yaml_files_detected_by_extension() {
+        let yaml = pricing().to_yaml_string().unwrap();
+        for ext in ["yaml", "yml"] {
+            let path = std::env::temp_dir().join(format!(
+                "ai-pricing-yaml-{}.{}",
+                std::process::id(),
+                ext
+            ));
+            std::fs::write(&path, &yaml).unwrap();
+            let loaded = AiPricingJson::from_path(&path);
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(loaded.expect("Failed to read YAML").providers.len(), 2);
+        }
+    }
+}